@@ -3,6 +3,7 @@
 use super::*;
 use crate::alloc::borrow::ToOwned;
 use crate::domain_registry::DomainConfig;
+use crate::runtime_registry::{acquire_runtime_code, do_upgrade_runtimes, ScheduledRuntimeUpgrade};
 use crate::staking::{do_reward_operators, OperatorConfig, OperatorStatus};
 use crate::staking_epoch::{do_finalize_domain_current_epoch, do_finalize_domain_epoch_staking};
 use crate::{DomainBlockNumberFor, Pallet as Domains};
@@ -13,6 +14,7 @@ use frame_support::traits::Hooks;
 use frame_support::weights::Weight;
 use frame_system::{Pallet as System, RawOrigin};
 use sp_core::crypto::UncheckedFrom;
+use sp_domains::storage::RawGenesis;
 use sp_domains::{
     dummy_opaque_bundle, DomainId, ExecutionReceipt, OperatorAllowList, OperatorId,
     OperatorPublicKey, RuntimeType,
@@ -202,11 +204,89 @@ mod benchmarks {
             .expect("scheduled upgrade must exist");
         assert_eq!(scheduled_upgrade.version.spec_version, 1);
         assert_eq!(
-            scheduled_upgrade.raw_genesis.get_runtime_code().unwrap(),
+            RuntimeCode::<T>::get(scheduled_upgrade.hash).unwrap(),
             runtime_blob
         );
     }
 
+    /// Benchmark applying a scheduled domain runtime upgrade in `on_initialize`, where `s` is the
+    /// size of the new runtime code and raw genesis storage, split evenly between the two.
+    #[benchmark]
+    fn upgrade_scheduled_runtime(s: Linear<1, 4_194_304>) {
+        let runtime_id = register_runtime::<T>();
+        let runtime_obj = RuntimeRegistry::<T>::get(runtime_id).expect("runtime object must exist");
+        let scheduled_at = frame_system::Pallet::<T>::current_block_number() + One::one();
+        let code_size = s / 2;
+        let code = vec![1u8; code_size as usize];
+        let code_hash = T::Hashing::hash(&code);
+        acquire_runtime_code::<T>(code_hash, code);
+        ScheduledRuntimeUpgrades::<T>::insert(
+            scheduled_at,
+            runtime_id,
+            ScheduledRuntimeUpgrade {
+                // Only the size of the raw genesis storage matters here
+                raw_genesis: RawGenesis::dummy(vec![0u8; (s - code_size) as usize]),
+                version: runtime_obj.version,
+                hash: code_hash,
+            },
+        );
+        ScheduledRuntimeUpgradeAt::<T>::insert(runtime_id, scheduled_at);
+
+        #[block]
+        {
+            do_upgrade_runtimes::<T>(scheduled_at);
+        }
+
+        let runtime_obj = RuntimeRegistry::<T>::get(runtime_id).expect("runtime object must exist");
+        assert_eq!(runtime_obj.runtime_upgrades, 1);
+        assert_eq!(runtime_obj.code_size, code_size);
+        assert!(!ScheduledRuntimeUpgradeAt::<T>::contains_key(runtime_id));
+    }
+
+    /// Benchmark `deregister_domain_runtime` extrinsic with the worst possible conditions:
+    /// - The runtime has a pending scheduled upgrade to release as well
+    /// - The upgrade history of the runtime is full
+    #[benchmark]
+    fn deregister_domain_runtime() {
+        let runtime_id = register_runtime::<T>();
+        let scheduled_at = schedule_runtime_upgrade::<T>(runtime_id);
+        let runtime_obj = RuntimeRegistry::<T>::get(runtime_id).expect("runtime object must exist");
+        for upgrade_index in 1..=T::MaxRuntimeUpgradeHistory::get() {
+            RuntimeUpgradeHistory::<T>::insert(
+                runtime_id,
+                upgrade_index,
+                (
+                    runtime_obj.version.clone(),
+                    runtime_obj.hash,
+                    runtime_obj.updated_at,
+                ),
+            );
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, runtime_id);
+
+        assert!(RuntimeRegistry::<T>::get(runtime_id).is_none());
+        assert!(ScheduledRuntimeUpgrades::<T>::get(scheduled_at, runtime_id).is_none());
+        assert!(!ScheduledRuntimeUpgradeAt::<T>::contains_key(runtime_id));
+        assert_eq!(
+            RuntimeUpgradeHistory::<T>::iter_prefix(runtime_id).count(),
+            0
+        );
+    }
+
+    #[benchmark]
+    fn cancel_domain_runtime_upgrade() {
+        let runtime_id = register_runtime::<T>();
+        let scheduled_at = schedule_runtime_upgrade::<T>(runtime_id);
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, runtime_id);
+
+        assert!(ScheduledRuntimeUpgrades::<T>::get(scheduled_at, runtime_id).is_none());
+        assert!(!ScheduledRuntimeUpgradeAt::<T>::contains_key(runtime_id));
+    }
+
     #[benchmark]
     fn instantiate_domain() {
         let creator = account("creator", 1, SEED);
@@ -417,6 +497,28 @@ mod benchmarks {
         runtime_id
     }
 
+    fn schedule_runtime_upgrade<T: Config>(runtime_id: RuntimeId) -> BlockNumberFor<T> {
+        let runtime_obj = RuntimeRegistry::<T>::get(runtime_id).expect("runtime object must exist");
+        let scheduled_at = frame_system::Pallet::<T>::current_block_number()
+            .checked_add(&T::DomainRuntimeUpgradeDelay::get())
+            .expect("must not overflow");
+        let code = vec![1u8; 1024];
+        let code_hash = T::Hashing::hash(&code);
+        acquire_runtime_code::<T>(code_hash, code);
+        ScheduledRuntimeUpgrades::<T>::insert(
+            scheduled_at,
+            runtime_id,
+            ScheduledRuntimeUpgrade {
+                raw_genesis: RawGenesis::dummy(vec![0u8; 1024]),
+                version: runtime_obj.version,
+                hash: code_hash,
+            },
+        );
+        ScheduledRuntimeUpgradeAt::<T>::insert(runtime_id, scheduled_at);
+
+        scheduled_at
+    }
+
     fn register_domain<T: Config>() -> DomainId {
         let creator = account("creator", 1, SEED);
         T::Currency::set_balance(
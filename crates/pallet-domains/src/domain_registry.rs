@@ -1,7 +1,7 @@
 //! Domain registry for domains
 
 use crate::block_tree::import_genesis_receipt;
-use crate::pallet::{DomainStakingSummary, NextEVMChainId};
+use crate::pallet::{DomainStakingSummary, NextEVMChainId, RuntimeDomainCount};
 use crate::runtime_registry::DomainRuntimeInfo;
 use crate::staking::StakingSummary;
 use crate::{
@@ -139,11 +139,14 @@ pub(crate) fn do_instantiate_domain<T: Config>(
                 chain_id: evm_chain_id,
             }
         }
+        RuntimeType::AutoId => DomainRuntimeInfo::AutoId,
     };
 
     let genesis_receipt = {
         let state_version = runtime_obj.version.state_version();
-        let raw_genesis = runtime_obj.into_complete_raw_genesis(domain_id, domain_runtime_info);
+        let raw_genesis = runtime_obj
+            .into_complete_raw_genesis::<T>(domain_id, domain_runtime_info)
+            .ok_or(Error::RuntimeNotFound)?;
         let state_root = raw_genesis.state_root::<DomainHashingFor<T>>(state_version);
         let genesis_block_hash = derive_domain_block_hash::<T::DomainHeader>(
             Zero::zero(),
@@ -161,6 +164,9 @@ pub(crate) fn do_instantiate_domain<T: Config>(
     };
     let genesis_receipt_hash = genesis_receipt.hash::<DomainHashingFor<T>>();
 
+    RuntimeDomainCount::<T>::mutate(domain_config.runtime_id, |domain_count| {
+        *domain_count = domain_count.saturating_add(1)
+    });
     let domain_obj = DomainObject {
         owner_account_id: owner_account_id.clone(),
         created_at,
@@ -219,8 +225,8 @@ pub(crate) fn do_update_domain_allow_list<T: Config>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::pallet::{DomainRegistry, NextDomainId, RuntimeRegistry};
-    use crate::runtime_registry::RuntimeObject;
+    use crate::pallet::{DomainRegistry, NextDomainId, RuntimeDomainCount, RuntimeRegistry};
+    use crate::runtime_registry::{acquire_runtime_code, RuntimeObject};
     use crate::tests::{new_test_ext, Test};
     use frame_support::assert_ok;
     use frame_support::traits::Currency;
@@ -271,7 +277,7 @@ mod tests {
                     runtime_type: Default::default(),
                     runtime_upgrades: 0,
                     hash: Default::default(),
-                    raw_genesis: RawGenesis::dummy(vec![1, 2, 3, 4]),
+                    raw_genesis: RawGenesis::default(),
                     version: RuntimeVersion {
                         spec_name: "test".into(),
                         spec_version: 1,
@@ -281,8 +287,10 @@ mod tests {
                     },
                     created_at: Default::default(),
                     updated_at: Default::default(),
+                    code_size: 4,
                 },
             );
+            acquire_runtime_code::<Test>(Default::default(), vec![1, 2, 3, 4]);
 
             // Failed to instantiate domain due to exceed max domain block size limit
             assert_eq!(
@@ -357,6 +365,7 @@ mod tests {
             assert_eq!(domain_obj.created_at, created_at);
             assert_eq!(domain_obj.domain_config, domain_config);
             assert_eq!(NextDomainId::<Test>::get(), 1.into());
+            assert_eq!(RuntimeDomainCount::<Test>::get(domain_config.runtime_id), 1);
             // Fund locked up thus can't withdraw, and usable balance is zero since ED is 1
             assert_eq!(Balances::usable_balance(creator), Zero::zero());
 
@@ -381,4 +390,73 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn test_domain_instantiation_by_runtime_type() {
+        let creator = 1u64;
+        let created_at = 0u64;
+
+        let mut ext = new_test_ext();
+        ext.execute_with(|| {
+            let runtime_obj = |runtime_type| RuntimeObject {
+                runtime_name: "runtime".to_owned(),
+                runtime_type,
+                runtime_upgrades: 0,
+                hash: Default::default(),
+                raw_genesis: RawGenesis::default(),
+                version: RuntimeVersion {
+                    spec_name: "test".into(),
+                    spec_version: 1,
+                    impl_version: 1,
+                    transaction_version: 1,
+                    ..Default::default()
+                },
+                created_at: Default::default(),
+                updated_at: Default::default(),
+                code_size: 4,
+            };
+            RuntimeRegistry::<Test>::insert(0, runtime_obj(RuntimeType::Evm));
+            RuntimeRegistry::<Test>::insert(1, runtime_obj(RuntimeType::AutoId));
+            acquire_runtime_code::<Test>(Default::default(), vec![1, 2, 3, 4]);
+            Balances::make_free_balance_be(
+                &creator,
+                2 * <Test as Config>::DomainInstantiationDeposit::get()
+                    + <Test as pallet_balances::Config>::ExistentialDeposit::get(),
+            );
+
+            let domain_config = |runtime_id| DomainConfig {
+                domain_name: "domain".to_owned(),
+                runtime_id,
+                max_block_size: 1,
+                max_block_weight: Weight::from_parts(1, 0),
+                bundle_slot_probability: (1, 1),
+                target_bundles_per_block: 1,
+                operator_allow_list: OperatorAllowList::Anyone,
+            };
+            let evm_chain_id = NextEVMChainId::<Test>::get();
+
+            let evm_domain_id =
+                do_instantiate_domain::<Test>(domain_config(0), creator, created_at).unwrap();
+            assert_eq!(
+                DomainRegistry::<Test>::get(evm_domain_id)
+                    .unwrap()
+                    .domain_runtime_info,
+                DomainRuntimeInfo::EVM {
+                    chain_id: evm_chain_id
+                }
+            );
+            assert_eq!(NextEVMChainId::<Test>::get(), evm_chain_id + 1);
+
+            // The AutoId domain doesn't take an EVM chain id.
+            let auto_id_domain_id =
+                do_instantiate_domain::<Test>(domain_config(1), creator, created_at).unwrap();
+            assert_eq!(
+                DomainRegistry::<Test>::get(auto_id_domain_id)
+                    .unwrap()
+                    .domain_runtime_info,
+                DomainRuntimeInfo::AutoId
+            );
+            assert_eq!(NextEVMChainId::<Test>::get(), evm_chain_id + 1);
+        });
+    }
 }
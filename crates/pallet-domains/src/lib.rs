@@ -26,6 +26,7 @@ mod tests;
 
 pub mod block_tree;
 pub mod domain_registry;
+pub mod migrations;
 pub mod runtime_registry;
 mod staking;
 mod staking_epoch;
@@ -39,7 +40,7 @@ use codec::{Decode, Encode};
 use frame_support::ensure;
 use frame_support::pallet_prelude::StorageVersion;
 use frame_support::traits::fungible::{Inspect, InspectHold};
-use frame_support::traits::{Get, Randomness as RandomnessT};
+use frame_support::traits::{Contains, Get, Randomness as RandomnessT};
 use frame_system::offchain::SubmitTransaction;
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
@@ -48,7 +49,8 @@ use sp_core::H256;
 use sp_domains::bundle_producer_election::BundleProducerElectionParams;
 use sp_domains::{
     DomainBlockLimit, DomainId, DomainInstanceData, ExecutionReceipt, OpaqueBundle, OperatorId,
-    OperatorPublicKey, RuntimeId, DOMAIN_EXTRINSICS_SHUFFLING_SEED_SUBJECT, EMPTY_EXTRINSIC_ROOT,
+    OperatorPublicKey, RuntimeId, RuntimeObjectInfo, DOMAIN_EXTRINSICS_SHUFFLING_SEED_SUBJECT,
+    EMPTY_EXTRINSIC_ROOT,
 };
 use sp_domains_fraud_proof::fraud_proof::{
     FraudProof, InvalidBlockFeesProof, InvalidDomainBlockHashProof,
@@ -64,6 +66,7 @@ use sp_runtime::{RuntimeAppPublic, SaturatedConversion, Saturating};
 use sp_std::boxed::Box;
 use sp_std::collections::btree_map::BTreeMap;
 use sp_std::vec::Vec;
+use sp_version::RuntimeVersion;
 pub use staking::OperatorConfig;
 use subspace_core_primitives::U256;
 use subspace_runtime_primitives::Balance;
@@ -108,7 +111,7 @@ pub type DomainHashingFor<T> = <<T as Config>::DomainHeader as Header>::Hashing;
 pub type ReceiptHashFor<T> = <<T as Config>::DomainHeader as Header>::Hash;
 
 /// The current storage version.
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 #[frame_support::pallet]
 mod pallet {
@@ -123,9 +126,9 @@ mod pallet {
         Error as DomainRegistryError,
     };
     use crate::runtime_registry::{
-        do_register_runtime, do_schedule_runtime_upgrade, do_upgrade_runtimes,
-        register_runtime_at_genesis, Error as RuntimeRegistryError, RuntimeObject,
-        ScheduledRuntimeUpgrade,
+        do_cancel_scheduled_runtime_upgrade, do_deregister_runtime, do_register_runtime,
+        do_schedule_runtime_upgrade, do_upgrade_runtimes, register_runtime_at_genesis,
+        Error as RuntimeRegistryError, RuntimeObject, ScheduledRuntimeUpgrade,
     };
     #[cfg(not(feature = "runtime-benchmarks"))]
     use crate::staking::do_reward_operators;
@@ -146,7 +149,7 @@ mod pallet {
     use domain_runtime_primitives::EVMChainId;
     use frame_support::pallet_prelude::*;
     use frame_support::traits::fungible::{InspectHold, Mutate, MutateHold};
-    use frame_support::traits::Randomness as RandomnessT;
+    use frame_support::traits::{Contains, Randomness as RandomnessT};
     use frame_support::weights::Weight;
     use frame_support::{Identity, PalletError};
     use frame_system::pallet_prelude::*;
@@ -298,6 +301,38 @@ mod pallet {
         /// The sudo account id
         #[pallet::constant]
         type SudoId: Get<Self::AccountId>;
+
+        /// Origin allowed to upgrade a domain runtime, usually root. Runtimes with a collective
+        /// can raise the bar for runtime upgrades above the other root calls, e.g. by requiring a
+        /// super-majority of the collective.
+        type RuntimeUpgradeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// The maximum number of past upgrades kept in the upgrade history of each domain runtime.
+        #[pallet::constant]
+        type MaxRuntimeUpgradeHistory: Get<u32>;
+
+        /// The maximum number of scheduled domain runtime upgrades applied in one block, the
+        /// remaining upgrades are deferred to the next block.
+        #[pallet::constant]
+        type MaxRuntimeUpgradesPerBlock: Get<u32>;
+
+        /// The maximum length of a domain runtime name, in bytes.
+        #[pallet::constant]
+        type MaxRuntimeNameLength: Get<u32>;
+
+        /// The maximum size of a domain runtime code together with the rest of its raw genesis
+        /// storage, in bytes.
+        #[pallet::constant]
+        type MaxDomainRuntimeCodeSize: Get<u32>;
+
+        /// Whether domain runtime upgrades are applied at the next block instead of after
+        /// [`Config::DomainRuntimeUpgradeDelay`], only meant for test networks.
+        #[pallet::constant]
+        type AllowImmediateRuntimeUpgrade: Get<bool>;
+
+        /// Domains that are frozen, bundles (and thus execution receipts) of these domains are
+        /// rejected.
+        type FrozenDomains: Contains<DomainId>;
     }
 
     #[pallet::pallet]
@@ -336,6 +371,29 @@ mod pallet {
     pub(super) type RuntimeRegistry<T: Config> =
         StorageMap<_, Identity, RuntimeId, RuntimeObject<BlockNumberFor<T>, T::Hash>, OptionQuery>;
 
+    /// The id of each registered domain runtime keyed by its name, used to keep the runtime names
+    /// unique.
+    #[pallet::storage]
+    pub(super) type RuntimeNames<T: Config> =
+        StorageMap<_, Blake2_128Concat, String, RuntimeId, OptionQuery>;
+
+    /// Number of domains instantiated with each domain runtime, a runtime can only be
+    /// deregistered once no domain uses it.
+    #[pallet::storage]
+    pub(super) type RuntimeDomainCount<T: Config> =
+        StorageMap<_, Identity, RuntimeId, u32, ValueQuery>;
+
+    /// Code of the domain runtimes keyed by the code hash, shared by the [`RuntimeRegistry`]
+    /// entries and the [`ScheduledRuntimeUpgrades`] with the same code.
+    #[pallet::storage]
+    pub(super) type RuntimeCode<T: Config> = StorageMap<_, Identity, T::Hash, Vec<u8>, OptionQuery>;
+
+    /// Number of [`RuntimeRegistry`] entries and [`ScheduledRuntimeUpgrades`] referencing each
+    /// [`RuntimeCode`] entry, the code is removed once it is no longer referenced.
+    #[pallet::storage]
+    pub(super) type RuntimeCodeRefCount<T: Config> =
+        StorageMap<_, Identity, T::Hash, u32, ValueQuery>;
+
     #[pallet::storage]
     pub(super) type ScheduledRuntimeUpgrades<T: Config> = StorageDoubleMap<
         _,
@@ -347,6 +405,25 @@ mod pallet {
         OptionQuery,
     >;
 
+    /// The block number at which the pending upgrade of each domain runtime is scheduled, so that
+    /// the upgrade in [`ScheduledRuntimeUpgrades`] can be found without iterating.
+    #[pallet::storage]
+    pub(super) type ScheduledRuntimeUpgradeAt<T: Config> =
+        StorageMap<_, Identity, RuntimeId, BlockNumberFor<T>, OptionQuery>;
+
+    /// The version, hash and block number of the past upgrades of each domain runtime, keyed by
+    /// the index of the upgrade, only the last `MaxRuntimeUpgradeHistory` upgrades are kept.
+    #[pallet::storage]
+    pub(super) type RuntimeUpgradeHistory<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        RuntimeId,
+        Identity,
+        u32,
+        (RuntimeVersion, T::Hash, BlockNumberFor<T>),
+        OptionQuery,
+    >;
+
     #[pallet::storage]
     pub(super) type NextOperatorId<T> = StorageValue<_, OperatorId, ValueQuery>;
 
@@ -582,6 +659,8 @@ mod pallet {
         InvalidExtrinsicRoot,
         /// This bundle duplicated with an already submitted bundle
         DuplicatedBundle,
+        /// The domain of the bundle is frozen.
+        FrozenDomain,
     }
 
     #[derive(TypeInfo, Encode, Decode, PalletError, Debug, PartialEq)]
@@ -703,10 +782,18 @@ mod pallet {
         DomainRuntimeUpgradeScheduled {
             runtime_id: RuntimeId,
             scheduled_at: BlockNumberFor<T>,
+            new_spec_version: u32,
         },
         DomainRuntimeUpgraded {
             runtime_id: RuntimeId,
         },
+        DomainRuntimeDeregistered {
+            runtime_id: RuntimeId,
+        },
+        DomainRuntimeUpgradeCancelled {
+            runtime_id: RuntimeId,
+            scheduled_at: BlockNumberFor<T>,
+        },
         OperatorRegistered {
             operator_id: OperatorId,
             domain_id: DomainId,
@@ -1057,17 +1144,11 @@ mod pallet {
             runtime_id: RuntimeId,
             raw_genesis_storage: Vec<u8>,
         ) -> DispatchResult {
-            ensure_root(origin)?;
+            T::RuntimeUpgradeOrigin::ensure_origin(origin)?;
 
             let block_number = frame_system::Pallet::<T>::current_block_number();
-            let scheduled_at =
-                do_schedule_runtime_upgrade::<T>(runtime_id, raw_genesis_storage, block_number)
-                    .map_err(Error::<T>::from)?;
-
-            Self::deposit_event(Event::DomainRuntimeUpgradeScheduled {
-                runtime_id,
-                scheduled_at,
-            });
+            do_schedule_runtime_upgrade::<T>(runtime_id, raw_genesis_storage, block_number)
+                .map_err(Error::<T>::from)?;
 
             Ok(())
         }
@@ -1261,6 +1342,43 @@ mod pallet {
             });
             Ok(())
         }
+
+        /// Removes a domain runtime that is not used by any domain from the registry and cancels
+        /// its scheduled upgrades.
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::deregister_domain_runtime())]
+        pub fn deregister_domain_runtime(
+            origin: OriginFor<T>,
+            runtime_id: RuntimeId,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            do_deregister_runtime::<T>(runtime_id).map_err(Error::<T>::from)?;
+
+            Self::deposit_event(Event::DomainRuntimeDeregistered { runtime_id });
+
+            Ok(())
+        }
+
+        /// Cancels the pending upgrade of a domain runtime before it takes effect.
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::cancel_domain_runtime_upgrade())]
+        pub fn cancel_domain_runtime_upgrade(
+            origin: OriginFor<T>,
+            runtime_id: RuntimeId,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let scheduled_at =
+                do_cancel_scheduled_runtime_upgrade::<T>(runtime_id).map_err(Error::<T>::from)?;
+
+            Self::deposit_event(Event::DomainRuntimeUpgradeCancelled {
+                runtime_id,
+                scheduled_at,
+            });
+
+            Ok(())
+        }
     }
 
     #[pallet::genesis_config]
@@ -1328,7 +1446,7 @@ mod pallet {
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
             // Do scheduled domain runtime upgrade
-            do_upgrade_runtimes::<T>(block_number);
+            let weight = do_upgrade_runtimes::<T>(block_number);
 
             // Store the hash of the parent consensus block for domain that have bundles submitted
             // in that consensus block
@@ -1340,7 +1458,7 @@ mod pallet {
 
             let _ = SuccessfulFraudProofs::<T>::clear(u32::MAX, None);
 
-            Weight::zero()
+            weight
         }
 
         fn on_finalize(_: BlockNumberFor<T>) {
@@ -1384,7 +1502,9 @@ mod pallet {
                             | BundleError::Receipt(BlockTreeError::StaleReceipt)
                             | BundleError::Receipt(BlockTreeError::NewBranchReceipt)
                             | BundleError::Receipt(BlockTreeError::UnavailableConsensusBlockHash)
-                            | BundleError::Receipt(BlockTreeError::BuiltOnUnknownConsensusBlock) => {
+                            | BundleError::Receipt(BlockTreeError::BuiltOnUnknownConsensusBlock)
+                            // Expected until the domain is unfrozen
+                            | BundleError::FrozenDomain => {
                                 log::debug!(
                                     target: "runtime::domains",
                                     "Bad bundle {:?}, error: {e:?}", opaque_bundle.domain_id(),
@@ -1444,8 +1564,8 @@ impl<T: Config> Pallet<T> {
     }
 
     pub fn domain_runtime_code(domain_id: DomainId) -> Option<Vec<u8>> {
-        RuntimeRegistry::<T>::get(Self::runtime_id(domain_id)?)
-            .and_then(|mut runtime_object| runtime_object.raw_genesis.take_runtime_code())
+        let runtime_object = RuntimeRegistry::<T>::get(Self::runtime_id(domain_id)?)?;
+        RuntimeCode::<T>::get(runtime_object.hash)
     }
 
     pub fn domain_best_number(domain_id: DomainId) -> Option<DomainBlockNumberFor<T>> {
@@ -1471,8 +1591,8 @@ impl<T: Config> Pallet<T> {
         let domain_obj = DomainRegistry::<T>::get(domain_id)?;
         let runtime_object = RuntimeRegistry::<T>::get(domain_obj.domain_config.runtime_id)?;
         let runtime_type = runtime_object.runtime_type.clone();
-        let raw_genesis =
-            runtime_object.into_complete_raw_genesis(domain_id, domain_obj.domain_runtime_info);
+        let raw_genesis = runtime_object
+            .into_complete_raw_genesis::<T>(domain_id, domain_obj.domain_runtime_info)?;
         Some((
             DomainInstanceData {
                 runtime_type,
@@ -1561,6 +1681,11 @@ impl<T: Config> Pallet<T> {
         let operator_id = opaque_bundle.operator_id();
         let sealed_header = &opaque_bundle.sealed_header;
 
+        ensure!(
+            !T::FrozenDomains::contains(&domain_id),
+            BundleError::FrozenDomain
+        );
+
         let operator = Operators::<T>::get(operator_id).ok_or(BundleError::InvalidOperatorId)?;
 
         ensure!(
@@ -1930,6 +2055,57 @@ impl<T: Config> Pallet<T> {
     ) -> Option<ReceiptHashFor<T>> {
         BlockTree::<T>::get(domain_id, domain_number)
     }
+
+    /// Returns the on-chain storage version of the pallet, which versions the storage layout of
+    /// the runtime registry among others.
+    pub fn runtime_registry_storage_version() -> u16 {
+        // `StorageVersion` is stored as a plain `u16` but doesn't expose its inner value.
+        frame_support::storage::unhashed::get_or_default(&StorageVersion::storage_key::<Self>())
+    }
+
+    /// Returns the block number, runtime id and target version of all the pending domain
+    /// runtime upgrades, ordered by block number.
+    pub fn scheduled_runtime_upgrades() -> Vec<(BlockNumberFor<T>, RuntimeId, RuntimeVersion)> {
+        let mut scheduled_upgrades: Vec<_> = ScheduledRuntimeUpgrades::<T>::iter()
+            .map(|(scheduled_at, runtime_id, scheduled_upgrade)| {
+                (scheduled_at, runtime_id, scheduled_upgrade.version)
+            })
+            .collect();
+        scheduled_upgrades
+            .sort_by_key(|(scheduled_at, runtime_id, _)| (*scheduled_at, *runtime_id));
+        scheduled_upgrades
+    }
+
+    /// Returns the metadata of the given domain runtime, without its raw genesis storage.
+    pub fn domain_runtime_object(
+        runtime_id: RuntimeId,
+    ) -> Option<RuntimeObjectInfo<BlockNumberFor<T>, T::Hash>> {
+        RuntimeRegistry::<T>::get(runtime_id).map(|runtime_obj| runtime_obj.info())
+    }
+
+    /// Returns the block number, target version and code hash of the pending upgrade of the
+    /// given domain runtime, if any.
+    pub fn pending_runtime_upgrade(
+        runtime_id: RuntimeId,
+    ) -> Option<(BlockNumberFor<T>, RuntimeVersion, T::Hash)> {
+        let scheduled_at = ScheduledRuntimeUpgradeAt::<T>::get(runtime_id)?;
+        ScheduledRuntimeUpgrades::<T>::get(scheduled_at, runtime_id).map(|scheduled_upgrade| {
+            (
+                scheduled_at,
+                scheduled_upgrade.version,
+                scheduled_upgrade.hash,
+            )
+        })
+    }
+
+    /// Returns the version, hash and block number of the `upgrade_index`-th upgrade of the
+    /// domain runtime, starting from 1, if it is still kept in the upgrade history.
+    pub fn runtime_upgrade_history(
+        runtime_id: RuntimeId,
+        upgrade_index: u32,
+    ) -> Option<(RuntimeVersion, T::Hash, BlockNumberFor<T>)> {
+        RuntimeUpgradeHistory::<T>::get(runtime_id, upgrade_index)
+    }
 }
 
 impl<T> Pallet<T>
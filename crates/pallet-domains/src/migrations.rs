@@ -0,0 +1,184 @@
+//! Storage migrations of the domains pallet.
+
+/// Storage layouts of the domains pallet at storage version 0, frozen so that the migrations
+/// keep decoding the old values after the current types change.
+pub mod v0 {
+    use alloc::string::String;
+    use codec::{Decode, Encode};
+    use scale_info::TypeInfo;
+    use sp_domains::storage::RawGenesis;
+    use sp_domains::RuntimeType;
+    use sp_version::RuntimeVersion;
+
+    /// Runtime object before the runtime code was moved out of the raw genesis and the code
+    /// size was recorded.
+    #[derive(TypeInfo, Debug, Encode, Decode, Clone, PartialEq, Eq)]
+    pub struct RuntimeObject<Number, Hash> {
+        pub runtime_name: String,
+        pub runtime_type: RuntimeType,
+        pub runtime_upgrades: u32,
+        pub hash: Hash,
+        // The raw genesis storage that contains the runtime code.
+        pub raw_genesis: RawGenesis,
+        pub version: RuntimeVersion,
+        pub created_at: Number,
+        pub updated_at: Number,
+    }
+
+    /// Scheduled runtime upgrade before the runtime code was moved out of the raw genesis.
+    #[derive(TypeInfo, Debug, Encode, Decode, Clone, PartialEq, Eq)]
+    pub struct ScheduledRuntimeUpgrade<Hash> {
+        // The new raw genesis storage that contains the runtime code.
+        pub raw_genesis: RawGenesis,
+        pub version: RuntimeVersion,
+        pub hash: Hash,
+    }
+}
+
+/// Migration from v0, where the runtime code was embedded in the raw genesis of each runtime
+/// object and scheduled upgrade, to v1 where it is stored once in `RuntimeCode`, the runtime
+/// objects record the code size, the runtime names are indexed in `RuntimeNames`, the
+/// scheduled upgrades in `ScheduledRuntimeUpgradeAt` and the domains using each runtime are
+/// counted in `RuntimeDomainCount`.
+pub mod v1 {
+    use super::v0;
+    use crate::pallet::{
+        DomainRegistry, RuntimeDomainCount, RuntimeNames, RuntimeRegistry,
+        ScheduledRuntimeUpgradeAt, ScheduledRuntimeUpgrades,
+    };
+    use crate::runtime_registry::{acquire_runtime_code, RuntimeObject, ScheduledRuntimeUpgrade};
+    use crate::{Config, Pallet};
+    use core::marker::PhantomData;
+    use frame_support::pallet_prelude::{StorageVersion, Weight};
+    use frame_support::storage::StoragePrefixedMap;
+    use frame_support::traits::{Get, GetStorageVersion, OnRuntimeUpgrade};
+    use frame_system::pallet_prelude::BlockNumberFor;
+    #[cfg(feature = "try-runtime")]
+    use sp_runtime::TryRuntimeError;
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// Moves the runtime code out of the raw genesis of the runtime objects and scheduled
+    /// upgrades into `RuntimeCode`, taking a reference for each of them, records the code size of
+    /// the runtime objects, indexes the runtime names and the block number of the scheduled
+    /// upgrades, counts the domains using each runtime and bumps the storage version to 1.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() != 0 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut count = 0u64;
+            RuntimeRegistry::<T>::translate::<v0::RuntimeObject<BlockNumberFor<T>, T::Hash>, _>(
+                |runtime_id, runtime_obj| {
+                    let v0::RuntimeObject {
+                        runtime_name,
+                        runtime_type,
+                        runtime_upgrades,
+                        hash,
+                        mut raw_genesis,
+                        version,
+                        created_at,
+                        updated_at,
+                    } = runtime_obj;
+
+                    let code_size = match raw_genesis.take_runtime_code() {
+                        Some(code) => {
+                            let code_size = code.len() as u32;
+                            acquire_runtime_code::<T>(hash, code);
+                            code_size
+                        }
+                        None => 0,
+                    };
+                    RuntimeNames::<T>::insert(&runtime_name, runtime_id);
+                    count += 1;
+
+                    Some(RuntimeObject {
+                        runtime_name,
+                        runtime_type,
+                        runtime_upgrades,
+                        hash,
+                        raw_genesis,
+                        version,
+                        created_at,
+                        updated_at,
+                        code_size,
+                    })
+                },
+            );
+            ScheduledRuntimeUpgrades::<T>::translate::<v0::ScheduledRuntimeUpgrade<T::Hash>, _>(
+                |scheduled_at, runtime_id, scheduled_upgrade| {
+                    let v0::ScheduledRuntimeUpgrade {
+                        mut raw_genesis,
+                        version,
+                        hash,
+                    } = scheduled_upgrade;
+
+                    if let Some(code) = raw_genesis.take_runtime_code() {
+                        acquire_runtime_code::<T>(hash, code);
+                    }
+                    ScheduledRuntimeUpgradeAt::<T>::insert(runtime_id, scheduled_at);
+                    count += 1;
+
+                    Some(ScheduledRuntimeUpgrade {
+                        raw_genesis,
+                        version,
+                        hash,
+                    })
+                },
+            );
+
+            let mut domain_count = 0u64;
+            for domain_obj in DomainRegistry::<T>::iter_values() {
+                RuntimeDomainCount::<T>::mutate(domain_obj.domain_config.runtime_id, |count| {
+                    *count = count.saturating_add(1)
+                });
+                domain_count += 1;
+            }
+
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            // Each entry is read and rewritten, along with the read and write of the code
+            // reference count, the write of the code and the write of the runtime name or the
+            // scheduled block number, each domain is read along with the read and write of the
+            // domain count of its runtime.
+            T::DbWeight::get().reads_writes(
+                1 + 2 * count + 2 * domain_count,
+                1 + 4 * count + domain_count,
+            )
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() <= 1,
+                "Unexpected storage version of pallet-domains"
+            );
+
+            Ok(Vec::new())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 1,
+                "Storage version of pallet-domains must be 1 after the migration"
+            );
+            frame_support::ensure!(
+                RuntimeRegistry::<T>::iter_values()
+                    .all(|runtime_obj| runtime_obj.raw_genesis.get_runtime_code().is_none()),
+                "Runtime code must be moved out of the runtime objects"
+            );
+            frame_support::ensure!(
+                ScheduledRuntimeUpgrades::<T>::iter_keys().all(|(scheduled_at, runtime_id)| {
+                    ScheduledRuntimeUpgradeAt::<T>::get(runtime_id) == Some(scheduled_at)
+                }),
+                "Scheduled upgrades must be indexed by runtime id"
+            );
+
+            Ok(())
+        }
+    }
+}
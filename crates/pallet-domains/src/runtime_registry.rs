@@ -1,17 +1,22 @@
 //! Runtime registry for domains
 
-use crate::pallet::{NextRuntimeId, RuntimeRegistry, ScheduledRuntimeUpgrades};
+use crate::pallet::{
+    NextRuntimeId, RuntimeCode, RuntimeCodeRefCount, RuntimeDomainCount, RuntimeNames,
+    RuntimeRegistry, RuntimeUpgradeHistory, ScheduledRuntimeUpgradeAt, ScheduledRuntimeUpgrades,
+};
+use crate::weights::WeightInfo;
 use crate::{Config, Event};
 use alloc::string::String;
 use codec::{Decode, Encode};
 use domain_runtime_primitives::EVMChainId;
+use frame_support::weights::Weight;
 use frame_support::PalletError;
 use frame_system::pallet_prelude::*;
 use scale_info::TypeInfo;
 use sp_core::Hasher;
 use sp_domains::storage::RawGenesis;
-use sp_domains::{DomainId, DomainsDigestItem, RuntimeId, RuntimeType};
-use sp_runtime::traits::{CheckedAdd, Get};
+use sp_domains::{DomainId, DomainsDigestItem, RuntimeId, RuntimeObjectInfo, RuntimeType};
+use sp_runtime::traits::{CheckedAdd, Get, One};
 use sp_runtime::DigestItem;
 use sp_std::vec::Vec;
 use sp_version::RuntimeVersion;
@@ -28,6 +33,14 @@ pub enum Error {
     MaxScheduledBlockNumber,
     FailedToDecodeRawGenesis,
     RuntimeCodeNotFoundInRawGenesis,
+    RuntimeInUse,
+    NoScheduledUpgrade,
+    RuntimeNameAlreadyExists,
+    RuntimeNameTooLong,
+    InvalidRuntimeName,
+    AuthoringVersionRegressed,
+    EmptyRuntimeCode,
+    RuntimeCodeTooLarge,
 }
 
 #[derive(TypeInfo, Debug, Encode, Decode, Clone, PartialEq, Eq)]
@@ -36,18 +49,27 @@ pub struct RuntimeObject<Number, Hash> {
     pub runtime_type: RuntimeType,
     pub runtime_upgrades: u32,
     pub hash: Hash,
-    // The raw gensis storage that contains the runtime code.
+    // The raw gensis storage without the runtime code, which is stored in `RuntimeCode` under
+    // `hash` and shared with the other runtimes and scheduled upgrades using the same code.
     // NOTE: don't use this field directly but `into_complete_raw_genesis` instead
     pub raw_genesis: RawGenesis,
     pub version: RuntimeVersion,
     pub created_at: Number,
     pub updated_at: Number,
+    /// The size of the runtime code, in bytes.
+    pub code_size: u32,
 }
 
 /// Domain runtime specific information to create domain raw genesis.
+///
+/// NOTE: this is stored on chain as part of the domain object, new variants must be appended.
 #[derive(TypeInfo, Debug, Encode, Decode, Clone, PartialEq, Eq, Copy)]
 pub enum DomainRuntimeInfo {
-    EVM { chain_id: EVMChainId },
+    EVM {
+        chain_id: EVMChainId,
+    },
+    /// AutoId runtime, it doesn't have any runtime specific genesis storage besides the domain id.
+    AutoId,
 }
 
 impl Default for DomainRuntimeInfo {
@@ -57,30 +79,112 @@ impl Default for DomainRuntimeInfo {
 }
 
 impl<Number, Hash> RuntimeObject<Number, Hash> {
-    // Return a complete raw genesis with runtime code and domain id set properly
-    pub fn into_complete_raw_genesis(
+    /// Returns the metadata of the runtime, leaving out the raw genesis storage.
+    pub fn info(&self) -> RuntimeObjectInfo<Number, Hash>
+    where
+        Number: Clone,
+        Hash: Clone,
+    {
+        RuntimeObjectInfo {
+            runtime_name: self.runtime_name.clone(),
+            runtime_type: self.runtime_type.clone(),
+            version: self.version.clone(),
+            hash: self.hash.clone(),
+            runtime_upgrades: self.runtime_upgrades,
+            created_at: self.created_at.clone(),
+            updated_at: self.updated_at.clone(),
+            code_size: self.code_size,
+        }
+    }
+
+    // Return a complete raw genesis with runtime code and domain id set properly, `None` if the
+    // runtime code is missing from `RuntimeCode`
+    pub fn into_complete_raw_genesis<T: Config<Hash = Hash>>(
         self,
         domain_id: DomainId,
         domain_runtime_info: DomainRuntimeInfo,
-    ) -> RawGenesis {
+    ) -> Option<RawGenesis> {
         let RuntimeObject {
-            mut raw_genesis, ..
+            raw_genesis, hash, ..
         } = self;
-        raw_genesis.set_domain_id(domain_id);
-        match domain_runtime_info {
-            DomainRuntimeInfo::EVM { chain_id } => raw_genesis.set_evm_chain_id(chain_id),
-        }
-        raw_genesis
+        complete_raw_genesis::<T>(raw_genesis, hash, domain_id, domain_runtime_info)
+    }
+
+    // Same as `into_complete_raw_genesis` but only clones the raw genesis
+    pub fn complete_raw_genesis<T: Config<Hash = Hash>>(
+        &self,
+        domain_id: DomainId,
+        domain_runtime_info: DomainRuntimeInfo,
+    ) -> Option<RawGenesis>
+    where
+        Hash: Clone,
+    {
+        complete_raw_genesis::<T>(
+            self.raw_genesis.clone(),
+            self.hash.clone(),
+            domain_id,
+            domain_runtime_info,
+        )
     }
 }
 
+fn complete_raw_genesis<T: Config>(
+    mut raw_genesis: RawGenesis,
+    code_hash: T::Hash,
+    domain_id: DomainId,
+    domain_runtime_info: DomainRuntimeInfo,
+) -> Option<RawGenesis> {
+    raw_genesis.set_runtime_code(RuntimeCode::<T>::get(code_hash)?);
+    raw_genesis.set_domain_id(domain_id);
+    match domain_runtime_info {
+        DomainRuntimeInfo::EVM { chain_id } => raw_genesis.set_evm_chain_id(chain_id),
+        DomainRuntimeInfo::AutoId => {}
+    }
+    Some(raw_genesis)
+}
+
 #[derive(TypeInfo, Debug, Encode, Decode, Clone, PartialEq, Eq)]
 pub struct ScheduledRuntimeUpgrade<Hash> {
+    /// The new raw genesis storage without the runtime code, which is stored in `RuntimeCode`
+    /// under `hash`.
     pub raw_genesis: RawGenesis,
     pub version: RuntimeVersion,
     pub hash: Hash,
 }
 
+/// Stores `code` under `code_hash`, unless it is already stored, and takes a reference to it.
+pub(crate) fn acquire_runtime_code<T: Config>(code_hash: T::Hash, code: Vec<u8>) {
+    RuntimeCodeRefCount::<T>::mutate(code_hash, |ref_count| {
+        if *ref_count == 0 {
+            RuntimeCode::<T>::insert(code_hash, code);
+        }
+        *ref_count = ref_count.saturating_add(1);
+    });
+}
+
+/// Drops a reference to the code stored under `code_hash`, the code is removed along with the
+/// last reference.
+pub(crate) fn release_runtime_code<T: Config>(code_hash: T::Hash) {
+    let ref_count = RuntimeCodeRefCount::<T>::get(code_hash).saturating_sub(1);
+    if ref_count == 0 {
+        RuntimeCodeRefCount::<T>::remove(code_hash);
+        RuntimeCode::<T>::remove(code_hash);
+    } else {
+        RuntimeCodeRefCount::<T>::insert(code_hash, ref_count);
+    }
+}
+
+/// Ensures the runtime code together with the rest of the raw genesis storage is within
+/// [`Config::MaxDomainRuntimeCodeSize`].
+fn ensure_runtime_code_size<T: Config>(code: &[u8], raw_genesis: &RawGenesis) -> Result<(), Error> {
+    let max_code_size = T::MaxDomainRuntimeCodeSize::get() as usize;
+    if code.len().saturating_add(raw_genesis.encoded_size()) > max_code_size {
+        return Err(Error::RuntimeCodeTooLarge);
+    }
+
+    Ok(())
+}
+
 /// Extracts the runtime version of the provided code.
 pub(crate) fn runtime_version(code: &[u8]) -> Result<RuntimeVersion, Error> {
     sp_io::misc::runtime_version(code)
@@ -104,6 +208,12 @@ pub(crate) fn can_upgrade_code(
         return Err(Error::SpecVersionNeedsToIncrease);
     }
 
+    // A lower authoring version could make the new runtime author blocks that the operators
+    // running the current one reject.
+    if new_version.authoring_version < current_version.authoring_version {
+        return Err(Error::AuthoringVersionRegressed);
+    }
+
     Ok(new_version)
 }
 
@@ -114,17 +224,36 @@ pub(crate) fn do_register_runtime<T: Config>(
     raw_genesis_storage: Vec<u8>,
     at: BlockNumberFor<T>,
 ) -> Result<RuntimeId, Error> {
-    let raw_genesis: RawGenesis = Decode::decode(&mut raw_genesis_storage.as_slice())
+    if runtime_name.len() as u32 > T::MaxRuntimeNameLength::get() {
+        return Err(Error::RuntimeNameTooLong);
+    }
+
+    if runtime_name.chars().any(char::is_control) {
+        return Err(Error::InvalidRuntimeName);
+    }
+
+    if RuntimeNames::<T>::contains_key(&runtime_name) {
+        return Err(Error::RuntimeNameAlreadyExists);
+    }
+
+    let mut raw_genesis: RawGenesis = Decode::decode(&mut raw_genesis_storage.as_slice())
         .map_err(|_| Error::FailedToDecodeRawGenesis)?;
 
     let code = raw_genesis
-        .get_runtime_code()
+        .take_runtime_code()
         .ok_or(Error::RuntimeCodeNotFoundInRawGenesis)?;
+    if code.is_empty() {
+        return Err(Error::EmptyRuntimeCode);
+    }
+    ensure_runtime_code_size::<T>(&code, &raw_genesis)?;
 
-    let version = runtime_version(code)?;
-    let runtime_hash = T::Hashing::hash(code);
+    let version = runtime_version(&code)?;
+    let runtime_hash = T::Hashing::hash(&code);
+    let code_size = code.len() as u32;
     let runtime_id = NextRuntimeId::<T>::get();
 
+    acquire_runtime_code::<T>(runtime_hash, code);
+    RuntimeNames::<T>::insert(&runtime_name, runtime_id);
     RuntimeRegistry::<T>::insert(
         runtime_id,
         RuntimeObject {
@@ -136,6 +265,7 @@ pub(crate) fn do_register_runtime<T: Config>(
             created_at: at,
             updated_at: at,
             runtime_upgrades: 0u32,
+            code_size,
         },
     );
 
@@ -154,16 +284,19 @@ pub(crate) fn register_runtime_at_genesis<T: Config>(
     raw_genesis_storage: Vec<u8>,
     at: BlockNumberFor<T>,
 ) -> Result<RuntimeId, Error> {
-    let raw_genesis: RawGenesis = Decode::decode(&mut raw_genesis_storage.as_slice())
+    let mut raw_genesis: RawGenesis = Decode::decode(&mut raw_genesis_storage.as_slice())
         .map_err(|_| Error::FailedToDecodeRawGenesis)?;
 
     let code = raw_genesis
-        .get_runtime_code()
+        .take_runtime_code()
         .ok_or(Error::RuntimeCodeNotFoundInRawGenesis)?;
 
-    let runtime_hash = T::Hashing::hash(code);
+    let runtime_hash = T::Hashing::hash(&code);
+    let code_size = code.len() as u32;
     let runtime_id = NextRuntimeId::<T>::get();
 
+    acquire_runtime_code::<T>(runtime_hash, code);
+    RuntimeNames::<T>::insert(&runtime_name, runtime_id);
     RuntimeRegistry::<T>::insert(
         runtime_id,
         RuntimeObject {
@@ -175,6 +308,7 @@ pub(crate) fn register_runtime_at_genesis<T: Config>(
             created_at: at,
             updated_at: at,
             runtime_upgrades: 0u32,
+            code_size,
         },
     );
 
@@ -192,41 +326,153 @@ pub(crate) fn do_schedule_runtime_upgrade<T: Config>(
 ) -> Result<BlockNumberFor<T>, Error> {
     let runtime_obj = RuntimeRegistry::<T>::get(runtime_id).ok_or(Error::MissingRuntimeObject)?;
 
-    let new_raw_genesis: RawGenesis = Decode::decode(&mut raw_genesis_storage.as_slice())
+    // A runtime has at most one pending upgrade, it must be cancelled or applied before another
+    // upgrade can be scheduled.
+    if ScheduledRuntimeUpgradeAt::<T>::contains_key(runtime_id) {
+        return Err(Error::RuntimeUpgradeAlreadyScheduled);
+    }
+
+    let mut new_raw_genesis: RawGenesis = Decode::decode(&mut raw_genesis_storage.as_slice())
         .map_err(|_| Error::FailedToDecodeRawGenesis)?;
 
     let new_code = new_raw_genesis
-        .get_runtime_code()
+        .take_runtime_code()
         .ok_or(Error::RuntimeCodeNotFoundInRawGenesis)?;
+    if new_code.is_empty() {
+        return Err(Error::EmptyRuntimeCode);
+    }
+    ensure_runtime_code_size::<T>(&new_code, &new_raw_genesis)?;
 
-    let new_runtime_version = can_upgrade_code(&runtime_obj.version, new_code)?;
-    let new_runtime_hash = T::Hashing::hash(new_code);
+    let new_runtime_version = can_upgrade_code(&runtime_obj.version, &new_code)?;
+    let new_spec_version = new_runtime_version.spec_version;
+    let new_runtime_hash = T::Hashing::hash(&new_code);
     let scheduled_upgrade = ScheduledRuntimeUpgrade {
         raw_genesis: new_raw_genesis,
         version: new_runtime_version,
         hash: new_runtime_hash,
     };
+    // The upgrades of the current block were already applied in `on_initialize`, so an
+    // immediate upgrade is applied at the next block.
+    let upgrade_delay = if T::AllowImmediateRuntimeUpgrade::get() {
+        One::one()
+    } else {
+        T::DomainRuntimeUpgradeDelay::get()
+    };
     let scheduled_at = current_block_number
-        .checked_add(&T::DomainRuntimeUpgradeDelay::get())
+        .checked_add(&upgrade_delay)
         .ok_or(Error::MaxScheduledBlockNumber)?;
 
+    acquire_runtime_code::<T>(new_runtime_hash, new_code);
     ScheduledRuntimeUpgrades::<T>::insert(scheduled_at, runtime_id, scheduled_upgrade);
+    ScheduledRuntimeUpgradeAt::<T>::insert(runtime_id, scheduled_at);
+
+    // deposit digest log for light clients
+    frame_system::Pallet::<T>::deposit_log(DigestItem::domain_runtime_upgrade_scheduled(
+        runtime_id,
+        new_spec_version,
+    ));
+
+    frame_system::Pallet::<T>::deposit_event(<T as Config>::RuntimeEvent::from(
+        Event::DomainRuntimeUpgradeScheduled {
+            runtime_id,
+            scheduled_at,
+            new_spec_version,
+        },
+    ));
+
+    Ok(scheduled_at)
+}
+
+/// Cancels the pending upgrade of the given runtime and returns the block number it was
+/// scheduled at.
+pub(crate) fn do_cancel_scheduled_runtime_upgrade<T: Config>(
+    runtime_id: RuntimeId,
+) -> Result<BlockNumberFor<T>, Error> {
+    let scheduled_at =
+        ScheduledRuntimeUpgradeAt::<T>::take(runtime_id).ok_or(Error::NoScheduledUpgrade)?;
+    let scheduled_upgrade = ScheduledRuntimeUpgrades::<T>::take(scheduled_at, runtime_id)
+        .ok_or(Error::NoScheduledUpgrade)?;
+    release_runtime_code::<T>(scheduled_upgrade.hash);
 
     Ok(scheduled_at)
 }
 
-pub(crate) fn do_upgrade_runtimes<T: Config>(at: BlockNumberFor<T>) {
-    for (runtime_id, scheduled_update) in ScheduledRuntimeUpgrades::<T>::drain_prefix(at) {
+/// Removes a domain runtime that is not used by any domain from the registry, along with its
+/// pending upgrade and upgrade history.
+pub(crate) fn do_deregister_runtime<T: Config>(runtime_id: RuntimeId) -> Result<(), Error> {
+    let runtime_obj = RuntimeRegistry::<T>::get(runtime_id).ok_or(Error::MissingRuntimeObject)?;
+
+    if RuntimeDomainCount::<T>::get(runtime_id) > 0 {
+        return Err(Error::RuntimeInUse);
+    }
+
+    if let Some(scheduled_at) = ScheduledRuntimeUpgradeAt::<T>::take(runtime_id) {
+        if let Some(scheduled_upgrade) =
+            ScheduledRuntimeUpgrades::<T>::take(scheduled_at, runtime_id)
+        {
+            release_runtime_code::<T>(scheduled_upgrade.hash);
+        }
+    }
+
+    // At most `MaxRuntimeUpgradeHistory` upgrades are kept in the history.
+    let _ = RuntimeUpgradeHistory::<T>::clear_prefix(
+        runtime_id,
+        T::MaxRuntimeUpgradeHistory::get(),
+        None,
+    );
+    if RuntimeNames::<T>::get(&runtime_obj.runtime_name) == Some(runtime_id) {
+        RuntimeNames::<T>::remove(&runtime_obj.runtime_name);
+    }
+    RuntimeRegistry::<T>::remove(runtime_id);
+    release_runtime_code::<T>(runtime_obj.hash);
+
+    Ok(())
+}
+
+/// Applies the domain runtime upgrades scheduled at block `at` and returns the consumed weight.
+///
+/// At most [`Config::MaxRuntimeUpgradesPerBlock`] upgrades are applied, in the order of their
+/// runtime id, the remaining upgrades are moved to the next block.
+pub(crate) fn do_upgrade_runtimes<T: Config>(at: BlockNumberFor<T>) -> Weight {
+    let mut weight = Weight::zero();
+    let mut scheduled_upgrades = ScheduledRuntimeUpgrades::<T>::drain_prefix(at);
+
+    let max_upgrades = T::MaxRuntimeUpgradesPerBlock::get() as usize;
+    for (runtime_id, scheduled_update) in scheduled_upgrades.by_ref().take(max_upgrades) {
+        let code_size =
+            RuntimeCode::<T>::decode_len(scheduled_update.hash).unwrap_or_default() as u32;
+        weight = weight.saturating_add(T::WeightInfo::upgrade_scheduled_runtime(
+            code_size.saturating_add(scheduled_update.raw_genesis.encoded_size() as u32),
+        ));
+
+        ScheduledRuntimeUpgradeAt::<T>::remove(runtime_id);
+
         RuntimeRegistry::<T>::mutate(runtime_id, |maybe_runtime_object| {
             let runtime_obj = maybe_runtime_object
                 .as_mut()
                 .expect("Runtime object exists since an upgrade is scheduled after verification");
 
+            // The reference of the scheduled upgrade to the new code is taken over by the
+            // runtime object
+            release_runtime_code::<T>(runtime_obj.hash);
+            runtime_obj.code_size = code_size;
             runtime_obj.raw_genesis = scheduled_update.raw_genesis;
             runtime_obj.version = scheduled_update.version;
             runtime_obj.hash = scheduled_update.hash;
             runtime_obj.runtime_upgrades = runtime_obj.runtime_upgrades.saturating_add(1);
             runtime_obj.updated_at = at;
+
+            let upgrade_index = runtime_obj.runtime_upgrades;
+            RuntimeUpgradeHistory::<T>::insert(
+                runtime_id,
+                upgrade_index,
+                (runtime_obj.version.clone(), runtime_obj.hash, at),
+            );
+            if let Some(pruned_index) =
+                upgrade_index.checked_sub(T::MaxRuntimeUpgradeHistory::get())
+            {
+                RuntimeUpgradeHistory::<T>::remove(runtime_id, pruned_index);
+            }
         });
 
         // deposit digest log for light clients
@@ -237,23 +483,51 @@ pub(crate) fn do_upgrade_runtimes<T: Config>(at: BlockNumberFor<T>) {
             Event::DomainRuntimeUpgraded { runtime_id },
         ));
     }
+
+    // Collect the deferred upgrades first to not insert while draining
+    let deferred_upgrades: Vec<_> = scheduled_upgrades.collect();
+    let next_block = at + One::one();
+    for (runtime_id, scheduled_update) in deferred_upgrades {
+        ScheduledRuntimeUpgrades::<T>::insert(next_block, runtime_id, scheduled_update);
+        ScheduledRuntimeUpgradeAt::<T>::insert(runtime_id, next_block);
+        weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 3));
+    }
+
+    weight
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::pallet::{NextRuntimeId, RuntimeRegistry, ScheduledRuntimeUpgrades};
-    use crate::runtime_registry::{Error as RuntimeRegistryError, RuntimeObject};
+    use crate::domain_registry::{DomainConfig, DomainObject};
+    use crate::migrations::v0;
+    use crate::migrations::v1::MigrateToV1;
+    use crate::pallet::{
+        DomainRegistry, NextRuntimeId, RuntimeCode, RuntimeCodeRefCount, RuntimeDomainCount,
+        RuntimeNames, RuntimeRegistry, RuntimeUpgradeHistory, ScheduledRuntimeUpgradeAt,
+        ScheduledRuntimeUpgrades,
+    };
+    use crate::runtime_registry::{
+        acquire_runtime_code, can_upgrade_code, do_upgrade_runtimes, DomainRuntimeInfo,
+        Error as RuntimeRegistryError, RuntimeObject, ScheduledRuntimeUpgrade,
+    };
     use crate::tests::{
-        new_test_ext, DomainRuntimeUpgradeDelay, Domains, ReadRuntimeVersion, System, Test,
+        new_test_ext, AllowImmediateRuntimeUpgrade, DomainRuntimeUpgradeDelay, Domains,
+        MaxDomainRuntimeCodeSize, MaxRuntimeNameLength, MaxRuntimeUpgradesPerBlock,
+        ReadRuntimeVersion, RuntimeEvent, System, Test,
     };
     use crate::Error;
     use codec::Encode;
     use frame_support::assert_ok;
     use frame_support::dispatch::RawOrigin;
-    use frame_support::traits::OnInitialize;
+    use frame_support::pallet_prelude::StorageVersion;
+    use frame_support::traits::{GetStorageVersion, OnInitialize, OnRuntimeUpgrade};
+    use frame_support::weights::Weight;
+    use sp_core::H256;
     use sp_domains::storage::RawGenesis;
-    use sp_domains::{DomainsDigestItem, RuntimeId, RuntimeType};
-    use sp_runtime::traits::BlockNumberProvider;
+    use sp_domains::{
+        DomainId, DomainsDigestItem, OperatorAllowList, RuntimeId, RuntimeObjectInfo, RuntimeType,
+    };
+    use sp_runtime::traits::{BlakeTwo256, BlockNumberProvider, Hash as HashT};
     use sp_runtime::{Digest, DispatchError};
     use sp_version::RuntimeVersion;
 
@@ -277,6 +551,7 @@ mod tests {
             read_runtime_version,
         ));
         ext.execute_with(|| {
+            System::set_block_number(1);
             let raw_genesis_storage = RawGenesis::dummy(vec![1, 2, 3, 4]).encode();
             let res = crate::Pallet::<Test>::register_domain_runtime(
                 RawOrigin::Root.into(),
@@ -288,7 +563,341 @@ mod tests {
             assert_ok!(res);
             let runtime_obj = RuntimeRegistry::<Test>::get(0).unwrap();
             assert_eq!(runtime_obj.version, version);
-            assert_eq!(NextRuntimeId::<Test>::get(), 1)
+            assert_eq!(runtime_obj.raw_genesis.get_runtime_code(), None);
+            assert_eq!(
+                RuntimeCode::<Test>::get(runtime_obj.hash),
+                Some(vec![1, 2, 3, 4])
+            );
+            assert_eq!(RuntimeCodeRefCount::<Test>::get(runtime_obj.hash), 1);
+            assert_eq!(NextRuntimeId::<Test>::get(), 1);
+            System::assert_last_event(RuntimeEvent::Domains(crate::Event::DomainRuntimeCreated {
+                runtime_id: 0,
+                runtime_type: RuntimeType::Evm,
+            }));
+        })
+    }
+
+    #[test]
+    fn create_domain_runtime_with_empty_code() {
+        new_test_ext().execute_with(|| {
+            let res = crate::Pallet::<Test>::register_domain_runtime(
+                RawOrigin::Root.into(),
+                "evm".to_owned(),
+                RuntimeType::Evm,
+                RawGenesis::dummy(vec![]).encode(),
+            );
+            assert_eq!(
+                res,
+                Err(Error::<Test>::RuntimeRegistry(RuntimeRegistryError::EmptyRuntimeCode).into())
+            );
+            assert_eq!(NextRuntimeId::<Test>::get(), 0);
+
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+            let res = crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(vec![]).encode(),
+            );
+            assert_eq!(
+                res,
+                Err(Error::<Test>::RuntimeRegistry(RuntimeRegistryError::EmptyRuntimeCode).into())
+            );
+        })
+    }
+
+    #[test]
+    fn domain_runtime_code_size_limit() {
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 2,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+        let read_runtime_version = ReadRuntimeVersion(version.encode());
+
+        let mut ext = new_test_ext();
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            read_runtime_version,
+        ));
+        ext.execute_with(|| {
+            // The raw genesis storage left once the runtime code is taken out also counts
+            // towards the limit.
+            let max_code_size =
+                MaxDomainRuntimeCodeSize::get() as usize - RawGenesis::default().encoded_size();
+            let too_large_err: DispatchError =
+                Error::<Test>::RuntimeRegistry(RuntimeRegistryError::RuntimeCodeTooLarge).into();
+
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+            NextRuntimeId::<Test>::set(1);
+
+            // Registration
+            let res = crate::Pallet::<Test>::register_domain_runtime(
+                RawOrigin::Root.into(),
+                "evm-over-limit".to_owned(),
+                RuntimeType::Evm,
+                RawGenesis::dummy(vec![1; max_code_size + 1]).encode(),
+            );
+            assert_eq!(res, Err(too_large_err));
+            assert_eq!(NextRuntimeId::<Test>::get(), 1);
+
+            assert_ok!(crate::Pallet::<Test>::register_domain_runtime(
+                RawOrigin::Root.into(),
+                "evm-at-limit".to_owned(),
+                RuntimeType::Evm,
+                RawGenesis::dummy(vec![1; max_code_size]).encode(),
+            ));
+            assert_eq!(
+                RuntimeRegistry::<Test>::get(1).unwrap().code_size,
+                max_code_size as u32
+            );
+
+            // Upgrade
+            let res = crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(vec![2; max_code_size + 1]).encode(),
+            );
+            assert_eq!(res, Err(too_large_err));
+            assert!(ScheduledRuntimeUpgradeAt::<Test>::get(0).is_none());
+
+            assert_ok!(crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(vec![2; max_code_size]).encode(),
+            ));
+            assert!(ScheduledRuntimeUpgradeAt::<Test>::get(0).is_some());
+
+            // The rest of the raw genesis storage is bounded too.
+            let mut raw_genesis = RawGenesis::from_storage(sp_core::storage::Storage {
+                top: [(b"key".to_vec(), vec![0; max_code_size])].into(),
+                children_default: Default::default(),
+            });
+            raw_genesis.set_runtime_code(vec![1, 2, 3, 4]);
+            let res = crate::Pallet::<Test>::register_domain_runtime(
+                RawOrigin::Root.into(),
+                "evm-2".to_owned(),
+                RuntimeType::Evm,
+                raw_genesis.encode(),
+            );
+            assert_eq!(res, Err(too_large_err));
+
+            // The code and the rest of the raw genesis storage each fit, but not combined.
+            let mut raw_genesis = RawGenesis::from_storage(sp_core::storage::Storage {
+                top: [(b"key".to_vec(), vec![0; max_code_size / 2])].into(),
+                children_default: Default::default(),
+            });
+            raw_genesis.set_runtime_code(vec![1; max_code_size / 2 + 1]);
+            let res = crate::Pallet::<Test>::register_domain_runtime(
+                RawOrigin::Root.into(),
+                "evm-3".to_owned(),
+                RuntimeType::Evm,
+                raw_genesis.encode(),
+            );
+            assert_eq!(res, Err(too_large_err));
+            assert_eq!(NextRuntimeId::<Test>::get(), 2);
+        })
+    }
+
+    #[test]
+    fn create_domain_runtime_records_code_size() {
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 1,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+        let read_runtime_version = ReadRuntimeVersion(version.encode());
+
+        let mut ext = new_test_ext();
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            read_runtime_version,
+        ));
+        ext.execute_with(|| {
+            let code = vec![1, 2, 3, 4, 5, 6];
+            assert_ok!(crate::Pallet::<Test>::register_domain_runtime(
+                RawOrigin::Root.into(),
+                "evm".to_owned(),
+                RuntimeType::Evm,
+                RawGenesis::dummy(code.clone()).encode(),
+            ));
+
+            let runtime_obj = RuntimeRegistry::<Test>::get(0).unwrap();
+            assert_eq!(runtime_obj.code_size, code.len() as u32);
+        })
+    }
+
+    #[test]
+    fn domain_runtime_object() {
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 1,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+        let read_runtime_version = ReadRuntimeVersion(version.encode());
+
+        let mut ext = new_test_ext();
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            read_runtime_version,
+        ));
+        ext.execute_with(|| {
+            assert_eq!(Domains::domain_runtime_object(0), None);
+
+            System::set_block_number(1);
+            let code = vec![1, 2, 3, 4];
+            assert_ok!(crate::Pallet::<Test>::register_domain_runtime(
+                RawOrigin::Root.into(),
+                "evm".to_owned(),
+                RuntimeType::Evm,
+                RawGenesis::dummy(code.clone()).encode(),
+            ));
+
+            let runtime_obj = RuntimeRegistry::<Test>::get(0).unwrap();
+            assert_eq!(
+                Domains::domain_runtime_object(0),
+                Some(RuntimeObjectInfo {
+                    runtime_name: "evm".to_owned(),
+                    runtime_type: RuntimeType::Evm,
+                    version,
+                    hash: runtime_obj.hash,
+                    runtime_upgrades: 0,
+                    created_at: 1,
+                    updated_at: 1,
+                    code_size: code.len() as u32,
+                })
+            );
+            assert_eq!(Domains::domain_runtime_object(1), None);
+        })
+    }
+
+    #[test]
+    fn upgrade_code_authoring_version() {
+        let mut current_version = dummy_runtime_object().version;
+        current_version.authoring_version = 2;
+
+        for (authoring_version, expected) in [
+            (1, Err(RuntimeRegistryError::AuthoringVersionRegressed)),
+            (2, Ok(())),
+            (3, Ok(())),
+        ] {
+            let new_version = RuntimeVersion {
+                spec_version: current_version.spec_version + 1,
+                authoring_version,
+                // `impl_version` is free to change.
+                impl_version: current_version.impl_version + 1,
+                ..current_version.clone()
+            };
+
+            let mut ext = new_test_ext();
+            ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+                ReadRuntimeVersion(new_version.encode()),
+            ));
+            ext.execute_with(|| {
+                assert_eq!(
+                    can_upgrade_code(&current_version, &[1, 2, 3, 4]),
+                    expected.map(|_| new_version.clone())
+                );
+            });
+        }
+    }
+
+    #[test]
+    fn create_domain_runtime_with_invalid_name() {
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 1,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+        let read_runtime_version = ReadRuntimeVersion(version.encode());
+
+        let mut ext = new_test_ext();
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            read_runtime_version,
+        ));
+        ext.execute_with(|| {
+            let register_runtime = |runtime_name: String| {
+                crate::Pallet::<Test>::register_domain_runtime(
+                    RawOrigin::Root.into(),
+                    runtime_name,
+                    RuntimeType::Evm,
+                    RawGenesis::dummy(vec![1, 2, 3, 4]).encode(),
+                )
+            };
+
+            let max_runtime_name_length = MaxRuntimeNameLength::get() as usize;
+            assert_eq!(
+                register_runtime("a".repeat(max_runtime_name_length + 1)),
+                Err(
+                    Error::<Test>::RuntimeRegistry(RuntimeRegistryError::RuntimeNameTooLong).into()
+                )
+            );
+            assert_eq!(
+                register_runtime("evm\n".to_owned()),
+                Err(
+                    Error::<Test>::RuntimeRegistry(RuntimeRegistryError::InvalidRuntimeName).into()
+                )
+            );
+            assert_eq!(NextRuntimeId::<Test>::get(), 0);
+
+            assert_ok!(register_runtime("a".repeat(max_runtime_name_length)));
+            assert_eq!(NextRuntimeId::<Test>::get(), 1);
+        })
+    }
+
+    #[test]
+    fn create_domain_runtime_with_duplicated_name() {
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 1,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+        let read_runtime_version = ReadRuntimeVersion(version.encode());
+
+        let mut ext = new_test_ext();
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            read_runtime_version,
+        ));
+        ext.execute_with(|| {
+            let register_runtime = |runtime_name: &str| {
+                crate::Pallet::<Test>::register_domain_runtime(
+                    RawOrigin::Root.into(),
+                    runtime_name.to_owned(),
+                    RuntimeType::Evm,
+                    RawGenesis::dummy(vec![1, 2, 3, 4]).encode(),
+                )
+            };
+
+            assert_ok!(register_runtime("evm"));
+            assert_eq!(
+                register_runtime("evm"),
+                Err(
+                    Error::<Test>::RuntimeRegistry(RuntimeRegistryError::RuntimeNameAlreadyExists)
+                        .into()
+                )
+            );
+            assert_eq!(NextRuntimeId::<Test>::get(), 1);
+
+            // Names are compared byte by byte.
+            assert_ok!(register_runtime("EVM"));
+            assert_ok!(register_runtime("evm-2"));
+            assert_eq!(NextRuntimeId::<Test>::get(), 3);
+            assert_eq!(RuntimeNames::<Test>::get("EVM".to_owned()), Some(1));
+
+            // The name is released along with the runtime.
+            assert_ok!(crate::Pallet::<Test>::deregister_domain_runtime(
+                RawOrigin::Root.into(),
+                0
+            ));
+            assert!(!RuntimeNames::<Test>::contains_key("evm".to_owned()));
+            assert_ok!(register_runtime("evm"));
+            assert_eq!(RuntimeNames::<Test>::get("evm".to_owned()), Some(3));
         })
     }
 
@@ -313,6 +922,7 @@ mod tests {
                     },
                     created_at: Default::default(),
                     updated_at: Default::default(),
+                    code_size: 4,
                 },
             );
 
@@ -384,6 +994,18 @@ mod tests {
                 .unwrap();
             let scheduled_upgrade =
                 ScheduledRuntimeUpgrades::<Test>::get(scheduled_block_number, 0).unwrap();
+            assert_eq!(scheduled_upgrade.raw_genesis.get_runtime_code(), None);
+            assert_eq!(
+                RuntimeCode::<Test>::get(scheduled_upgrade.hash),
+                Some(vec![6, 7, 8, 9])
+            );
+            System::assert_last_event(RuntimeEvent::Domains(
+                crate::Event::DomainRuntimeUpgradeScheduled {
+                    runtime_id: 0,
+                    scheduled_at: scheduled_block_number,
+                    new_spec_version: 2,
+                },
+            ));
             assert_eq!(
                 scheduled_upgrade.version,
                 RuntimeVersion {
@@ -397,7 +1019,338 @@ mod tests {
         })
     }
 
-    fn go_to_block(block: u64) {
+    #[test]
+    fn upgrade_domain_runtime_requires_runtime_upgrade_origin() {
+        let mut ext = new_test_ext();
+        ext.execute_with(|| {
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+
+            let res = crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Signed(1).into(),
+                0,
+                RawGenesis::dummy(vec![6, 7, 8, 9]).encode(),
+            );
+            assert_eq!(res, Err(DispatchError::BadOrigin));
+            assert!(!ScheduledRuntimeUpgradeAt::<Test>::contains_key(0));
+        })
+    }
+
+    #[test]
+    fn runtime_registry_storage_version_reads_on_chain_version() {
+        let mut ext = new_test_ext();
+        ext.execute_with(|| {
+            StorageVersion::new(3).put::<Domains>();
+            assert_eq!(Domains::runtime_registry_storage_version(), 3);
+        })
+    }
+
+    fn dummy_runtime_object() -> RuntimeObject<u64, H256> {
+        RuntimeObject {
+            runtime_name: "evm".to_owned(),
+            runtime_type: Default::default(),
+            runtime_upgrades: 0,
+            hash: Default::default(),
+            raw_genesis: RawGenesis::default(),
+            version: RuntimeVersion {
+                spec_name: "test".into(),
+                spec_version: 1,
+                impl_version: 1,
+                transaction_version: 1,
+                ..Default::default()
+            },
+            created_at: Default::default(),
+            updated_at: Default::default(),
+            code_size: 4,
+        }
+    }
+
+    #[test]
+    fn complete_raw_genesis_for_domain_runtime_info() {
+        new_test_ext().execute_with(|| {
+            let domain_id = DomainId::new(1);
+
+            // The runtime code is missing.
+            assert_eq!(
+                dummy_runtime_object()
+                    .into_complete_raw_genesis::<Test>(domain_id, DomainRuntimeInfo::AutoId),
+                None
+            );
+
+            acquire_runtime_code::<Test>(Default::default(), vec![1, 2, 3, 4]);
+
+            let mut expected = RawGenesis::dummy(vec![1, 2, 3, 4]);
+            expected.set_domain_id(domain_id);
+            let raw_genesis = dummy_runtime_object()
+                .into_complete_raw_genesis::<Test>(domain_id, DomainRuntimeInfo::AutoId);
+            assert_eq!(raw_genesis, Some(expected.clone()));
+
+            expected.set_evm_chain_id(10);
+            let raw_genesis = dummy_runtime_object().into_complete_raw_genesis::<Test>(
+                domain_id,
+                DomainRuntimeInfo::EVM { chain_id: 10 },
+            );
+            assert_eq!(raw_genesis, Some(expected));
+        })
+    }
+
+    #[test]
+    fn complete_raw_genesis_matches_into_complete_raw_genesis() {
+        new_test_ext().execute_with(|| {
+            let domain_id = DomainId::new(1);
+            let runtime_obj = dummy_runtime_object();
+            acquire_runtime_code::<Test>(runtime_obj.hash, vec![1, 2, 3, 4]);
+
+            for domain_runtime_info in [
+                DomainRuntimeInfo::EVM { chain_id: 10 },
+                DomainRuntimeInfo::AutoId,
+            ] {
+                assert_eq!(
+                    runtime_obj.complete_raw_genesis::<Test>(domain_id, domain_runtime_info),
+                    runtime_obj
+                        .clone()
+                        .into_complete_raw_genesis::<Test>(domain_id, domain_runtime_info)
+                );
+            }
+            // The runtime object is left untouched.
+            assert_eq!(runtime_obj, dummy_runtime_object());
+        })
+    }
+
+    #[test]
+    fn runtime_code_is_shared_and_released() {
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 1,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+        let read_runtime_version = ReadRuntimeVersion(version.encode());
+
+        let mut ext = new_test_ext();
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            read_runtime_version,
+        ));
+        ext.execute_with(|| {
+            let raw_genesis = RawGenesis::dummy(vec![1, 2, 3, 4]);
+            for runtime_name in ["evm", "evm-2"] {
+                assert_ok!(crate::Pallet::<Test>::register_domain_runtime(
+                    RawOrigin::Root.into(),
+                    runtime_name.to_owned(),
+                    RuntimeType::Evm,
+                    raw_genesis.encode(),
+                ));
+            }
+
+            // Both runtimes share the same code.
+            let code_hash = RuntimeRegistry::<Test>::get(0).unwrap().hash;
+            assert_eq!(RuntimeRegistry::<Test>::get(1).unwrap().hash, code_hash);
+            assert_eq!(RuntimeCode::<Test>::iter().count(), 1);
+            assert_eq!(RuntimeCodeRefCount::<Test>::get(code_hash), 2);
+
+            // The complete raw genesis is identical to the one registered.
+            let mut expected = raw_genesis.clone();
+            expected.set_domain_id(DomainId::new(0));
+            expected.set_evm_chain_id(10);
+            assert_eq!(
+                RuntimeRegistry::<Test>::get(0)
+                    .unwrap()
+                    .into_complete_raw_genesis::<Test>(
+                        DomainId::new(0),
+                        DomainRuntimeInfo::EVM { chain_id: 10 }
+                    ),
+                Some(expected)
+            );
+
+            assert_ok!(crate::Pallet::<Test>::deregister_domain_runtime(
+                RawOrigin::Root.into(),
+                0
+            ));
+            assert_eq!(RuntimeCodeRefCount::<Test>::get(code_hash), 1);
+            assert!(RuntimeCode::<Test>::contains_key(code_hash));
+
+            assert_ok!(crate::Pallet::<Test>::deregister_domain_runtime(
+                RawOrigin::Root.into(),
+                1
+            ));
+            assert_eq!(RuntimeCodeRefCount::<Test>::get(code_hash), 0);
+            assert!(!RuntimeCode::<Test>::contains_key(code_hash));
+        })
+    }
+
+    #[test]
+    fn migrate_runtime_code_to_v1() {
+        new_test_ext().execute_with(|| {
+            StorageVersion::new(0).put::<Domains>();
+
+            // The runtime object is stored with the v0 layout, without the code size.
+            let code = vec![1, 2, 3, 4, 5];
+            let code_hash = BlakeTwo256::hash(&code);
+            let RuntimeObject {
+                runtime_name,
+                runtime_type,
+                runtime_upgrades,
+                version,
+                created_at,
+                updated_at,
+                ..
+            } = dummy_runtime_object();
+            frame_support::storage::unhashed::put(
+                &RuntimeRegistry::<Test>::hashed_key_for(0),
+                &v0::RuntimeObject {
+                    runtime_name,
+                    runtime_type,
+                    runtime_upgrades,
+                    hash: code_hash,
+                    raw_genesis: RawGenesis::dummy(code.clone()),
+                    version,
+                    created_at,
+                    updated_at,
+                },
+            );
+            // The scheduled upgrade is stored with the v0 layout as well.
+            let upgrade_code = vec![6, 7, 8, 9];
+            let upgrade_hash = BlakeTwo256::hash(&upgrade_code);
+            let upgrade_version = RuntimeVersion {
+                spec_version: 2,
+                ..Default::default()
+            };
+            frame_support::storage::unhashed::put(
+                &ScheduledRuntimeUpgrades::<Test>::hashed_key_for(10, 0),
+                &v0::ScheduledRuntimeUpgrade {
+                    raw_genesis: RawGenesis::dummy(upgrade_code.clone()),
+                    version: upgrade_version.clone(),
+                    hash: upgrade_hash,
+                },
+            );
+            // A domain uses the runtime.
+            DomainRegistry::<Test>::insert(
+                DomainId::new(0),
+                DomainObject {
+                    owner_account_id: Default::default(),
+                    created_at: Default::default(),
+                    genesis_receipt_hash: Default::default(),
+                    domain_config: DomainConfig {
+                        domain_name: "evm-domain".to_owned(),
+                        runtime_id: 0,
+                        max_block_size: 1u32,
+                        max_block_weight: Weight::from_parts(1, 0),
+                        bundle_slot_probability: (1, 1),
+                        target_bundles_per_block: 1,
+                        operator_allow_list: OperatorAllowList::Anyone,
+                    },
+                    domain_runtime_info: Default::default(),
+                },
+            );
+
+            MigrateToV1::<Test>::on_runtime_upgrade();
+
+            assert_eq!(Domains::on_chain_storage_version(), StorageVersion::new(1));
+            assert_eq!(RuntimeCode::<Test>::get(code_hash), Some(code.clone()));
+            assert_eq!(RuntimeCodeRefCount::<Test>::get(code_hash), 1);
+            assert_eq!(RuntimeCode::<Test>::get(upgrade_hash), Some(upgrade_code));
+            assert_eq!(RuntimeCodeRefCount::<Test>::get(upgrade_hash), 1);
+
+            let runtime_obj = RuntimeRegistry::<Test>::get(0).unwrap();
+            assert_eq!(runtime_obj.code_size, code.len() as u32);
+            assert_eq!(
+                RuntimeNames::<Test>::get(&runtime_obj.runtime_name),
+                Some(0)
+            );
+            assert!(runtime_obj.raw_genesis.get_runtime_code().is_none());
+            let scheduled_upgrade = ScheduledRuntimeUpgrades::<Test>::get(10, 0)
+                .expect("Scheduled upgrade must survive the migration");
+            assert_eq!(scheduled_upgrade.version, upgrade_version);
+            assert_eq!(scheduled_upgrade.hash, upgrade_hash);
+            assert!(scheduled_upgrade.raw_genesis.get_runtime_code().is_none());
+            // The upgrade in flight is still found through the runtime api.
+            assert_eq!(ScheduledRuntimeUpgradeAt::<Test>::get(0), Some(10));
+            assert_eq!(RuntimeDomainCount::<Test>::get(0), 1);
+            assert_eq!(
+                Domains::pending_runtime_upgrade(0),
+                Some((10, upgrade_version, upgrade_hash))
+            );
+
+            // The complete raw genesis is unchanged by the migration.
+            let domain_id = DomainId::new(0);
+            let mut expected = RawGenesis::dummy(code);
+            expected.set_domain_id(domain_id);
+            assert_eq!(
+                runtime_obj.into_complete_raw_genesis::<Test>(domain_id, DomainRuntimeInfo::AutoId),
+                Some(expected)
+            );
+        })
+    }
+
+    fn dummy_scheduled_upgrade() -> ScheduledRuntimeUpgrade<H256> {
+        ScheduledRuntimeUpgrade {
+            raw_genesis: RawGenesis::dummy(vec![6, 7, 8, 9]),
+            version: Default::default(),
+            hash: Default::default(),
+        }
+    }
+
+    #[test]
+    fn deregister_domain_runtime_in_use() {
+        let mut ext = new_test_ext();
+        ext.execute_with(|| {
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+            RuntimeDomainCount::<Test>::insert(0, 1);
+
+            let res = crate::Pallet::<Test>::deregister_domain_runtime(RawOrigin::Root.into(), 0);
+            assert_eq!(
+                res,
+                Err(Error::<Test>::RuntimeRegistry(RuntimeRegistryError::RuntimeInUse).into())
+            );
+            assert!(RuntimeRegistry::<Test>::contains_key(0));
+        })
+    }
+
+    #[test]
+    fn deregister_domain_runtime() {
+        let mut ext = new_test_ext();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+            RuntimeRegistry::<Test>::insert(1, dummy_runtime_object());
+            ScheduledRuntimeUpgrades::<Test>::insert(10, 0, dummy_scheduled_upgrade());
+            ScheduledRuntimeUpgradeAt::<Test>::insert(0, 10);
+            ScheduledRuntimeUpgrades::<Test>::insert(10, 1, dummy_scheduled_upgrade());
+            ScheduledRuntimeUpgradeAt::<Test>::insert(1, 10);
+            RuntimeUpgradeHistory::<Test>::insert(
+                0,
+                1,
+                (RuntimeVersion::default(), H256::zero(), 5),
+            );
+
+            assert_ok!(crate::Pallet::<Test>::deregister_domain_runtime(
+                RawOrigin::Root.into(),
+                0
+            ));
+
+            assert!(!RuntimeRegistry::<Test>::contains_key(0));
+            assert!(RuntimeRegistry::<Test>::contains_key(1));
+            assert!(!ScheduledRuntimeUpgrades::<Test>::contains_key(10, 0));
+            assert!(!ScheduledRuntimeUpgradeAt::<Test>::contains_key(0));
+            assert!(ScheduledRuntimeUpgrades::<Test>::contains_key(10, 1));
+            assert_eq!(ScheduledRuntimeUpgradeAt::<Test>::get(1), Some(10));
+            assert!(!RuntimeUpgradeHistory::<Test>::contains_key(0, 1));
+            System::assert_last_event(RuntimeEvent::Domains(
+                crate::Event::DomainRuntimeDeregistered { runtime_id: 0 },
+            ));
+
+            let res = crate::Pallet::<Test>::deregister_domain_runtime(RawOrigin::Root.into(), 0);
+            assert_eq!(
+                res,
+                Err(
+                    Error::<Test>::RuntimeRegistry(RuntimeRegistryError::MissingRuntimeObject)
+                        .into()
+                )
+            );
+        })
+    }
+
+    fn go_to_block(block: u64) {
         for i in System::block_number() + 1..=block {
             let parent_hash = if System::block_number() > 1 {
                 let header = System::finalize();
@@ -413,6 +1366,96 @@ mod tests {
         }
     }
 
+    #[test]
+    fn runtime_upgrades_over_block_limit_are_deferred() {
+        let mut ext = new_test_ext();
+        ext.execute_with(|| {
+            let max_upgrades = MaxRuntimeUpgradesPerBlock::get();
+            let scheduled_at = 10;
+            for runtime_id in 0..=max_upgrades {
+                RuntimeRegistry::<Test>::insert(runtime_id, dummy_runtime_object());
+                ScheduledRuntimeUpgrades::<Test>::insert(
+                    scheduled_at,
+                    runtime_id,
+                    ScheduledRuntimeUpgrade {
+                        raw_genesis: RawGenesis::dummy(vec![6, 7, 8, 9]),
+                        version: Default::default(),
+                        hash: Default::default(),
+                    },
+                );
+                ScheduledRuntimeUpgradeAt::<Test>::insert(runtime_id, scheduled_at);
+            }
+
+            let weight = do_upgrade_runtimes::<Test>(scheduled_at);
+            assert!(weight.ref_time() > 0);
+
+            // The upgrades are applied in the order of runtime id, the last one is deferred.
+            for runtime_id in 0..max_upgrades {
+                let runtime_obj = RuntimeRegistry::<Test>::get(runtime_id).unwrap();
+                assert_eq!(runtime_obj.runtime_upgrades, 1);
+                assert!(!ScheduledRuntimeUpgradeAt::<Test>::contains_key(runtime_id));
+            }
+            let deferred_runtime_id = max_upgrades;
+            assert_eq!(
+                RuntimeRegistry::<Test>::get(deferred_runtime_id)
+                    .unwrap()
+                    .runtime_upgrades,
+                0
+            );
+            assert_eq!(
+                ScheduledRuntimeUpgrades::<Test>::iter_prefix(scheduled_at).count(),
+                0
+            );
+            assert!(ScheduledRuntimeUpgrades::<Test>::contains_key(
+                scheduled_at + 1,
+                deferred_runtime_id
+            ));
+            assert_eq!(
+                ScheduledRuntimeUpgradeAt::<Test>::get(deferred_runtime_id),
+                Some(scheduled_at + 1)
+            );
+
+            do_upgrade_runtimes::<Test>(scheduled_at + 1);
+            assert_eq!(
+                RuntimeRegistry::<Test>::get(deferred_runtime_id)
+                    .unwrap()
+                    .runtime_upgrades,
+                1
+            );
+            assert!(!ScheduledRuntimeUpgradeAt::<Test>::contains_key(
+                deferred_runtime_id
+            ));
+        });
+    }
+
+    #[test]
+    fn upgrade_runtimes_weight_accounts_for_code_size() {
+        let upgrade_weight = |code: Vec<u8>| {
+            let mut ext = new_test_ext();
+            ext.execute_with(|| {
+                let scheduled_at = 10;
+                let code_hash = BlakeTwo256::hash(&code);
+                RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+                acquire_runtime_code::<Test>(code_hash, code);
+                ScheduledRuntimeUpgrades::<Test>::insert(
+                    scheduled_at,
+                    0,
+                    ScheduledRuntimeUpgrade {
+                        raw_genesis: RawGenesis::dummy(vec![6, 7, 8, 9]),
+                        version: Default::default(),
+                        hash: code_hash,
+                    },
+                );
+                ScheduledRuntimeUpgradeAt::<Test>::insert(0, scheduled_at);
+
+                do_upgrade_runtimes::<Test>(scheduled_at)
+            })
+        };
+
+        // The code is stored separately from the raw genesis, but it is still part of the upgrade
+        assert!(upgrade_weight(vec![1; 1024]).ref_time() > upgrade_weight(vec![1]).ref_time());
+    }
+
     fn fetch_upgraded_runtime_from_digest(digest: Digest) -> Option<RuntimeId> {
         for log in digest.logs {
             match log.as_domain_runtime_upgrade() {
@@ -451,6 +1494,7 @@ mod tests {
                     version: version.clone(),
                     created_at: Default::default(),
                     updated_at: Default::default(),
+                    code_size: 4,
                 },
             );
 
@@ -476,17 +1520,534 @@ mod tests {
                 .checked_add(DomainRuntimeUpgradeDelay::get())
                 .unwrap();
 
+            System::assert_last_event(RuntimeEvent::Domains(
+                crate::Event::DomainRuntimeUpgradeScheduled {
+                    runtime_id: 0,
+                    scheduled_at: scheduled_block_number,
+                    new_spec_version: 2,
+                },
+            ));
+            assert!(System::digest()
+                .logs
+                .iter()
+                .any(|log| log.as_domain_runtime_upgrade_scheduled() == Some((0, 2))));
+
+            let scheduled_upgrade =
+                ScheduledRuntimeUpgrades::<Test>::get(scheduled_block_number, 0).unwrap();
+            assert_eq!(
+                Domains::pending_runtime_upgrade(0),
+                Some((
+                    scheduled_block_number,
+                    version.clone(),
+                    scheduled_upgrade.hash
+                ))
+            );
+
             go_to_block(scheduled_block_number);
             assert_eq!(
                 ScheduledRuntimeUpgrades::<Test>::get(scheduled_block_number, 0),
                 None
             );
+            assert_eq!(Domains::pending_runtime_upgrade(0), None);
 
             let runtime_obj = RuntimeRegistry::<Test>::get(0).unwrap();
             assert_eq!(runtime_obj.version, version);
+            assert_eq!(runtime_obj.hash, scheduled_upgrade.hash);
+            assert_eq!(RuntimeCodeRefCount::<Test>::get(scheduled_upgrade.hash), 1);
+
+            // The upgraded runtime produces the same genesis as the raw genesis it was
+            // upgraded with.
+            let domain_id = DomainId::new(0);
+            let mut expected = RawGenesis::dummy(vec![6, 7, 8, 9]);
+            expected.set_domain_id(domain_id);
+            assert_eq!(
+                runtime_obj.into_complete_raw_genesis::<Test>(domain_id, DomainRuntimeInfo::AutoId),
+                Some(expected)
+            );
 
             let digest = System::digest();
             assert_eq!(Some(0), fetch_upgraded_runtime_from_digest(digest))
         });
     }
+
+    #[test]
+    fn runtime_upgrade_history_records_past_upgrades() {
+        let mut ext = new_test_ext();
+        ext.execute_with(|| {
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+            NextRuntimeId::<Test>::set(1);
+
+            let upgrades: Vec<_> = (2..5)
+                .map(|spec_version| {
+                    let mut scheduled_upgrade = dummy_scheduled_upgrade();
+                    scheduled_upgrade.version.spec_version = spec_version;
+                    scheduled_upgrade.hash = H256::repeat_byte(spec_version as u8);
+                    scheduled_upgrade
+                })
+                .collect();
+
+            for (block_number, scheduled_upgrade) in (2..).zip(upgrades.iter().take(2)) {
+                ScheduledRuntimeUpgrades::<Test>::insert(block_number, 0, scheduled_upgrade);
+            }
+            go_to_block(3);
+
+            for (upgrade_index, scheduled_upgrade) in (1..).zip(upgrades.iter().take(2)) {
+                assert_eq!(
+                    Domains::runtime_upgrade_history(0, upgrade_index),
+                    Some((
+                        scheduled_upgrade.version.clone(),
+                        scheduled_upgrade.hash,
+                        upgrade_index as u64 + 1
+                    ))
+                );
+            }
+            assert_eq!(Domains::runtime_upgrade_history(0, 3), None);
+
+            // Only the last `MaxRuntimeUpgradeHistory` upgrades are kept.
+            ScheduledRuntimeUpgrades::<Test>::insert(4, 0, &upgrades[2]);
+            go_to_block(4);
+            assert_eq!(Domains::runtime_upgrade_history(0, 1), None);
+            assert_eq!(
+                Domains::runtime_upgrade_history(0, 3),
+                Some((upgrades[2].version.clone(), upgrades[2].hash, 4))
+            );
+            assert_eq!(RuntimeUpgradeHistory::<Test>::iter_prefix(0).count(), 2);
+        });
+    }
+
+    #[test]
+    fn immediate_domain_runtime_upgrade() {
+        let mut ext = new_test_ext();
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 2,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+
+        ext.execute_with(|| {
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+            NextRuntimeId::<Test>::set(1);
+        });
+
+        let read_runtime_version = ReadRuntimeVersion(version.encode());
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            read_runtime_version,
+        ));
+
+        ext.execute_with(|| {
+            AllowImmediateRuntimeUpgrade::set(true);
+            go_to_block(1);
+
+            assert_ok!(crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(vec![6, 7, 8, 9, 10]).encode(),
+            ));
+            assert!(ScheduledRuntimeUpgrades::<Test>::contains_key(2, 0));
+
+            go_to_block(2);
+            AllowImmediateRuntimeUpgrade::set(false);
+
+            let runtime_obj = RuntimeRegistry::<Test>::get(0).unwrap();
+            assert_eq!(runtime_obj.version, version);
+            assert_eq!(runtime_obj.updated_at, 2);
+            assert_eq!(runtime_obj.code_size, 5);
+            assert_eq!(
+                Some(0),
+                fetch_upgraded_runtime_from_digest(System::digest())
+            );
+        });
+    }
+
+    #[test]
+    fn scheduled_runtime_upgrades_returns_pending_upgrades() {
+        let mut ext = new_test_ext();
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 2,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+
+        ext.execute_with(|| {
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+            RuntimeRegistry::<Test>::insert(1, dummy_runtime_object());
+            NextRuntimeId::<Test>::set(2);
+        });
+
+        let read_runtime_version = ReadRuntimeVersion(version.encode());
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            read_runtime_version,
+        ));
+
+        ext.execute_with(|| {
+            assert!(Domains::scheduled_runtime_upgrades().is_empty());
+
+            let mut expected = Vec::new();
+            for (runtime_id, block_number) in [(1, 3), (0, 5)] {
+                go_to_block(block_number);
+                assert_ok!(crate::Pallet::<Test>::upgrade_domain_runtime(
+                    RawOrigin::Root.into(),
+                    runtime_id,
+                    RawGenesis::dummy(vec![6, 7, 8, 9]).encode(),
+                ));
+                expected.push((
+                    block_number + DomainRuntimeUpgradeDelay::get(),
+                    runtime_id,
+                    version.clone(),
+                ));
+            }
+
+            assert_eq!(Domains::scheduled_runtime_upgrades(), expected);
+        });
+    }
+
+    #[test]
+    fn schedule_domain_runtime_upgrade_twice() {
+        let mut ext = new_test_ext();
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 2,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+
+        ext.execute_with(|| {
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+            NextRuntimeId::<Test>::set(1);
+        });
+
+        let read_runtime_version = ReadRuntimeVersion(version.encode());
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            read_runtime_version,
+        ));
+
+        ext.execute_with(|| {
+            go_to_block(1);
+            assert_ok!(crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(vec![6, 7, 8, 9]).encode(),
+            ));
+
+            let scheduled_block_number = 1 + DomainRuntimeUpgradeDelay::get();
+
+            // Another upgrade in the same block is rejected.
+            let res = crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(vec![10, 11, 12, 13]).encode(),
+            );
+            assert_eq!(
+                res,
+                Err(Error::<Test>::RuntimeRegistry(
+                    RuntimeRegistryError::RuntimeUpgradeAlreadyScheduled
+                )
+                .into())
+            );
+
+            // Another upgrade at a later block is rejected while the first one is pending.
+            go_to_block(2);
+            let res = crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(vec![10, 11, 12, 13]).encode(),
+            );
+            assert_eq!(
+                res,
+                Err(Error::<Test>::RuntimeRegistry(
+                    RuntimeRegistryError::RuntimeUpgradeAlreadyScheduled
+                )
+                .into())
+            );
+
+            // The rejected upgrades neither replaced the pending upgrade nor scheduled another.
+            assert_eq!(
+                ScheduledRuntimeUpgradeAt::<Test>::get(0),
+                Some(scheduled_block_number)
+            );
+            assert_eq!(
+                ScheduledRuntimeUpgrades::<Test>::iter_keys().collect::<Vec<_>>(),
+                vec![(scheduled_block_number, 0)]
+            );
+            assert_eq!(
+                ScheduledRuntimeUpgrades::<Test>::get(scheduled_block_number, 0)
+                    .unwrap()
+                    .hash,
+                BlakeTwo256::hash(&[6, 7, 8, 9])
+            );
+        });
+    }
+
+    #[test]
+    fn schedule_domain_runtime_upgrades_back_to_back() {
+        let mut ext = new_test_ext();
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 2,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+
+        ext.execute_with(|| {
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+            NextRuntimeId::<Test>::set(1);
+        });
+
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            ReadRuntimeVersion(version.encode()),
+        ));
+
+        ext.execute_with(|| {
+            go_to_block(1);
+            assert_ok!(crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(vec![6, 7, 8, 9]).encode(),
+            ));
+            let first_scheduled_at = 1 + DomainRuntimeUpgradeDelay::get();
+            go_to_block(first_scheduled_at);
+            assert_eq!(RuntimeRegistry::<Test>::get(0).unwrap().version, version);
+        });
+
+        // The next upgrade can be scheduled in the very block the previous one is applied.
+        let next_version = RuntimeVersion {
+            spec_version: 3,
+            ..version
+        };
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            ReadRuntimeVersion(next_version.encode()),
+        ));
+
+        ext.execute_with(|| {
+            let first_scheduled_at = 1 + DomainRuntimeUpgradeDelay::get();
+            assert_ok!(crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(vec![10, 11, 12, 13]).encode(),
+            ));
+            let second_scheduled_at = first_scheduled_at + DomainRuntimeUpgradeDelay::get();
+            System::assert_last_event(RuntimeEvent::Domains(
+                crate::Event::DomainRuntimeUpgradeScheduled {
+                    runtime_id: 0,
+                    scheduled_at: second_scheduled_at,
+                    new_spec_version: next_version.spec_version,
+                },
+            ));
+
+            go_to_block(second_scheduled_at);
+            let runtime_obj = RuntimeRegistry::<Test>::get(0).unwrap();
+            assert_eq!(runtime_obj.version, next_version);
+            assert_eq!(runtime_obj.runtime_upgrades, 2);
+            assert_eq!(runtime_obj.hash, BlakeTwo256::hash(&[10, 11, 12, 13]));
+        });
+    }
+
+    #[test]
+    fn cancel_scheduled_domain_runtime_upgrade() {
+        let mut ext = new_test_ext();
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 2,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+
+        ext.execute_with(|| {
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+            NextRuntimeId::<Test>::set(1);
+        });
+
+        let read_runtime_version = ReadRuntimeVersion(version.encode());
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            read_runtime_version,
+        ));
+
+        ext.execute_with(|| {
+            go_to_block(1);
+            assert_ok!(crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(vec![6, 7, 8, 9]).encode(),
+            ));
+
+            let scheduled_block_number = frame_system::Pallet::<Test>::current_block_number()
+                .checked_add(DomainRuntimeUpgradeDelay::get())
+                .unwrap();
+
+            assert_ok!(crate::Pallet::<Test>::cancel_domain_runtime_upgrade(
+                RawOrigin::Root.into(),
+                0,
+            ));
+            System::assert_last_event(RuntimeEvent::Domains(
+                crate::Event::DomainRuntimeUpgradeCancelled {
+                    runtime_id: 0,
+                    scheduled_at: scheduled_block_number,
+                },
+            ));
+
+            // Nothing is left to cancel.
+            let res =
+                crate::Pallet::<Test>::cancel_domain_runtime_upgrade(RawOrigin::Root.into(), 0);
+            assert_eq!(
+                res,
+                Err(
+                    Error::<Test>::RuntimeRegistry(RuntimeRegistryError::NoScheduledUpgrade).into()
+                )
+            );
+
+            go_to_block(scheduled_block_number + 1);
+
+            let runtime_obj = RuntimeRegistry::<Test>::get(0).unwrap();
+            assert_eq!(runtime_obj, dummy_runtime_object());
+            assert_eq!(None, fetch_upgraded_runtime_from_digest(System::digest()));
+        });
+    }
+
+    #[test]
+    fn scheduled_runtime_upgrade_at_follows_pending_upgrade() {
+        let mut ext = new_test_ext();
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 2,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+
+        ext.execute_with(|| {
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+            NextRuntimeId::<Test>::set(1);
+        });
+
+        let read_runtime_version = ReadRuntimeVersion(version.encode());
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            read_runtime_version,
+        ));
+
+        ext.execute_with(|| {
+            go_to_block(1);
+            assert!(!ScheduledRuntimeUpgradeAt::<Test>::contains_key(0));
+
+            assert_ok!(crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(vec![6, 7, 8, 9]).encode(),
+            ));
+            let scheduled_block_number = 1 + DomainRuntimeUpgradeDelay::get();
+            assert_eq!(
+                ScheduledRuntimeUpgradeAt::<Test>::get(0),
+                Some(scheduled_block_number)
+            );
+
+            assert_ok!(crate::Pallet::<Test>::cancel_domain_runtime_upgrade(
+                RawOrigin::Root.into(),
+                0,
+            ));
+            assert!(!ScheduledRuntimeUpgradeAt::<Test>::contains_key(0));
+
+            // The upgrade can be scheduled again once cancelled and is then applied as usual.
+            go_to_block(2);
+            assert_ok!(crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(vec![6, 7, 8, 9]).encode(),
+            ));
+            let rescheduled_block_number = 2 + DomainRuntimeUpgradeDelay::get();
+            assert_eq!(
+                ScheduledRuntimeUpgradeAt::<Test>::get(0),
+                Some(rescheduled_block_number)
+            );
+
+            go_to_block(rescheduled_block_number);
+            let runtime_obj = RuntimeRegistry::<Test>::get(0).unwrap();
+            assert_eq!(runtime_obj.version, version);
+            assert_eq!(runtime_obj.updated_at, rescheduled_block_number);
+            assert!(!ScheduledRuntimeUpgradeAt::<Test>::contains_key(0));
+        });
+    }
+
+    #[test]
+    fn cancel_then_reschedule_domain_runtime_upgrade() {
+        let mut ext = new_test_ext();
+        let version = RuntimeVersion {
+            spec_name: "test".into(),
+            spec_version: 2,
+            impl_version: 1,
+            transaction_version: 1,
+            ..Default::default()
+        };
+
+        ext.execute_with(|| {
+            RuntimeRegistry::<Test>::insert(0, dummy_runtime_object());
+            NextRuntimeId::<Test>::set(1);
+        });
+
+        let read_runtime_version = ReadRuntimeVersion(version.encode());
+        ext.register_extension(sp_core::traits::ReadRuntimeVersionExt::new(
+            read_runtime_version,
+        ));
+
+        ext.execute_with(|| {
+            let cancelled_code = vec![6, 7, 8, 9];
+            let cancelled_code_hash = BlakeTwo256::hash(&cancelled_code);
+            go_to_block(1);
+            assert_ok!(crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(cancelled_code).encode(),
+            ));
+            assert!(RuntimeCode::<Test>::contains_key(cancelled_code_hash));
+
+            go_to_block(2);
+            assert_ok!(crate::Pallet::<Test>::cancel_domain_runtime_upgrade(
+                RawOrigin::Root.into(),
+                0,
+            ));
+            System::assert_last_event(RuntimeEvent::Domains(
+                crate::Event::DomainRuntimeUpgradeCancelled {
+                    runtime_id: 0,
+                    scheduled_at: 1 + DomainRuntimeUpgradeDelay::get(),
+                },
+            ));
+            // The code of the cancelled upgrade is not referenced anymore.
+            assert!(!RuntimeCode::<Test>::contains_key(cancelled_code_hash));
+
+            let code = vec![10, 11, 12, 13];
+            let code_hash = BlakeTwo256::hash(&code);
+            assert_ok!(crate::Pallet::<Test>::upgrade_domain_runtime(
+                RawOrigin::Root.into(),
+                0,
+                RawGenesis::dummy(code.clone()).encode(),
+            ));
+            let scheduled_block_number = 2 + DomainRuntimeUpgradeDelay::get();
+            System::assert_last_event(RuntimeEvent::Domains(
+                crate::Event::DomainRuntimeUpgradeScheduled {
+                    runtime_id: 0,
+                    scheduled_at: scheduled_block_number,
+                    new_spec_version: version.spec_version,
+                },
+            ));
+
+            // Only the rescheduled upgrade is applied, at its own block.
+            go_to_block(1 + DomainRuntimeUpgradeDelay::get());
+            assert_eq!(
+                RuntimeRegistry::<Test>::get(0).unwrap(),
+                dummy_runtime_object()
+            );
+
+            go_to_block(scheduled_block_number);
+            let runtime_obj = RuntimeRegistry::<Test>::get(0).unwrap();
+            assert_eq!(runtime_obj.version, version);
+            assert_eq!(runtime_obj.hash, code_hash);
+            assert_eq!(runtime_obj.runtime_upgrades, 1);
+            assert_eq!(RuntimeCode::<Test>::get(code_hash), Some(code));
+        });
+    }
 }
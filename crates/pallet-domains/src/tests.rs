@@ -12,12 +12,15 @@ use core::mem;
 use domain_runtime_primitives::opaque::Header as DomainHeader;
 use domain_runtime_primitives::BlockNumber as DomainBlockNumber;
 use frame_support::dispatch::{DispatchInfo, RawOrigin};
-use frame_support::traits::{ConstU16, ConstU32, ConstU64, Currency, Hooks, VariantCount};
+use frame_support::traits::{
+    ConstU16, ConstU32, ConstU64, Contains, Currency, Hooks, VariantCount,
+};
 use frame_support::weights::constants::RocksDbWeight;
 use frame_support::weights::{IdentityFee, Weight};
 use frame_support::{assert_err, assert_ok, parameter_types, PalletId};
 use frame_system::mocking::MockUncheckedExtrinsic;
 use frame_system::pallet_prelude::*;
+use frame_system::EnsureRoot;
 use scale_info::TypeInfo;
 use sp_core::crypto::Pair;
 use sp_core::storage::{StateVersion, StorageKey};
@@ -114,6 +117,20 @@ parameter_types! {
     pub const DomainInstantiationDeposit: Balance = 100;
     pub const MaxDomainNameLength: u32 = 16;
     pub const BlockTreePruningDepth: u32 = 16;
+    pub const MaxRuntimeUpgradeHistory: u32 = 2;
+    pub const MaxRuntimeUpgradesPerBlock: u32 = 2;
+    pub const MaxRuntimeNameLength: u32 = 16;
+    pub const MaxDomainRuntimeCodeSize: u32 = 1024 * 1024;
+    pub static AllowImmediateRuntimeUpgrade: bool = false;
+    pub static FrozenDomain: Option<DomainId> = None;
+}
+
+pub struct FrozenDomains;
+
+impl Contains<DomainId> for FrozenDomains {
+    fn contains(domain_id: &DomainId) -> bool {
+        FrozenDomain::get() == Some(*domain_id)
+    }
 }
 
 static CONFIRMATION_DEPTH_K: AtomicU64 = AtomicU64::new(10);
@@ -237,6 +254,13 @@ impl pallet_domains::Config for Test {
     type MaxNominators = MaxNominators;
     type Randomness = MockRandomness;
     type SudoId = ();
+    type RuntimeUpgradeOrigin = EnsureRoot<AccountId>;
+    type MaxRuntimeUpgradeHistory = MaxRuntimeUpgradeHistory;
+    type MaxRuntimeUpgradesPerBlock = MaxRuntimeUpgradesPerBlock;
+    type MaxRuntimeNameLength = MaxRuntimeNameLength;
+    type MaxDomainRuntimeCodeSize = MaxDomainRuntimeCodeSize;
+    type AllowImmediateRuntimeUpgrade = AllowImmediateRuntimeUpgrade;
+    type FrozenDomains = FrozenDomains;
 }
 
 pub struct ExtrinsicStorageFees;
@@ -787,6 +811,26 @@ fn test_calculate_tx_range() {
     );
 }
 
+#[test]
+fn bundle_of_frozen_domain_is_rejected() {
+    new_test_ext().execute_with(|| {
+        let bundle = create_dummy_bundle(DOMAIN_ID, 0, System::parent_hash());
+
+        FrozenDomain::set(Some(DOMAIN_ID));
+        assert_err!(
+            pallet_domains::Pallet::<Test>::validate_bundle(&bundle),
+            BundleError::FrozenDomain
+        );
+
+        // Freezing another domain doesn't affect the bundle
+        FrozenDomain::set(Some(DomainId::new(1)));
+        assert_ne!(
+            pallet_domains::Pallet::<Test>::validate_bundle(&bundle),
+            Err(BundleError::FrozenDomain)
+        );
+    });
+}
+
 #[test]
 fn test_bundle_fromat_verification() {
     let opaque_extrinsic = |dest: u64, value: u128| -> OpaqueExtrinsic {
@@ -35,6 +35,7 @@ pub trait WeightInfo {
 	fn pending_staking_operation() -> Weight;
 	fn register_domain_runtime() -> Weight;
 	fn upgrade_domain_runtime() -> Weight;
+	fn upgrade_scheduled_runtime(s: u32, ) -> Weight;
 	fn instantiate_domain() -> Weight;
 	fn register_operator() -> Weight;
 	fn nominate_operator() -> Weight;
@@ -42,6 +43,8 @@ pub trait WeightInfo {
 	fn deregister_operator() -> Weight;
 	fn withdraw_stake() -> Weight;
 	fn auto_stake_block_rewards() -> Weight;
+	fn deregister_domain_runtime() -> Weight;
+	fn cancel_domain_runtime_upgrade() -> Weight;
 }
 
 /// Weights for pallet_domains using the Substrate node and recommended hardware.
@@ -135,6 +138,30 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// NOTE: placeholder weight estimated from the storage accesses below, it is not generated by
+	/// the benchmark CLI yet, re-run the `upgrade_scheduled_runtime` benchmark to replace it.
+	/// Storage: Domains ScheduledRuntimeUpgrades (r:1 w:1)
+	/// Proof Skipped: Domains ScheduledRuntimeUpgrades (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeRegistry (r:1 w:1)
+	/// Proof Skipped: Domains RuntimeRegistry (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeCode (r:1 w:1)
+	/// Proof Skipped: Domains RuntimeCode (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeCodeRefCount (r:1 w:1)
+	/// Proof Skipped: Domains RuntimeCodeRefCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Digest (r:1 w:1)
+	/// Proof Skipped: System Digest (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Domains ScheduledRuntimeUpgradeAt (r:0 w:1)
+	/// Proof Skipped: Domains ScheduledRuntimeUpgradeAt (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeUpgradeHistory (r:0 w:2)
+	/// Proof Skipped: Domains RuntimeUpgradeHistory (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `s` is `[1, 4194304]`.
+	fn upgrade_scheduled_runtime(s: u32, ) -> Weight {
+		Weight::from_parts(29_000_000, 3514)
+			.saturating_add(Weight::from_parts(1_150, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(Weight::from_parts(0, 2).saturating_mul(s.into()))
+	}
 	/// Storage: Domains RuntimeRegistry (r:1 w:0)
 	/// Proof Skipped: Domains RuntimeRegistry (max_values: None, max_size: None, mode: Measured)
 	/// Storage: System Account (r:1 w:1)
@@ -290,6 +317,44 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// NOTE: placeholder weight estimated from the storage accesses below, it is not generated by
+	/// the benchmark CLI yet, re-run the `deregister_domain_runtime` benchmark to replace it.
+	/// Storage: Domains RuntimeRegistry (r:1 w:1)
+	/// Proof Skipped: Domains RuntimeRegistry (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeDomainCount (r:1 w:0)
+	/// Proof Skipped: Domains RuntimeDomainCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains ScheduledRuntimeUpgradeAt (r:1 w:1)
+	/// Proof Skipped: Domains ScheduledRuntimeUpgradeAt (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains ScheduledRuntimeUpgrades (r:1 w:1)
+	/// Proof Skipped: Domains ScheduledRuntimeUpgrades (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeCodeRefCount (r:2 w:2)
+	/// Proof Skipped: Domains RuntimeCodeRefCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeCode (r:0 w:2)
+	/// Proof Skipped: Domains RuntimeCode (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeUpgradeHistory (r:0 w:16)
+	/// Proof Skipped: Domains RuntimeUpgradeHistory (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeNames (r:1 w:1)
+	/// Proof Skipped: Domains RuntimeNames (max_values: None, max_size: None, mode: Measured)
+	fn deregister_domain_runtime() -> Weight {
+		Weight::from_parts(50_000_000, 10_000)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(24_u64))
+	}
+	/// NOTE: placeholder weight estimated from the storage accesses below, it is not generated by
+	/// the benchmark CLI yet, re-run the `cancel_domain_runtime_upgrade` benchmark to replace it.
+	/// Storage: Domains ScheduledRuntimeUpgradeAt (r:1 w:1)
+	/// Proof Skipped: Domains ScheduledRuntimeUpgradeAt (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains ScheduledRuntimeUpgrades (r:1 w:1)
+	/// Proof Skipped: Domains ScheduledRuntimeUpgrades (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeCodeRefCount (r:1 w:1)
+	/// Proof Skipped: Domains RuntimeCodeRefCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeCode (r:0 w:1)
+	/// Proof Skipped: Domains RuntimeCode (max_values: None, max_size: None, mode: Measured)
+	fn cancel_domain_runtime_upgrade() -> Weight {
+		Weight::from_parts(25_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -382,6 +447,30 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// NOTE: placeholder weight estimated from the storage accesses below, it is not generated by
+	/// the benchmark CLI yet, re-run the `upgrade_scheduled_runtime` benchmark to replace it.
+	/// Storage: Domains ScheduledRuntimeUpgrades (r:1 w:1)
+	/// Proof Skipped: Domains ScheduledRuntimeUpgrades (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeRegistry (r:1 w:1)
+	/// Proof Skipped: Domains RuntimeRegistry (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeCode (r:1 w:1)
+	/// Proof Skipped: Domains RuntimeCode (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeCodeRefCount (r:1 w:1)
+	/// Proof Skipped: Domains RuntimeCodeRefCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Digest (r:1 w:1)
+	/// Proof Skipped: System Digest (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Domains ScheduledRuntimeUpgradeAt (r:0 w:1)
+	/// Proof Skipped: Domains ScheduledRuntimeUpgradeAt (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeUpgradeHistory (r:0 w:2)
+	/// Proof Skipped: Domains RuntimeUpgradeHistory (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `s` is `[1, 4194304]`.
+	fn upgrade_scheduled_runtime(s: u32, ) -> Weight {
+		Weight::from_parts(29_000_000, 3514)
+			.saturating_add(Weight::from_parts(1_150, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(Weight::from_parts(0, 2).saturating_mul(s.into()))
+	}
 	/// Storage: Domains RuntimeRegistry (r:1 w:0)
 	/// Proof Skipped: Domains RuntimeRegistry (max_values: None, max_size: None, mode: Measured)
 	/// Storage: System Account (r:1 w:1)
@@ -537,4 +626,42 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// NOTE: placeholder weight estimated from the storage accesses below, it is not generated by
+	/// the benchmark CLI yet, re-run the `deregister_domain_runtime` benchmark to replace it.
+	/// Storage: Domains RuntimeRegistry (r:1 w:1)
+	/// Proof Skipped: Domains RuntimeRegistry (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeDomainCount (r:1 w:0)
+	/// Proof Skipped: Domains RuntimeDomainCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains ScheduledRuntimeUpgradeAt (r:1 w:1)
+	/// Proof Skipped: Domains ScheduledRuntimeUpgradeAt (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains ScheduledRuntimeUpgrades (r:1 w:1)
+	/// Proof Skipped: Domains ScheduledRuntimeUpgrades (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeCodeRefCount (r:2 w:2)
+	/// Proof Skipped: Domains RuntimeCodeRefCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeCode (r:0 w:2)
+	/// Proof Skipped: Domains RuntimeCode (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeUpgradeHistory (r:0 w:16)
+	/// Proof Skipped: Domains RuntimeUpgradeHistory (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeNames (r:1 w:1)
+	/// Proof Skipped: Domains RuntimeNames (max_values: None, max_size: None, mode: Measured)
+	fn deregister_domain_runtime() -> Weight {
+		Weight::from_parts(50_000_000, 10_000)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(24_u64))
+	}
+	/// NOTE: placeholder weight estimated from the storage accesses below, it is not generated by
+	/// the benchmark CLI yet, re-run the `cancel_domain_runtime_upgrade` benchmark to replace it.
+	/// Storage: Domains ScheduledRuntimeUpgradeAt (r:1 w:1)
+	/// Proof Skipped: Domains ScheduledRuntimeUpgradeAt (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains ScheduledRuntimeUpgrades (r:1 w:1)
+	/// Proof Skipped: Domains ScheduledRuntimeUpgrades (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeCodeRefCount (r:1 w:1)
+	/// Proof Skipped: Domains RuntimeCodeRefCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Domains RuntimeCode (r:0 w:1)
+	/// Proof Skipped: Domains RuntimeCode (max_values: None, max_size: None, mode: Measured)
+	fn cancel_domain_runtime_upgrade() -> Weight {
+		Weight::from_parts(25_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
 }
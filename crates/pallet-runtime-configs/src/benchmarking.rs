@@ -4,8 +4,18 @@ use frame_benchmarking::v2::*;
 
 #[benchmarks]
 mod benchmarks {
-    use crate::{Call, Config, Pallet};
+    use crate::{
+        Call, Config, ConfigChange, ConfigUpdates, DisabledCalls, FeatureFlagSet, FrozenDomains,
+        Pallet, PendingConfirmationDepthK, ScheduledConfigChanges, TransferAllowlist,
+        TransferAllowlistSize,
+    };
+    use frame_benchmarking::v2::*;
+    use frame_support::traits::Get;
+    use frame_system::pallet_prelude::BlockNumberFor;
     use frame_system::RawOrigin;
+    use sp_domains::DomainId;
+    use sp_runtime::traits::Saturating;
+    use sp_runtime_configs::CostOfStorageParameters;
 
     #[benchmark]
     fn set_enable_domains() {
@@ -38,4 +48,241 @@ mod benchmarks {
 
         assert!(Pallet::<T>::enable_non_root_calls());
     }
+
+    #[benchmark]
+    fn set_feature_flags() {
+        let feature_flags = FeatureFlagSet {
+            enable_domains: Some(true),
+            enable_dynamic_cost_of_storage: Some(true),
+            enable_balance_transfers: Some(true),
+            enable_non_root_calls: Some(true),
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, feature_flags);
+
+        assert!(Pallet::<T>::enable_domains());
+        assert!(Pallet::<T>::enable_dynamic_cost_of_storage());
+        assert!(Pallet::<T>::enable_balance_transfers());
+        assert!(Pallet::<T>::enable_non_root_calls());
+    }
+
+    #[benchmark]
+    fn set_confirmation_depth_k() {
+        let confirmation_depth_k =
+            T::MinConfirmationDepthK::get().saturating_add(BlockNumberFor::<T>::from(1u32));
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, confirmation_depth_k);
+
+        assert_eq!(
+            PendingConfirmationDepthK::<T>::get().map(|(_, depth)| depth),
+            Some(confirmation_depth_k)
+        );
+    }
+
+    #[benchmark]
+    fn schedule_config_change() {
+        let at = frame_system::Pallet::<T>::block_number()
+            .saturating_add(BlockNumberFor::<T>::from(1u32));
+        let change = ConfigChange::FeatureFlags(all_feature_flags());
+        // Fill the block up to the limit so the last change is the most expensive one to push
+        for _ in 1..T::MaxScheduledConfigChangesPerBlock::get() {
+            ScheduledConfigChanges::<T>::try_append(at, change.clone())
+                .expect("Below the limit; qed");
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, change, at);
+
+        assert_eq!(
+            ScheduledConfigChanges::<T>::get(at).len() as u32,
+            T::MaxScheduledConfigChangesPerBlock::get()
+        );
+    }
+
+    #[benchmark]
+    fn cancel_scheduled_config_change() {
+        let at = frame_system::Pallet::<T>::block_number()
+            .saturating_add(BlockNumberFor::<T>::from(1u32));
+        let change = ConfigChange::FeatureFlags(all_feature_flags());
+        // The cancelled change is the last one, so all the others are compared first
+        for _ in 1..T::MaxScheduledConfigChangesPerBlock::get() {
+            ScheduledConfigChanges::<T>::try_append(
+                at,
+                ConfigChange::FeatureFlags(FeatureFlagSet {
+                    enable_domains: Some(true),
+                    ..Default::default()
+                }),
+            )
+            .expect("Below the limit; qed");
+        }
+        ScheduledConfigChanges::<T>::try_append(at, change.clone()).expect("Below the limit; qed");
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, change, at);
+
+        assert_eq!(
+            ScheduledConfigChanges::<T>::get(at).len() as u32,
+            T::MaxScheduledConfigChangesPerBlock::get() - 1
+        );
+    }
+
+    #[benchmark]
+    fn set_configs(n: Linear<0, 5>) {
+        // Confirmation depth k is the most expensive config to change, so it is included first
+        let confirmation_depth_k =
+            T::MinConfirmationDepthK::get().saturating_add(BlockNumberFor::<T>::from(1u32));
+        let updates = ConfigUpdates {
+            confirmation_depth_k: (n > 0).then_some(confirmation_depth_k),
+            enable_domains: (n > 1).then_some(true),
+            enable_dynamic_cost_of_storage: (n > 2).then_some(true),
+            enable_balance_transfers: (n > 3).then_some(true),
+            enable_non_root_calls: (n > 4).then_some(true),
+        };
+        assert_eq!(updates.count(), n);
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, updates);
+    }
+
+    #[benchmark]
+    fn add_to_transfer_allowlist() {
+        // Fill the allowlist up to the limit so the capacity check is done with the last free slot
+        for index in 1..T::MaxTransferAllowlistSize::get() {
+            Pallet::<T>::add_to_transfer_allowlist(
+                RawOrigin::Root.into(),
+                account("allowed", index, 0),
+            )
+            .expect("Below the limit; qed");
+        }
+        let who: T::AccountId = account("who", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, who.clone());
+
+        assert!(TransferAllowlist::<T>::contains_key(who));
+        assert_eq!(
+            TransferAllowlistSize::<T>::get(),
+            T::MaxTransferAllowlistSize::get()
+        );
+    }
+
+    #[benchmark]
+    fn remove_from_transfer_allowlist() {
+        let who: T::AccountId = account("who", 0, 0);
+        Pallet::<T>::add_to_transfer_allowlist(RawOrigin::Root.into(), who.clone())
+            .expect("Allowlist is empty; qed");
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, who.clone());
+
+        assert!(!TransferAllowlist::<T>::contains_key(who));
+    }
+
+    #[benchmark]
+    fn disable_call() {
+        let pallet_name_hash = [1; 16];
+        let call_name_hash = [2; 16];
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, pallet_name_hash, call_name_hash);
+
+        assert!(DisabledCalls::<T>::contains_key((
+            pallet_name_hash,
+            call_name_hash
+        )));
+    }
+
+    #[benchmark]
+    fn enable_call() {
+        let pallet_name_hash = [1; 16];
+        let call_name_hash = [2; 16];
+        DisabledCalls::<T>::insert((pallet_name_hash, call_name_hash), ());
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, pallet_name_hash, call_name_hash);
+
+        assert!(!DisabledCalls::<T>::contains_key((
+            pallet_name_hash,
+            call_name_hash
+        )));
+    }
+
+    #[benchmark]
+    fn set_enable_non_root_calls_at() {
+        let at = frame_system::Pallet::<T>::block_number().saturating_add(1u32.into());
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, Some(at));
+
+        assert_eq!(Pallet::<T>::enable_non_root_calls_at(), Some(at));
+    }
+
+    #[benchmark]
+    fn set_maintenance_mode() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, true);
+
+        assert!(Pallet::<T>::enable_chain_maintenance_mode());
+    }
+
+    #[benchmark]
+    fn freeze_domain() {
+        // Freeze other domains up to the limit so the capacity check is done with the last free
+        // slot
+        for index in 1..T::MaxFrozenDomains::get() {
+            Pallet::<T>::freeze_domain(RawOrigin::Root.into(), DomainId::new(index))
+                .expect("Below the limit; qed");
+        }
+        let domain_id = DomainId::new(0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, domain_id);
+
+        assert!(Pallet::<T>::is_domain_frozen(domain_id));
+        assert_eq!(
+            FrozenDomains::<T>::get().len() as u32,
+            T::MaxFrozenDomains::get()
+        );
+    }
+
+    #[benchmark]
+    fn unfreeze_domain() {
+        for index in 0..T::MaxFrozenDomains::get() {
+            Pallet::<T>::freeze_domain(RawOrigin::Root.into(), DomainId::new(index))
+                .expect("Below the limit; qed");
+        }
+        let domain_id = DomainId::new(0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, domain_id);
+
+        assert!(!Pallet::<T>::is_domain_frozen(domain_id));
+    }
+
+    #[benchmark]
+    fn set_cost_of_storage_parameters() {
+        let params = CostOfStorageParameters {
+            replication_factor: 100,
+            min_multiplier: 1,
+            max_multiplier: 1_000,
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, params);
+
+        assert_eq!(Pallet::<T>::cost_of_storage_parameters(), params);
+    }
+
+    fn all_feature_flags() -> FeatureFlagSet {
+        FeatureFlagSet {
+            enable_domains: Some(true),
+            enable_dynamic_cost_of_storage: Some(true),
+            enable_balance_transfers: Some(true),
+            enable_non_root_calls: Some(true),
+        }
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -14,23 +14,189 @@
 // limitations under the License.
 
 //! Pallet for tweaking the runtime configs for multiple network.
+//!
+//! Setters that would leave the configs unchanged succeed without writing anything or emitting
+//! an event, and their fee is refunded.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod migrations;
+#[cfg(all(feature = "std", test))]
+mod mock;
+#[cfg(all(feature = "std", test))]
+mod tests;
 pub mod weights;
 
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use frame_support::pallet_prelude::StorageVersion;
+use frame_support::traits::{CallMetadata, Contains, GetCallMetadata, PalletInfoAccess};
+use frame_support::{StorageHasher, Twox128};
 pub use pallet::*;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// The current storage version.
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+/// Set of feature flags to change at once, flags that are `None` are left untouched.
+#[derive(Default, Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct FeatureFlagSet {
+    /// New value of [`EnableDomains`].
+    pub enable_domains: Option<bool>,
+    /// New value of [`EnableDynamicCostOfStorage`].
+    pub enable_dynamic_cost_of_storage: Option<bool>,
+    /// New value of [`EnableBalanceTransfers`].
+    pub enable_balance_transfers: Option<bool>,
+    /// New value of [`EnableNonRootCalls`].
+    pub enable_non_root_calls: Option<bool>,
+}
+
+/// Set of configs to change at once with [`Call::set_configs`], configs that are `None` are left
+/// untouched.
+#[derive(Default, Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct ConfigUpdates<BlockNumber> {
+    /// New value of [`EnableDomains`].
+    pub enable_domains: Option<bool>,
+    /// New value of [`EnableDynamicCostOfStorage`].
+    pub enable_dynamic_cost_of_storage: Option<bool>,
+    /// New value of [`EnableBalanceTransfers`].
+    pub enable_balance_transfers: Option<bool>,
+    /// New value of [`EnableNonRootCalls`].
+    pub enable_non_root_calls: Option<bool>,
+    /// New value of [`ConfirmationDepthK`], it takes effect with the same delay as with
+    /// [`Call::set_confirmation_depth_k`].
+    pub confirmation_depth_k: Option<BlockNumber>,
+}
+
+impl<BlockNumber> ConfigUpdates<BlockNumber> {
+    /// Number of configs that are changed.
+    pub fn count(&self) -> u32 {
+        let Self {
+            enable_domains,
+            enable_dynamic_cost_of_storage,
+            enable_balance_transfers,
+            enable_non_root_calls,
+            confirmation_depth_k,
+        } = self;
+
+        [
+            enable_domains.is_some(),
+            enable_dynamic_cost_of_storage.is_some(),
+            enable_balance_transfers.is_some(),
+            enable_non_root_calls.is_some(),
+            confirmation_depth_k.is_some(),
+        ]
+        .into_iter()
+        .filter(|is_some| *is_some)
+        .count() as u32
+    }
+}
+
+impl<BlockNumber> From<FeatureFlagSet> for ConfigUpdates<BlockNumber> {
+    fn from(feature_flags: FeatureFlagSet) -> Self {
+        let FeatureFlagSet {
+            enable_domains,
+            enable_dynamic_cost_of_storage,
+            enable_balance_transfers,
+            enable_non_root_calls,
+        } = feature_flags;
+
+        Self {
+            enable_domains,
+            enable_dynamic_cost_of_storage,
+            enable_balance_transfers,
+            enable_non_root_calls,
+            confirmation_depth_k: None,
+        }
+    }
+}
+
+/// Call filter that rejects the calls disabled with [`Call::disable_call`], meant to be used as
+/// (part of) `frame_system::Config::BaseCallFilter`.
+pub struct EnabledCalls<T>(PhantomData<T>);
+
+impl<T, RuntimeCall> Contains<RuntimeCall> for EnabledCalls<T>
+where
+    T: Config,
+    RuntimeCall: GetCallMetadata,
+{
+    fn contains(call: &RuntimeCall) -> bool {
+        let CallMetadata {
+            function_name,
+            pallet_name,
+        } = call.get_call_metadata();
+
+        !DisabledCalls::<T>::contains_key((
+            Twox128::hash(pallet_name.as_bytes()),
+            Twox128::hash(function_name.as_bytes()),
+        ))
+    }
+}
+
+/// Call filter that, while [`EnableChainMaintenanceMode`] is `true`, rejects all the calls except
+/// the calls of this pallet and `AllowedCalls`, meant to be used as (part of)
+/// `frame_system::Config::BaseCallFilter`.
+///
+/// Calls dispatched with root origin bypass `BaseCallFilter`, `AllowedCalls` is expected to
+/// contain the inherents of the runtime and the calls used to dispatch root calls (like
+/// `pallet-sudo`).
+pub struct MaintenanceModeFilter<T, AllowedCalls>(PhantomData<(T, AllowedCalls)>);
+
+impl<T, AllowedCalls, RuntimeCall> Contains<RuntimeCall> for MaintenanceModeFilter<T, AllowedCalls>
+where
+    T: Config,
+    AllowedCalls: Contains<RuntimeCall>,
+    RuntimeCall: GetCallMetadata,
+{
+    fn contains(call: &RuntimeCall) -> bool {
+        if !EnableChainMaintenanceMode::<T>::get() {
+            return true;
+        }
+
+        call.get_call_metadata().pallet_name == <Pallet<T> as PalletInfoAccess>::name()
+            || AllowedCalls::contains(call)
+    }
+}
+
+/// Config change that can be scheduled with [`Call::schedule_config_change`].
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum ConfigChange {
+    /// Change feature flags, same as [`Call::set_feature_flags`].
+    FeatureFlags(FeatureFlagSet),
+}
+
+impl<BlockNumber> From<ConfigChange> for ConfigUpdates<BlockNumber> {
+    fn from(change: ConfigChange) -> Self {
+        match change {
+            ConfigChange::FeatureFlags(feature_flags) => feature_flags.into(),
+        }
+    }
+}
 
 #[frame_support::pallet]
 mod pallet {
     use crate::weights::WeightInfo;
+    use crate::{ConfigChange, ConfigUpdates, FeatureFlagSet, STORAGE_VERSION};
+    use frame_support::dispatch::PostDispatchInfo;
     use frame_support::pallet_prelude::*;
+    use frame_support::traits::{Contains, OriginTrait, PalletInfoAccess};
+    use frame_support::{StorageHasher, Twox128};
     use frame_system::pallet_prelude::*;
-    use sp_runtime::traits::Zero;
+    use sp_domains::DomainId;
+    use sp_runtime::traits::{Saturating, Zero};
+    #[cfg(any(feature = "try-runtime", test))]
+    use sp_runtime::TryRuntimeError;
+    use sp_runtime_configs::{
+        ConfigKey, CostOfStorageParameters, RuntimeConfigs, RuntimeConfigsProvider,
+    };
+    use sp_std::vec::Vec;
+    use subspace_runtime_primitives::MIN_REPLICATION_FACTOR;
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Whether to enable calls in pallet-domains.
@@ -53,13 +219,277 @@ mod pallet {
     #[pallet::getter(fn enable_non_root_calls)]
     pub type EnableNonRootCalls<T> = StorageValue<_, bool, ValueQuery>;
 
+    /// Whether the chain is in maintenance mode, see [`MaintenanceModeFilter`].
+    #[pallet::storage]
+    #[pallet::getter(fn enable_chain_maintenance_mode)]
+    pub type EnableChainMaintenanceMode<T> = StorageValue<_, bool, ValueQuery>;
+
+    /// Block number at which [`EnableNonRootCalls`] is automatically set to `true`.
+    #[pallet::storage]
+    #[pallet::getter(fn enable_non_root_calls_at)]
+    pub type EnableNonRootCallsAt<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+    #[pallet::type_value]
+    pub fn DefaultCostOfStorageParameters() -> CostOfStorageParameters {
+        CostOfStorageParameters {
+            replication_factor: MIN_REPLICATION_FACTOR,
+            min_multiplier: 0,
+            max_multiplier: u128::MAX,
+        }
+    }
+
+    /// Parameters of the dynamic cost of storage, only used when [`EnableDynamicCostOfStorage`]
+    /// is `true`.
+    #[pallet::storage]
+    #[pallet::getter(fn cost_of_storage_parameters)]
+    pub type CostOfStorageParams<T> =
+        StorageValue<_, CostOfStorageParameters, ValueQuery, DefaultCostOfStorageParameters>;
+
+    /// Accounts that can transfer balances even when [`EnableBalanceTransfers`] is `false`.
+    #[pallet::storage]
+    pub type TransferAllowlist<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Calls disabled with [`Call::disable_call`], keyed by the `twox_128` hashes of the pallet
+    /// name and the call name.
+    #[pallet::storage]
+    pub type DisabledCalls<T> =
+        StorageMap<_, Blake2_128Concat, ([u8; 16], [u8; 16]), (), OptionQuery>;
+
+    /// Domains frozen with [`Call::freeze_domain`], see [`Pallet::is_domain_frozen`].
     #[pallet::storage]
+    pub type FrozenDomains<T: Config> =
+        StorageValue<_, BoundedBTreeSet<DomainId, T::MaxFrozenDomains>, ValueQuery>;
+
+    /// Block number and account, `None` unless the origin was signed, of the last change to each
+    /// config.
+    ///
+    /// Changes applied by the pallet itself, like scheduled config changes, have no account.
+    #[pallet::storage]
+    #[pallet::getter(fn last_config_change)]
+    pub type ConfigChangeLog<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        ConfigKey,
+        (BlockNumberFor<T>, Option<T::AccountId>),
+        OptionQuery,
+    >;
+
+    /// Number of accounts in [`TransferAllowlist`].
+    #[pallet::storage]
+    pub type TransferAllowlistSize<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// Depth `K` after which a block enters the recorded history.
+    #[pallet::storage]
+    #[pallet::getter(fn confirmation_depth_k)]
     pub type ConfirmationDepthK<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
 
+    /// New value of [`ConfirmationDepthK`] set with [`Call::set_confirmation_depth_k`] and the
+    /// block number it takes effect at.
+    #[pallet::storage]
+    pub type PendingConfirmationDepthK<T: Config> =
+        StorageValue<_, (BlockNumberFor<T>, BlockNumberFor<T>), OptionQuery>;
+
+    /// Config changes scheduled with [`Call::schedule_config_change`], keyed by the block number
+    /// they are applied at.
+    #[pallet::storage]
+    pub type ScheduledConfigChanges<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<ConfigChange, T::MaxScheduledConfigChangesPerBlock>,
+        ValueQuery,
+    >;
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
+
+        /// The minimum confirmation depth k that can be set with
+        /// [`Call::set_confirmation_depth_k`].
+        #[pallet::constant]
+        type MinConfirmationDepthK: Get<BlockNumberFor<Self>>;
+
+        /// The maximum confirmation depth k that can be set in the genesis config or with
+        /// [`Call::set_confirmation_depth_k`].
+        #[pallet::constant]
+        type MaxConfirmationDepthK: Get<BlockNumberFor<Self>>;
+
+        /// The maximum number of config changes that can be scheduled at the same block.
+        #[pallet::constant]
+        type MaxScheduledConfigChangesPerBlock: Get<u32>;
+
+        /// The maximum number of accounts in [`TransferAllowlist`].
+        #[pallet::constant]
+        type MaxTransferAllowlistSize: Get<u32>;
+
+        /// The maximum number of domains in [`FrozenDomains`].
+        #[pallet::constant]
+        type MaxFrozenDomains: Get<u32>;
+
+        /// Origin allowed to change [`EnableDomains`].
+        type EnableDomainsOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to change [`EnableDynamicCostOfStorage`] and [`CostOfStorageParams`].
+        type EnableDynamicCostOfStorageOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to change [`EnableBalanceTransfers`] and [`TransferAllowlist`].
+        type EnableBalanceTransfersOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to change [`EnableNonRootCalls`] and [`EnableNonRootCallsAt`].
+        type EnableNonRootCallsOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to change [`ConfirmationDepthK`].
+        type ConfirmationDepthKOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to change [`DisabledCalls`].
+        type DisabledCallsOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// `(pallet name, call name)` pairs of the calls that can't be disabled with
+        /// [`Call::disable_call`].
+        ///
+        /// Expected to contain the inherents of the runtime, without which blocks can't be
+        /// produced, and the calls used to dispatch root calls (like `pallet-sudo`), without which
+        /// the disabled calls can't be enabled again.
+        type NonDisableableCalls: Get<&'static [(&'static str, &'static str)]>;
+
+        /// Origin allowed to change [`EnableChainMaintenanceMode`].
+        type MaintenanceModeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to change [`FrozenDomains`].
+        type FrozenDomainsOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Confirmation depth k can not be zero.
+        ZeroConfirmationDepthK,
+        /// Confirmation depth k is smaller than [`Config::MinConfirmationDepthK`].
+        ConfirmationDepthKTooLow,
+        /// Confirmation depth k is larger than [`Config::MaxConfirmationDepthK`].
+        ConfirmationDepthKTooHigh,
+        /// Config changes can only be scheduled at a future block.
+        ScheduledInThePast,
+        /// There are already [`Config::MaxScheduledConfigChangesPerBlock`] config changes
+        /// scheduled at the block.
+        TooManyScheduledConfigChanges,
+        /// There is no such config change scheduled at the block.
+        ScheduledConfigChangeNotFound,
+        /// There are already [`Config::MaxTransferAllowlistSize`] accounts in the transfer
+        /// allowlist.
+        TransferAllowlistFull,
+        /// The account is already in the transfer allowlist.
+        AlreadyInTransferAllowlist,
+        /// The account is not in the transfer allowlist.
+        NotInTransferAllowlist,
+        /// Calls of this pallet can not be disabled.
+        CannotDisableOwnCalls,
+        /// The call is in [`Config::NonDisableableCalls`] and can not be disabled.
+        CannotDisableCall,
+        /// The call is already disabled.
+        CallAlreadyDisabled,
+        /// The call is not disabled.
+        CallNotDisabled,
+        /// Replication factor of the cost of storage parameters can not be zero.
+        ZeroReplicationFactor,
+        /// Minimum multiplier of the cost of storage parameters is larger than the maximum one.
+        InvalidMultiplierRange,
+        /// There are already [`Config::MaxFrozenDomains`] frozen domains.
+        TooManyFrozenDomains,
+        /// The domain is already frozen.
+        DomainAlreadyFrozen,
+        /// The domain is not frozen.
+        DomainNotFrozen,
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Feature flags were changed, only the flags that are `Some` were changed.
+        FeatureFlagsUpdated { feature_flags: FeatureFlagSet },
+        /// A new confirmation depth k was set and takes effect at block `effective_at`.
+        ConfirmationDepthKScheduled {
+            confirmation_depth_k: BlockNumberFor<T>,
+            effective_at: BlockNumberFor<T>,
+        },
+        /// The pending confirmation depth k took effect.
+        ConfirmationDepthKUpdated {
+            confirmation_depth_k: BlockNumberFor<T>,
+        },
+        /// A config change was scheduled at block `at`.
+        ConfigChangeScheduled {
+            change: ConfigChange,
+            at: BlockNumberFor<T>,
+        },
+        /// A config change scheduled at block `at` was cancelled.
+        ScheduledConfigChangeCancelled {
+            change: ConfigChange,
+            at: BlockNumberFor<T>,
+        },
+        /// An account was added to the transfer allowlist.
+        AddedToTransferAllowlist { who: T::AccountId },
+        /// An account was removed from the transfer allowlist.
+        RemovedFromTransferAllowlist { who: T::AccountId },
+        /// A call was disabled.
+        CallDisabled {
+            pallet_name_hash: [u8; 16],
+            call_name_hash: [u8; 16],
+        },
+        /// A previously disabled call was enabled again.
+        CallEnabled {
+            pallet_name_hash: [u8; 16],
+            call_name_hash: [u8; 16],
+        },
+        /// Block number at which calls from non-root accounts are automatically enabled was
+        /// changed, `None` means the activation was cancelled.
+        EnableNonRootCallsAtUpdated { at: Option<BlockNumberFor<T>> },
+        /// Parameters of the dynamic cost of storage were changed.
+        CostOfStorageParametersUpdated {
+            old: CostOfStorageParameters,
+            new: CostOfStorageParameters,
+        },
+        /// The chain entered maintenance mode, only root calls, inherents and calls of this
+        /// pallet are allowed.
+        MaintenanceModeEntered,
+        /// The chain exited maintenance mode.
+        MaintenanceModeExited,
+        /// A domain was frozen.
+        DomainFrozen { domain_id: DomainId },
+        /// A frozen domain was unfrozen.
+        DomainUnfrozen { domain_id: DomainId },
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+            Self::apply_pending_confirmation_depth_k(block_number)
+                .saturating_add(Self::apply_scheduled_config_changes(block_number))
+                .saturating_add(Self::apply_enable_non_root_calls_at(block_number))
+        }
+
+        fn integrity_test() {
+            assert!(
+                !T::MinConfirmationDepthK::get().is_zero(),
+                "`MinConfirmationDepthK` must not be zero"
+            );
+            assert!(
+                T::MinConfirmationDepthK::get() <= T::MaxConfirmationDepthK::get(),
+                "`MinConfirmationDepthK` must not be larger than `MaxConfirmationDepthK`"
+            );
+            assert!(
+                T::MaxScheduledConfigChangesPerBlock::get() > 0,
+                "`MaxScheduledConfigChangesPerBlock` must not be zero"
+            );
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(block_number: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+            Self::do_try_state(block_number)
+        }
     }
 
     #[pallet::genesis_config]
@@ -72,8 +502,12 @@ mod pallet {
         pub enable_balance_transfers: bool,
         /// Whether to enable calls from non-root account
         pub enable_non_root_calls: bool,
+        /// Block number at which calls from non-root account are enabled automatically
+        pub enable_non_root_calls_at: Option<BlockNumberFor<T>>,
         /// Confirmation depth k to use in the archiving process
         pub confirmation_depth_k: BlockNumberFor<T>,
+        /// Parameters of the dynamic cost of storage
+        pub cost_of_storage_parameters: CostOfStorageParameters,
     }
 
     impl<T: Config> Default for GenesisConfig<T> {
@@ -84,7 +518,9 @@ mod pallet {
                 enable_dynamic_cost_of_storage: false,
                 enable_balance_transfers: false,
                 enable_non_root_calls: false,
+                enable_non_root_calls_at: None,
                 confirmation_depth_k: BlockNumberFor::<T>::from(100u32),
+                cost_of_storage_parameters: DefaultCostOfStorageParameters::get(),
             }
         }
     }
@@ -97,19 +533,38 @@ mod pallet {
                 enable_dynamic_cost_of_storage,
                 enable_balance_transfers,
                 enable_non_root_calls,
+                enable_non_root_calls_at,
                 confirmation_depth_k,
+                cost_of_storage_parameters,
             } = self;
 
             assert!(
                 !confirmation_depth_k.is_zero(),
                 "ConfirmationDepthK can not be zero"
             );
+            assert!(
+                *confirmation_depth_k <= T::MaxConfirmationDepthK::get(),
+                "ConfirmationDepthK can not be larger than MaxConfirmationDepthK"
+            );
+            assert!(
+                cost_of_storage_parameters.replication_factor != 0,
+                "Replication factor of CostOfStorageParameters can not be zero"
+            );
+            assert!(
+                cost_of_storage_parameters.min_multiplier
+                    <= cost_of_storage_parameters.max_multiplier,
+                "Min multiplier of CostOfStorageParameters can not be larger than max multiplier"
+            );
 
             <EnableDomains<T>>::put(enable_domains);
             <EnableDynamicCostOfStorage<T>>::put(enable_dynamic_cost_of_storage);
             <EnableBalanceTransfers<T>>::put(enable_balance_transfers);
             <EnableNonRootCalls<T>>::put(enable_non_root_calls);
+            if let Some(enable_non_root_calls_at) = enable_non_root_calls_at {
+                <EnableNonRootCallsAt<T>>::put(enable_non_root_calls_at);
+            }
             <ConfirmationDepthK<T>>::put(confirmation_depth_k);
+            <CostOfStorageParams<T>>::put(cost_of_storage_parameters);
         }
     }
 
@@ -118,26 +573,48 @@ mod pallet {
         /// Change enable domains state.
         #[pallet::call_index(0)]
         #[pallet::weight(<T as Config>::WeightInfo::set_enable_domains())]
-        pub fn set_enable_domains(origin: OriginFor<T>, enable_domains: bool) -> DispatchResult {
-            ensure_root(origin)?;
+        pub fn set_enable_domains(
+            origin: OriginFor<T>,
+            enable_domains: bool,
+        ) -> DispatchResultWithPostInfo {
+            let changed_by = origin.clone().into_signer();
+            T::EnableDomainsOrigin::ensure_origin(origin)?;
 
-            EnableDomains::<T>::put(enable_domains);
+            let changed = Self::apply_config_change(
+                ConfigChange::FeatureFlags(FeatureFlagSet {
+                    enable_domains: Some(enable_domains),
+                    ..Default::default()
+                }),
+                changed_by,
+            );
 
-            Ok(())
+            Ok(Self::post_dispatch_info(changed))
         }
 
         /// Enable or disable dynamic cost of storage.
+        ///
+        /// NOTE: this call used to write [`EnableBalanceTransfers`] instead of
+        /// [`EnableDynamicCostOfStorage`]. The wrongly flipped flag can't be told apart from an
+        /// intended change on-chain, so chains that dispatched it before the fix have to set both
+        /// flags to their intended values again, e.g. with a single [`Call::set_feature_flags`].
         #[pallet::call_index(1)]
         #[pallet::weight(<T as Config>::WeightInfo::set_enable_dynamic_cost_of_storage())]
         pub fn set_enable_dynamic_cost_of_storage(
             origin: OriginFor<T>,
             enable_dynamic_cost_of_storage: bool,
-        ) -> DispatchResult {
-            ensure_root(origin)?;
+        ) -> DispatchResultWithPostInfo {
+            let changed_by = origin.clone().into_signer();
+            T::EnableDynamicCostOfStorageOrigin::ensure_origin(origin)?;
 
-            EnableBalanceTransfers::<T>::put(enable_dynamic_cost_of_storage);
+            let changed = Self::apply_config_change(
+                ConfigChange::FeatureFlags(FeatureFlagSet {
+                    enable_dynamic_cost_of_storage: Some(enable_dynamic_cost_of_storage),
+                    ..Default::default()
+                }),
+                changed_by,
+            );
 
-            Ok(())
+            Ok(Self::post_dispatch_info(changed))
         }
 
         /// Enable or disable balance transfers for all users.
@@ -146,12 +623,19 @@ mod pallet {
         pub fn set_enable_balance_transfers(
             origin: OriginFor<T>,
             enable_balance_transfers: bool,
-        ) -> DispatchResult {
-            ensure_root(origin)?;
+        ) -> DispatchResultWithPostInfo {
+            let changed_by = origin.clone().into_signer();
+            T::EnableBalanceTransfersOrigin::ensure_origin(origin)?;
 
-            EnableBalanceTransfers::<T>::put(enable_balance_transfers);
+            let changed = Self::apply_config_change(
+                ConfigChange::FeatureFlags(FeatureFlagSet {
+                    enable_balance_transfers: Some(enable_balance_transfers),
+                    ..Default::default()
+                }),
+                changed_by,
+            );
 
-            Ok(())
+            Ok(Self::post_dispatch_info(changed))
         }
 
         /// Enable or disable calls from non-root users.
@@ -160,12 +644,801 @@ mod pallet {
         pub fn set_enable_non_root_calls(
             origin: OriginFor<T>,
             enable_non_root_calls: bool,
+        ) -> DispatchResultWithPostInfo {
+            let changed_by = origin.clone().into_signer();
+            T::EnableNonRootCallsOrigin::ensure_origin(origin)?;
+
+            let changed = Self::apply_config_change(
+                ConfigChange::FeatureFlags(FeatureFlagSet {
+                    enable_non_root_calls: Some(enable_non_root_calls),
+                    ..Default::default()
+                }),
+                changed_by,
+            );
+
+            Ok(Self::post_dispatch_info(changed))
+        }
+
+        /// Change multiple feature flags at once, flags that are `None` are left untouched.
+        ///
+        /// The origin must be allowed to change every flag that is `Some`.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_feature_flags())]
+        pub fn set_feature_flags(
+            origin: OriginFor<T>,
+            feature_flags: FeatureFlagSet,
+        ) -> DispatchResultWithPostInfo {
+            let changed_by = origin.clone().into_signer();
+            Self::ensure_config_updates_origin(origin, &feature_flags.clone().into())?;
+
+            let changed =
+                Self::apply_config_change(ConfigChange::FeatureFlags(feature_flags), changed_by);
+
+            Ok(Self::post_dispatch_info(changed))
+        }
+
+        /// Change the confirmation depth k used by the archiving process.
+        ///
+        /// The new value takes effect after as many blocks as the current confirmation depth k,
+        /// so that the blocks that are already waiting to be archived keep the depth they were
+        /// produced with. A later call replaces the pending value and restarts the delay.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_confirmation_depth_k())]
+        pub fn set_confirmation_depth_k(
+            origin: OriginFor<T>,
+            confirmation_depth_k: BlockNumberFor<T>,
+        ) -> DispatchResultWithPostInfo {
+            let changed_by = origin.clone().into_signer();
+            T::ConfirmationDepthKOrigin::ensure_origin(origin)?;
+
+            Self::ensure_valid_confirmation_depth_k(confirmation_depth_k)?;
+            if confirmation_depth_k == Self::target_confirmation_depth_k() {
+                return Ok(Self::post_dispatch_info(false));
+            }
+            Self::schedule_confirmation_depth_k(confirmation_depth_k);
+            Self::note_config_change(ConfigKey::ConfirmationDepthK, changed_by);
+
+            Ok(Self::post_dispatch_info(true))
+        }
+
+        /// Schedule a config change to be applied at the beginning of block `at`.
+        ///
+        /// The change is applied as if the corresponding setter was dispatched at that block,
+        /// emitting the same event. Only the origins allowed to change the configs can schedule
+        /// or cancel the change.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::schedule_config_change())]
+        pub fn schedule_config_change(
+            origin: OriginFor<T>,
+            change: ConfigChange,
+            at: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            Self::ensure_config_updates_origin(origin, &change.clone().into())?;
+
+            ensure!(
+                at > frame_system::Pallet::<T>::block_number(),
+                Error::<T>::ScheduledInThePast
+            );
+
+            ScheduledConfigChanges::<T>::try_mutate(at, |changes| {
+                changes
+                    .try_push(change.clone())
+                    .map_err(|_| Error::<T>::TooManyScheduledConfigChanges)
+            })?;
+
+            Self::deposit_event(Event::ConfigChangeScheduled { change, at });
+
+            Ok(())
+        }
+
+        /// Cancel a config change previously scheduled at block `at`.
+        ///
+        /// If the same change was scheduled multiple times at that block, only one of them is
+        /// cancelled.
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_scheduled_config_change())]
+        pub fn cancel_scheduled_config_change(
+            origin: OriginFor<T>,
+            change: ConfigChange,
+            at: BlockNumberFor<T>,
         ) -> DispatchResult {
-            ensure_root(origin)?;
+            Self::ensure_config_updates_origin(origin, &change.clone().into())?;
+
+            ScheduledConfigChanges::<T>::try_mutate_exists(at, |maybe_changes| {
+                let changes = maybe_changes
+                    .as_mut()
+                    .ok_or(Error::<T>::ScheduledConfigChangeNotFound)?;
+                let index = changes
+                    .iter()
+                    .position(|scheduled_change| scheduled_change == &change)
+                    .ok_or(Error::<T>::ScheduledConfigChangeNotFound)?;
+                changes.remove(index);
+                if changes.is_empty() {
+                    *maybe_changes = None;
+                }
+
+                Ok::<_, Error<T>>(())
+            })?;
 
-            EnableNonRootCalls::<T>::put(enable_non_root_calls);
+            Self::deposit_event(Event::ScheduledConfigChangeCancelled { change, at });
 
             Ok(())
         }
+
+        /// Change multiple configs at once, configs that are `None` are left untouched.
+        ///
+        /// The origin must be allowed to change every config that is `Some`.
+        /// Either all the configs are changed or none of them is, an event is emitted for every
+        /// changed config as if its own setter was dispatched.
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_configs(updates.count()))]
+        pub fn set_configs(
+            origin: OriginFor<T>,
+            updates: ConfigUpdates<BlockNumberFor<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let changed_by = origin.clone().into_signer();
+            Self::ensure_config_updates_origin(origin, &updates)?;
+
+            let ConfigUpdates {
+                enable_domains,
+                enable_dynamic_cost_of_storage,
+                enable_balance_transfers,
+                enable_non_root_calls,
+                confirmation_depth_k,
+            } = updates;
+
+            if let Some(confirmation_depth_k) = confirmation_depth_k {
+                Self::ensure_valid_confirmation_depth_k(confirmation_depth_k)?;
+            }
+
+            let mut changed_count = 0;
+
+            let feature_flags = [
+                enable_domains.map(|enable_domains| FeatureFlagSet {
+                    enable_domains: Some(enable_domains),
+                    ..Default::default()
+                }),
+                enable_dynamic_cost_of_storage.map(|enable_dynamic_cost_of_storage| {
+                    FeatureFlagSet {
+                        enable_dynamic_cost_of_storage: Some(enable_dynamic_cost_of_storage),
+                        ..Default::default()
+                    }
+                }),
+                enable_balance_transfers.map(|enable_balance_transfers| FeatureFlagSet {
+                    enable_balance_transfers: Some(enable_balance_transfers),
+                    ..Default::default()
+                }),
+                enable_non_root_calls.map(|enable_non_root_calls| FeatureFlagSet {
+                    enable_non_root_calls: Some(enable_non_root_calls),
+                    ..Default::default()
+                }),
+            ];
+            for feature_flags in feature_flags.into_iter().flatten() {
+                if Self::apply_config_change(
+                    ConfigChange::FeatureFlags(feature_flags),
+                    changed_by.clone(),
+                ) {
+                    changed_count += 1;
+                }
+            }
+            if let Some(confirmation_depth_k) = confirmation_depth_k {
+                if confirmation_depth_k != Self::target_confirmation_depth_k() {
+                    Self::schedule_confirmation_depth_k(confirmation_depth_k);
+                    Self::note_config_change(ConfigKey::ConfirmationDepthK, changed_by);
+                    changed_count += 1;
+                }
+            }
+
+            if changed_count == 0 {
+                return Ok(Self::post_dispatch_info(false));
+            }
+
+            Ok(Some(<T as Config>::WeightInfo::set_configs(changed_count)).into())
+        }
+
+        /// Allow `who` to transfer balances even when balance transfers are disabled.
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_to_transfer_allowlist())]
+        pub fn add_to_transfer_allowlist(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            let changed_by = origin.clone().into_signer();
+            T::EnableBalanceTransfersOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                !TransferAllowlist::<T>::contains_key(&who),
+                Error::<T>::AlreadyInTransferAllowlist
+            );
+            let allowlist_size = TransferAllowlistSize::<T>::get();
+            ensure!(
+                allowlist_size < T::MaxTransferAllowlistSize::get(),
+                Error::<T>::TransferAllowlistFull
+            );
+
+            TransferAllowlist::<T>::insert(&who, ());
+            TransferAllowlistSize::<T>::put(allowlist_size + 1);
+
+            Self::note_config_change(ConfigKey::TransferAllowlist, changed_by);
+
+            Self::deposit_event(Event::AddedToTransferAllowlist { who });
+
+            Ok(())
+        }
+
+        /// Remove `who` from the accounts allowed to transfer balances when balance transfers are
+        /// disabled.
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_from_transfer_allowlist())]
+        pub fn remove_from_transfer_allowlist(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            let changed_by = origin.clone().into_signer();
+            T::EnableBalanceTransfersOrigin::ensure_origin(origin)?;
+
+            TransferAllowlist::<T>::take(&who).ok_or(Error::<T>::NotInTransferAllowlist)?;
+            TransferAllowlistSize::<T>::mutate(|allowlist_size| {
+                *allowlist_size = allowlist_size.saturating_sub(1)
+            });
+
+            Self::note_config_change(ConfigKey::TransferAllowlist, changed_by);
+
+            Self::deposit_event(Event::RemovedFromTransferAllowlist { who });
+
+            Ok(())
+        }
+
+        /// Disable the call whose name hashes to `call_name_hash` of the pallet whose name hashes
+        /// to `pallet_name_hash` (both with `twox_128`), it is then rejected by [`EnabledCalls`].
+        ///
+        /// Calls of this pallet and [`Config::NonDisableableCalls`] can't be disabled, so the
+        /// configs can always be changed back. Calls dispatched by sudo bypass the filter.
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as Config>::WeightInfo::disable_call())]
+        pub fn disable_call(
+            origin: OriginFor<T>,
+            pallet_name_hash: [u8; 16],
+            call_name_hash: [u8; 16],
+        ) -> DispatchResult {
+            let changed_by = origin.clone().into_signer();
+            T::DisabledCallsOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                pallet_name_hash != Twox128::hash(<Self as PalletInfoAccess>::name().as_bytes()),
+                Error::<T>::CannotDisableOwnCalls
+            );
+            ensure!(
+                !Self::is_non_disableable_call(pallet_name_hash, call_name_hash),
+                Error::<T>::CannotDisableCall
+            );
+            ensure!(
+                !DisabledCalls::<T>::contains_key((pallet_name_hash, call_name_hash)),
+                Error::<T>::CallAlreadyDisabled
+            );
+
+            DisabledCalls::<T>::insert((pallet_name_hash, call_name_hash), ());
+
+            Self::note_config_change(ConfigKey::DisabledCalls, changed_by);
+
+            Self::deposit_event(Event::CallDisabled {
+                pallet_name_hash,
+                call_name_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Enable a call previously disabled with [`Call::disable_call`].
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config>::WeightInfo::enable_call())]
+        pub fn enable_call(
+            origin: OriginFor<T>,
+            pallet_name_hash: [u8; 16],
+            call_name_hash: [u8; 16],
+        ) -> DispatchResult {
+            let changed_by = origin.clone().into_signer();
+            T::DisabledCallsOrigin::ensure_origin(origin)?;
+
+            DisabledCalls::<T>::take((pallet_name_hash, call_name_hash))
+                .ok_or(Error::<T>::CallNotDisabled)?;
+
+            Self::note_config_change(ConfigKey::DisabledCalls, changed_by);
+
+            Self::deposit_event(Event::CallEnabled {
+                pallet_name_hash,
+                call_name_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Change the parameters of the dynamic cost of storage.
+        ///
+        /// The replication factor must be non-zero and the minimum multiplier must not be larger
+        /// than the maximum one.
+        #[pallet::call_index(14)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_cost_of_storage_parameters())]
+        pub fn set_cost_of_storage_parameters(
+            origin: OriginFor<T>,
+            params: CostOfStorageParameters,
+        ) -> DispatchResultWithPostInfo {
+            let changed_by = origin.clone().into_signer();
+            T::EnableDynamicCostOfStorageOrigin::ensure_origin(origin)?;
+
+            Self::ensure_valid_cost_of_storage_parameters(&params)?;
+
+            let old = CostOfStorageParams::<T>::get();
+            if old == params {
+                return Ok(Self::post_dispatch_info(false));
+            }
+            CostOfStorageParams::<T>::put(params);
+
+            Self::note_config_change(ConfigKey::CostOfStorageParameters, changed_by);
+
+            Self::deposit_event(Event::CostOfStorageParametersUpdated { old, new: params });
+
+            Ok(Self::post_dispatch_info(true))
+        }
+
+        /// Set the block number at which calls from non-root users are enabled automatically,
+        /// `None` cancels the pending activation.
+        ///
+        /// The activation is independent of [`Call::set_enable_non_root_calls`], which can still
+        /// be used to change the flag before the block number is reached.
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_enable_non_root_calls_at())]
+        pub fn set_enable_non_root_calls_at(
+            origin: OriginFor<T>,
+            at: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let changed_by = origin.clone().into_signer();
+            T::EnableNonRootCallsOrigin::ensure_origin(origin)?;
+
+            match at {
+                Some(at) => {
+                    ensure!(
+                        at > frame_system::Pallet::<T>::block_number(),
+                        Error::<T>::ScheduledInThePast
+                    );
+                    EnableNonRootCallsAt::<T>::put(at);
+                }
+                None => {
+                    EnableNonRootCallsAt::<T>::kill();
+                }
+            }
+
+            Self::note_config_change(ConfigKey::EnableNonRootCallsAt, changed_by);
+
+            Self::deposit_event(Event::EnableNonRootCallsAtUpdated { at });
+
+            Ok(())
+        }
+
+        /// Enter or exit maintenance mode, see [`MaintenanceModeFilter`].
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_maintenance_mode())]
+        pub fn set_maintenance_mode(
+            origin: OriginFor<T>,
+            enabled: bool,
+        ) -> DispatchResultWithPostInfo {
+            let changed_by = origin.clone().into_signer();
+            T::MaintenanceModeOrigin::ensure_origin(origin)?;
+
+            if EnableChainMaintenanceMode::<T>::get() == enabled {
+                return Ok(Self::post_dispatch_info(false));
+            }
+
+            EnableChainMaintenanceMode::<T>::put(enabled);
+
+            Self::note_config_change(ConfigKey::ChainMaintenanceMode, changed_by);
+
+            Self::deposit_event(if enabled {
+                Event::MaintenanceModeEntered
+            } else {
+                Event::MaintenanceModeExited
+            });
+
+            Ok(Self::post_dispatch_info(true))
+        }
+
+        /// Freeze the domain `domain_id`, its bundles are rejected by `pallet-domains` until the
+        /// domain is unfrozen.
+        ///
+        /// The domain doesn't need to exist yet, the freeze takes effect once it is instantiated.
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as Config>::WeightInfo::freeze_domain())]
+        pub fn freeze_domain(origin: OriginFor<T>, domain_id: DomainId) -> DispatchResult {
+            let changed_by = origin.clone().into_signer();
+            T::FrozenDomainsOrigin::ensure_origin(origin)?;
+
+            FrozenDomains::<T>::try_mutate(|frozen_domains| {
+                let inserted = frozen_domains
+                    .try_insert(domain_id)
+                    .map_err(|_| Error::<T>::TooManyFrozenDomains)?;
+                ensure!(inserted, Error::<T>::DomainAlreadyFrozen);
+
+                Ok::<_, Error<T>>(())
+            })?;
+
+            Self::note_config_change(ConfigKey::FrozenDomains, changed_by);
+
+            Self::deposit_event(Event::DomainFrozen { domain_id });
+
+            Ok(())
+        }
+
+        /// Unfreeze the domain `domain_id` frozen with [`Call::freeze_domain`].
+        #[pallet::call_index(18)]
+        #[pallet::weight(<T as Config>::WeightInfo::unfreeze_domain())]
+        pub fn unfreeze_domain(origin: OriginFor<T>, domain_id: DomainId) -> DispatchResult {
+            let changed_by = origin.clone().into_signer();
+            T::FrozenDomainsOrigin::ensure_origin(origin)?;
+
+            FrozenDomains::<T>::try_mutate(|frozen_domains| {
+                ensure!(
+                    frozen_domains.remove(&domain_id),
+                    Error::<T>::DomainNotFrozen
+                );
+
+                Ok::<_, Error<T>>(())
+            })?;
+
+            Self::note_config_change(ConfigKey::FrozenDomains, changed_by);
+
+            Self::deposit_event(Event::DomainUnfrozen { domain_id });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> RuntimeConfigsProvider<BlockNumberFor<T>> for Pallet<T> {
+        fn dynamic_cost_of_storage() -> bool {
+            EnableDynamicCostOfStorage::<T>::get()
+        }
+
+        fn transfers_enabled() -> bool {
+            EnableBalanceTransfers::<T>::get()
+        }
+
+        fn confirmation_depth_k() -> BlockNumberFor<T> {
+            ConfirmationDepthK::<T>::get()
+        }
+    }
+
+    impl<T: Config> Contains<DomainId> for Pallet<T> {
+        fn contains(domain_id: &DomainId) -> bool {
+            Self::is_domain_frozen(*domain_id)
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Whether the domain `domain_id` is frozen with [`Call::freeze_domain`].
+        pub fn is_domain_frozen(domain_id: DomainId) -> bool {
+            FrozenDomains::<T>::get().contains(&domain_id)
+        }
+
+        /// Whether `who` can transfer balances, either because balance transfers are enabled or
+        /// because it is in [`TransferAllowlist`].
+        pub fn can_transfer(who: &T::AccountId) -> bool {
+            EnableBalanceTransfers::<T>::get() || TransferAllowlist::<T>::contains_key(who)
+        }
+
+        /// Returns the last change of every config changed at least once, see [`ConfigChangeLog`].
+        pub fn config_change_log() -> Vec<(ConfigKey, BlockNumberFor<T>, Option<T::AccountId>)> {
+            ConfigChangeLog::<T>::iter()
+                .map(|(key, (block_number, changed_by))| (key, block_number, changed_by))
+                .collect()
+        }
+
+        /// Returns the current values of all the runtime configs.
+        pub fn runtime_configs() -> RuntimeConfigs<BlockNumberFor<T>> {
+            RuntimeConfigs {
+                enable_domains: EnableDomains::<T>::get(),
+                enable_dynamic_cost_of_storage: EnableDynamicCostOfStorage::<T>::get(),
+                enable_balance_transfers: EnableBalanceTransfers::<T>::get(),
+                enable_non_root_calls: EnableNonRootCalls::<T>::get(),
+                confirmation_depth_k: ConfirmationDepthK::<T>::get(),
+                cost_of_storage_parameters: CostOfStorageParams::<T>::get(),
+            }
+        }
+
+        /// Ensures `origin` is allowed to change all the configs that are `Some` in `updates`,
+        /// empty updates require root.
+        fn ensure_config_updates_origin(
+            origin: OriginFor<T>,
+            updates: &ConfigUpdates<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let ConfigUpdates {
+                enable_domains,
+                enable_dynamic_cost_of_storage,
+                enable_balance_transfers,
+                enable_non_root_calls,
+                confirmation_depth_k,
+            } = updates;
+
+            if updates.count() == 0 {
+                ensure_root(origin)?;
+                return Ok(());
+            }
+
+            if enable_domains.is_some() {
+                T::EnableDomainsOrigin::ensure_origin(origin.clone())?;
+            }
+            if enable_dynamic_cost_of_storage.is_some() {
+                T::EnableDynamicCostOfStorageOrigin::ensure_origin(origin.clone())?;
+            }
+            if enable_balance_transfers.is_some() {
+                T::EnableBalanceTransfersOrigin::ensure_origin(origin.clone())?;
+            }
+            if enable_non_root_calls.is_some() {
+                T::EnableNonRootCallsOrigin::ensure_origin(origin.clone())?;
+            }
+            if confirmation_depth_k.is_some() {
+                T::ConfirmationDepthKOrigin::ensure_origin(origin)?;
+            }
+
+            Ok(())
+        }
+
+        fn ensure_valid_confirmation_depth_k(
+            confirmation_depth_k: BlockNumberFor<T>,
+        ) -> Result<(), Error<T>> {
+            ensure!(
+                !confirmation_depth_k.is_zero(),
+                Error::<T>::ZeroConfirmationDepthK
+            );
+            ensure!(
+                confirmation_depth_k >= T::MinConfirmationDepthK::get(),
+                Error::<T>::ConfirmationDepthKTooLow
+            );
+            ensure!(
+                confirmation_depth_k <= T::MaxConfirmationDepthK::get(),
+                Error::<T>::ConfirmationDepthKTooHigh
+            );
+
+            Ok(())
+        }
+
+        /// Checks the invariants of the pallet storage at the end of block `block_number`.
+        #[cfg(any(feature = "try-runtime", test))]
+        pub(crate) fn do_try_state(block_number: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+            ensure!(
+                !ConfirmationDepthK::<T>::get().is_zero(),
+                "ConfirmationDepthK must not be zero"
+            );
+
+            if let Some((effective_at, confirmation_depth_k)) =
+                PendingConfirmationDepthK::<T>::get()
+            {
+                ensure!(
+                    effective_at > block_number,
+                    "PendingConfirmationDepthK must take effect at a future block"
+                );
+                ensure!(
+                    Self::ensure_valid_confirmation_depth_k(confirmation_depth_k).is_ok(),
+                    "PendingConfirmationDepthK must be a valid confirmation depth k"
+                );
+            }
+
+            if let Some(at) = EnableNonRootCallsAt::<T>::get() {
+                ensure!(
+                    at > block_number,
+                    "EnableNonRootCallsAt must be a future block"
+                );
+            }
+
+            for (at, changes) in ScheduledConfigChanges::<T>::iter() {
+                ensure!(
+                    at > block_number,
+                    "ScheduledConfigChanges must only be scheduled at future blocks"
+                );
+                ensure!(
+                    !changes.is_empty(),
+                    "ScheduledConfigChanges must not contain empty entries"
+                );
+            }
+
+            let allowlist_size = TransferAllowlistSize::<T>::get();
+            ensure!(
+                TransferAllowlist::<T>::iter_keys().count() == allowlist_size as usize,
+                "TransferAllowlistSize must match the number of accounts in TransferAllowlist"
+            );
+            ensure!(
+                allowlist_size <= T::MaxTransferAllowlistSize::get(),
+                "TransferAllowlist must not exceed MaxTransferAllowlistSize"
+            );
+
+            let own_pallet_name_hash = Twox128::hash(<Self as PalletInfoAccess>::name().as_bytes());
+            ensure!(
+                DisabledCalls::<T>::iter_keys()
+                    .all(|(pallet_name_hash, _)| pallet_name_hash != own_pallet_name_hash),
+                "DisabledCalls must not contain calls of this pallet"
+            );
+            ensure!(
+                DisabledCalls::<T>::iter_keys().all(|(pallet_name_hash, call_name_hash)| {
+                    !Self::is_non_disableable_call(pallet_name_hash, call_name_hash)
+                }),
+                "DisabledCalls must not contain NonDisableableCalls"
+            );
+
+            ensure!(
+                Self::ensure_valid_cost_of_storage_parameters(&CostOfStorageParams::<T>::get())
+                    .is_ok(),
+                "CostOfStorageParams must be valid"
+            );
+
+            Ok(())
+        }
+
+        fn is_non_disableable_call(pallet_name_hash: [u8; 16], call_name_hash: [u8; 16]) -> bool {
+            T::NonDisableableCalls::get()
+                .iter()
+                .any(|(pallet_name, call_name)| {
+                    Twox128::hash(pallet_name.as_bytes()) == pallet_name_hash
+                        && Twox128::hash(call_name.as_bytes()) == call_name_hash
+                })
+        }
+
+        fn ensure_valid_cost_of_storage_parameters(
+            params: &CostOfStorageParameters,
+        ) -> Result<(), Error<T>> {
+            ensure!(
+                params.replication_factor != 0,
+                Error::<T>::ZeroReplicationFactor
+            );
+            ensure!(
+                params.min_multiplier <= params.max_multiplier,
+                Error::<T>::InvalidMultiplierRange
+            );
+
+            Ok(())
+        }
+
+        fn schedule_confirmation_depth_k(confirmation_depth_k: BlockNumberFor<T>) {
+            let effective_at = frame_system::Pallet::<T>::block_number()
+                .saturating_add(ConfirmationDepthK::<T>::get());
+            PendingConfirmationDepthK::<T>::put((effective_at, confirmation_depth_k));
+
+            Self::deposit_event(Event::ConfirmationDepthKScheduled {
+                confirmation_depth_k,
+                effective_at,
+            });
+        }
+
+        fn note_config_change(key: ConfigKey, changed_by: Option<T::AccountId>) {
+            ConfigChangeLog::<T>::insert(
+                key,
+                (frame_system::Pallet::<T>::block_number(), changed_by),
+            );
+        }
+
+        /// Post dispatch info of a setter, setters that left the configs unchanged don't pay fees.
+        fn post_dispatch_info(changed: bool) -> PostDispatchInfo {
+            if changed {
+                Pays::Yes.into()
+            } else {
+                Pays::No.into()
+            }
+        }
+
+        /// Confirmation depth k that is in effect once the pending value (if any) is applied.
+        fn target_confirmation_depth_k() -> BlockNumberFor<T> {
+            PendingConfirmationDepthK::<T>::get()
+                .map(|(_effective_at, confirmation_depth_k)| confirmation_depth_k)
+                .unwrap_or_else(ConfirmationDepthK::<T>::get)
+        }
+
+        /// Applies the parts of `change` that differ from the current configs and returns whether
+        /// anything changed, nothing is written and no event is emitted otherwise.
+        fn apply_config_change(change: ConfigChange, changed_by: Option<T::AccountId>) -> bool {
+            match change {
+                ConfigChange::FeatureFlags(feature_flags) => {
+                    let feature_flags = FeatureFlagSet {
+                        enable_domains: feature_flags
+                            .enable_domains
+                            .filter(|&enabled| enabled != EnableDomains::<T>::get()),
+                        enable_dynamic_cost_of_storage: feature_flags
+                            .enable_dynamic_cost_of_storage
+                            .filter(|&enabled| enabled != EnableDynamicCostOfStorage::<T>::get()),
+                        enable_balance_transfers: feature_flags
+                            .enable_balance_transfers
+                            .filter(|&enabled| enabled != EnableBalanceTransfers::<T>::get()),
+                        enable_non_root_calls: feature_flags
+                            .enable_non_root_calls
+                            .filter(|&enabled| enabled != EnableNonRootCalls::<T>::get()),
+                    };
+                    if feature_flags == FeatureFlagSet::default() {
+                        return false;
+                    }
+
+                    let FeatureFlagSet {
+                        enable_domains,
+                        enable_dynamic_cost_of_storage,
+                        enable_balance_transfers,
+                        enable_non_root_calls,
+                    } = feature_flags;
+
+                    if let Some(enable_domains) = enable_domains {
+                        EnableDomains::<T>::put(enable_domains);
+                        Self::note_config_change(ConfigKey::EnableDomains, changed_by.clone());
+                    }
+                    if let Some(enable_dynamic_cost_of_storage) = enable_dynamic_cost_of_storage {
+                        EnableDynamicCostOfStorage::<T>::put(enable_dynamic_cost_of_storage);
+                        Self::note_config_change(
+                            ConfigKey::EnableDynamicCostOfStorage,
+                            changed_by.clone(),
+                        );
+                    }
+                    if let Some(enable_balance_transfers) = enable_balance_transfers {
+                        EnableBalanceTransfers::<T>::put(enable_balance_transfers);
+                        Self::note_config_change(
+                            ConfigKey::EnableBalanceTransfers,
+                            changed_by.clone(),
+                        );
+                    }
+                    if let Some(enable_non_root_calls) = enable_non_root_calls {
+                        EnableNonRootCalls::<T>::put(enable_non_root_calls);
+                        Self::note_config_change(ConfigKey::EnableNonRootCalls, changed_by);
+                    }
+
+                    Self::deposit_event(Event::FeatureFlagsUpdated { feature_flags });
+                }
+            }
+
+            true
+        }
+
+        fn apply_pending_confirmation_depth_k(block_number: BlockNumberFor<T>) -> Weight {
+            let Some((effective_at, confirmation_depth_k)) = PendingConfirmationDepthK::<T>::get()
+            else {
+                return T::DbWeight::get().reads(1);
+            };
+
+            if block_number < effective_at {
+                return T::DbWeight::get().reads(1);
+            }
+
+            ConfirmationDepthK::<T>::put(confirmation_depth_k);
+            PendingConfirmationDepthK::<T>::kill();
+
+            Self::deposit_event(Event::ConfirmationDepthKUpdated {
+                confirmation_depth_k,
+            });
+
+            T::DbWeight::get().reads_writes(1, 2)
+        }
+
+        fn apply_enable_non_root_calls_at(block_number: BlockNumberFor<T>) -> Weight {
+            match EnableNonRootCallsAt::<T>::get() {
+                Some(at) if block_number >= at => {
+                    EnableNonRootCallsAt::<T>::kill();
+                    Self::apply_config_change(
+                        ConfigChange::FeatureFlags(FeatureFlagSet {
+                            enable_non_root_calls: Some(true),
+                            ..Default::default()
+                        }),
+                        None,
+                    );
+
+                    T::DbWeight::get().reads_writes(1, 3)
+                }
+                _ => T::DbWeight::get().reads(1),
+            }
+        }
+
+        fn apply_scheduled_config_changes(block_number: BlockNumberFor<T>) -> Weight {
+            let changes = ScheduledConfigChanges::<T>::take(block_number);
+            let applied_changes = changes.len() as u64;
+
+            for change in changes {
+                Self::apply_config_change(change, None);
+            }
+
+            // Changing feature flags is the most expensive change
+            T::DbWeight::get().reads_writes(1, 1).saturating_add(
+                <T as Config>::WeightInfo::set_feature_flags().saturating_mul(applied_changes),
+            )
+        }
     }
 }
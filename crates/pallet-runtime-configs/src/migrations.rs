@@ -0,0 +1,62 @@
+//! Storage migrations of the runtime configs pallet.
+
+/// Migration from the initial storage layout, which had no storage version, to v1.
+pub mod v1 {
+    use crate::{Pallet, TransferAllowlistSize};
+    use core::marker::PhantomData;
+    use frame_support::pallet_prelude::{StorageVersion, Weight};
+    use frame_support::traits::{Get, GetStorageVersion, OnRuntimeUpgrade};
+    #[cfg(feature = "try-runtime")]
+    use sp_runtime::TryRuntimeError;
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// Initializes the storage items added after v0 that have no value yet with their defaults
+    /// and bumps the storage version to 1.
+    ///
+    /// Items that already have a value, e.g. because they were set by a call before the upgrade,
+    /// are left untouched.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: crate::Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() != 0 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut writes = 1;
+            if !TransferAllowlistSize::<T>::exists() {
+                TransferAllowlistSize::<T>::put(0);
+                writes += 1;
+            }
+
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(2, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() <= 1,
+                "Unexpected storage version of pallet-runtime-configs"
+            );
+
+            Ok(Vec::new())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 1,
+                "Storage version of pallet-runtime-configs must be 1 after the migration"
+            );
+            frame_support::ensure!(
+                TransferAllowlistSize::<T>::exists(),
+                "TransferAllowlistSize must be initialized"
+            );
+
+            Ok(())
+        }
+    }
+}
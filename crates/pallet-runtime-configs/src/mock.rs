@@ -0,0 +1,84 @@
+use crate::{self as pallet_runtime_configs};
+use frame_support::traits::{ConstU16, ConstU32, ConstU64, EitherOfDiverse};
+use frame_support::{ord_parameter_types, parameter_types};
+use frame_system::{EnsureRoot, EnsureSignedBy};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub struct Test {
+        System: frame_system,
+        RuntimeConfigs: pallet_runtime_configs,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type RuntimeTask = RuntimeTask;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ConstU16<42>;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+ord_parameter_types! {
+    pub const BalanceTransfersAdmin: u64 = 1;
+}
+
+parameter_types! {
+    pub const NonDisableableCalls: &'static [(&'static str, &'static str)] =
+        &[("System", "set_code")];
+}
+
+impl pallet_runtime_configs::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MinConfirmationDepthK = ConstU64<5>;
+    type MaxConfirmationDepthK = ConstU64<1000>;
+    type MaxScheduledConfigChangesPerBlock = ConstU32<2>;
+    type MaxTransferAllowlistSize = ConstU32<2>;
+    type MaxFrozenDomains = ConstU32<2>;
+    type EnableDomainsOrigin = EnsureRoot<u64>;
+    type EnableDynamicCostOfStorageOrigin = EnsureRoot<u64>;
+    type EnableBalanceTransfersOrigin =
+        EitherOfDiverse<EnsureRoot<u64>, EnsureSignedBy<BalanceTransfersAdmin, u64>>;
+    type EnableNonRootCallsOrigin = EnsureRoot<u64>;
+    type ConfirmationDepthKOrigin = EnsureRoot<u64>;
+    type DisabledCallsOrigin = EnsureRoot<u64>;
+    type NonDisableableCalls = NonDisableableCalls;
+    type MaintenanceModeOrigin = EnsureRoot<u64>;
+    type FrozenDomainsOrigin = EnsureRoot<u64>;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    let mut t: sp_io::TestExternalities = t.into();
+
+    t.execute_with(|| System::set_block_number(1));
+
+    t
+}
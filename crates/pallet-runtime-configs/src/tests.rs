@@ -0,0 +1,1391 @@
+use crate::mock::{
+    new_test_ext, BalanceTransfersAdmin, RuntimeCall, RuntimeConfigs, RuntimeEvent,
+    RuntimeGenesisConfig, RuntimeOrigin, System, Test,
+};
+use crate::weights::WeightInfo;
+use crate::{
+    ConfigChange, ConfigChangeLog, ConfigUpdates, ConfirmationDepthK, CostOfStorageParams,
+    DefaultCostOfStorageParameters, DisabledCalls, EnableBalanceTransfers,
+    EnableChainMaintenanceMode, EnableDomains, EnableDynamicCostOfStorage, EnableNonRootCalls,
+    EnableNonRootCallsAt, EnabledCalls, Error, Event, FeatureFlagSet, FrozenDomains,
+    MaintenanceModeFilter, PendingConfirmationDepthK, ScheduledConfigChanges, TransferAllowlist,
+    TransferAllowlistSize,
+};
+use frame_support::dispatch::{DispatchResultWithPostInfo, GetDispatchInfo, Pays};
+use frame_support::pallet_prelude::StorageVersion;
+use frame_support::traits::{
+    CallMetadata, Contains, Everything, Get, GetCallMetadata, GetCallName, GetStorageVersion,
+    Hooks, Nothing, OnRuntimeUpgrade, PalletInfoAccess, StoragePrefixedMap,
+};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_core::hashing::twox_128;
+use sp_domains::DomainId;
+use sp_runtime::{BuildStorage, DispatchError};
+use sp_runtime_configs::{ConfigKey, CostOfStorageParameters};
+use std::collections::{BTreeMap, BTreeSet};
+
+const NOT_ROOT: u64 = 100;
+
+/// Returns all the key-value pairs stored by the pallet.
+fn pallet_storage() -> BTreeMap<Vec<u8>, Vec<u8>> {
+    let prefix = twox_128(RuntimeConfigs::name().as_bytes());
+    let mut storage = BTreeMap::new();
+    let mut key = prefix.to_vec();
+    while let Some(next_key) = sp_io::storage::next_key(&key) {
+        if !next_key.starts_with(&prefix) {
+            break;
+        }
+        let value = sp_io::storage::get(&next_key).expect("Key exists; qed");
+        storage.insert(next_key.clone(), value.to_vec());
+        key = next_key;
+    }
+    storage
+}
+
+/// Dispatches `call` and returns the keys of the pallet storage whose value changed, ignoring
+/// [`ConfigChangeLog`] that is covered by `config_change_log`.
+fn changed_storage_keys<R, E>(call: impl FnOnce() -> Result<R, E>) -> BTreeSet<Vec<u8>>
+where
+    R: std::fmt::Debug,
+    E: std::fmt::Debug,
+{
+    let before = pallet_storage();
+    assert_ok!(call());
+    let after = pallet_storage();
+
+    let config_change_log_prefix = ConfigChangeLog::<Test>::final_prefix();
+    before
+        .keys()
+        .chain(after.keys())
+        .filter(|key| !key.starts_with(&config_change_log_prefix))
+        .filter(|key| before.get(*key) != after.get(*key))
+        .cloned()
+        .collect()
+}
+
+fn assert_feature_flags_updated(feature_flags: FeatureFlagSet) {
+    System::assert_last_event(RuntimeEvent::RuntimeConfigs(Event::FeatureFlagsUpdated {
+        feature_flags,
+    }));
+}
+
+#[test]
+fn set_enable_domains() {
+    new_test_ext().execute_with(|| {
+        let changed_keys = changed_storage_keys(|| {
+            RuntimeConfigs::set_enable_domains(RuntimeOrigin::root(), true)
+        });
+
+        assert_eq!(
+            changed_keys,
+            BTreeSet::from([EnableDomains::<Test>::hashed_key().to_vec()])
+        );
+        assert!(RuntimeConfigs::enable_domains());
+        assert_feature_flags_updated(FeatureFlagSet {
+            enable_domains: Some(true),
+            ..Default::default()
+        });
+
+        assert_noop!(
+            RuntimeConfigs::set_enable_domains(RuntimeOrigin::signed(NOT_ROOT), false),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn set_enable_dynamic_cost_of_storage() {
+    new_test_ext().execute_with(|| {
+        let changed_keys = changed_storage_keys(|| {
+            RuntimeConfigs::set_enable_dynamic_cost_of_storage(RuntimeOrigin::root(), true)
+        });
+
+        assert_eq!(
+            changed_keys,
+            BTreeSet::from([EnableDynamicCostOfStorage::<Test>::hashed_key().to_vec()])
+        );
+        assert!(RuntimeConfigs::enable_dynamic_cost_of_storage());
+        assert!(!RuntimeConfigs::enable_balance_transfers());
+        assert_feature_flags_updated(FeatureFlagSet {
+            enable_dynamic_cost_of_storage: Some(true),
+            ..Default::default()
+        });
+
+        assert_noop!(
+            RuntimeConfigs::set_enable_dynamic_cost_of_storage(
+                RuntimeOrigin::signed(NOT_ROOT),
+                false
+            ),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn set_enable_balance_transfers() {
+    new_test_ext().execute_with(|| {
+        let changed_keys = changed_storage_keys(|| {
+            RuntimeConfigs::set_enable_balance_transfers(RuntimeOrigin::root(), true)
+        });
+
+        assert_eq!(
+            changed_keys,
+            BTreeSet::from([EnableBalanceTransfers::<Test>::hashed_key().to_vec()])
+        );
+        assert!(RuntimeConfigs::enable_balance_transfers());
+        assert_feature_flags_updated(FeatureFlagSet {
+            enable_balance_transfers: Some(true),
+            ..Default::default()
+        });
+
+        assert_noop!(
+            RuntimeConfigs::set_enable_balance_transfers(RuntimeOrigin::signed(NOT_ROOT), false),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn set_enable_non_root_calls() {
+    new_test_ext().execute_with(|| {
+        let changed_keys = changed_storage_keys(|| {
+            RuntimeConfigs::set_enable_non_root_calls(RuntimeOrigin::root(), true)
+        });
+
+        assert_eq!(
+            changed_keys,
+            BTreeSet::from([EnableNonRootCalls::<Test>::hashed_key().to_vec()])
+        );
+        assert!(RuntimeConfigs::enable_non_root_calls());
+        assert_feature_flags_updated(FeatureFlagSet {
+            enable_non_root_calls: Some(true),
+            ..Default::default()
+        });
+
+        assert_noop!(
+            RuntimeConfigs::set_enable_non_root_calls(RuntimeOrigin::signed(NOT_ROOT), false),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn set_feature_flags() {
+    new_test_ext().execute_with(|| {
+        let feature_flags = FeatureFlagSet {
+            enable_domains: Some(true),
+            enable_dynamic_cost_of_storage: None,
+            enable_balance_transfers: Some(true),
+            enable_non_root_calls: None,
+        };
+
+        let changed_keys = changed_storage_keys(|| {
+            RuntimeConfigs::set_feature_flags(RuntimeOrigin::root(), feature_flags.clone())
+        });
+
+        assert_eq!(
+            changed_keys,
+            BTreeSet::from([
+                EnableDomains::<Test>::hashed_key().to_vec(),
+                EnableBalanceTransfers::<Test>::hashed_key().to_vec(),
+            ])
+        );
+        assert!(RuntimeConfigs::enable_domains());
+        assert!(RuntimeConfigs::enable_balance_transfers());
+        assert_feature_flags_updated(feature_flags);
+
+        assert_noop!(
+            RuntimeConfigs::set_feature_flags(
+                RuntimeOrigin::signed(NOT_ROOT),
+                FeatureFlagSet::default()
+            ),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn redundant_updates() {
+    new_test_ext().execute_with(|| {
+        ConfirmationDepthK::<Test>::put(10);
+
+        let unchanged_calls: [Box<dyn Fn() -> DispatchResultWithPostInfo>; 6] = [
+            Box::new(|| RuntimeConfigs::set_enable_domains(RuntimeOrigin::root(), false)),
+            Box::new(|| {
+                RuntimeConfigs::set_feature_flags(
+                    RuntimeOrigin::root(),
+                    FeatureFlagSet {
+                        enable_balance_transfers: Some(false),
+                        enable_non_root_calls: Some(false),
+                        ..Default::default()
+                    },
+                )
+            }),
+            Box::new(|| RuntimeConfigs::set_confirmation_depth_k(RuntimeOrigin::root(), 10)),
+            Box::new(|| {
+                RuntimeConfigs::set_configs(
+                    RuntimeOrigin::root(),
+                    ConfigUpdates {
+                        enable_domains: Some(false),
+                        confirmation_depth_k: Some(10),
+                        ..Default::default()
+                    },
+                )
+            }),
+            Box::new(|| RuntimeConfigs::set_maintenance_mode(RuntimeOrigin::root(), false)),
+            Box::new(|| {
+                RuntimeConfigs::set_cost_of_storage_parameters(
+                    RuntimeOrigin::root(),
+                    DefaultCostOfStorageParameters::get(),
+                )
+            }),
+        ];
+        for call in unchanged_calls {
+            let mut post_info = None;
+            let changed_keys = changed_storage_keys(|| call().map(|info| post_info = Some(info)));
+
+            assert_eq!(post_info.unwrap().pays_fee, Pays::No);
+            assert!(changed_keys.is_empty());
+        }
+        assert!(System::events().is_empty());
+        assert!(RuntimeConfigs::config_change_log().is_empty());
+
+        // Changed values are paid for as usual
+        let post_info = RuntimeConfigs::set_enable_domains(RuntimeOrigin::root(), true).unwrap();
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+        assert_feature_flags_updated(FeatureFlagSet {
+            enable_domains: Some(true),
+            ..Default::default()
+        });
+
+        // Only the flags that change are written and reported
+        assert_ok!(RuntimeConfigs::set_feature_flags(
+            RuntimeOrigin::root(),
+            FeatureFlagSet {
+                enable_domains: Some(true),
+                enable_balance_transfers: Some(true),
+                ..Default::default()
+            }
+        ));
+        assert_feature_flags_updated(FeatureFlagSet {
+            enable_balance_transfers: Some(true),
+            ..Default::default()
+        });
+
+        // Only the configs that change are charged for
+        let post_info = RuntimeConfigs::set_configs(
+            RuntimeOrigin::root(),
+            ConfigUpdates {
+                enable_domains: Some(true),
+                enable_non_root_calls: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+        assert_eq!(
+            post_info.actual_weight,
+            Some(<Test as crate::Config>::WeightInfo::set_configs(1))
+        );
+
+        // Setting the pending confirmation depth k again doesn't restart the delay
+        assert_ok!(RuntimeConfigs::set_confirmation_depth_k(
+            RuntimeOrigin::root(),
+            20
+        ));
+        System::set_block_number(5);
+        let post_info =
+            RuntimeConfigs::set_confirmation_depth_k(RuntimeOrigin::root(), 20).unwrap();
+        assert_eq!(post_info.pays_fee, Pays::No);
+        assert_eq!(PendingConfirmationDepthK::<Test>::get(), Some((11, 20)));
+    });
+}
+
+#[test]
+fn set_confirmation_depth_k() {
+    new_test_ext().execute_with(|| {
+        ConfirmationDepthK::<Test>::put(10);
+
+        assert_noop!(
+            RuntimeConfigs::set_confirmation_depth_k(RuntimeOrigin::signed(NOT_ROOT), 20),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            RuntimeConfigs::set_confirmation_depth_k(RuntimeOrigin::root(), 0),
+            Error::<Test>::ZeroConfirmationDepthK
+        );
+        assert_noop!(
+            RuntimeConfigs::set_confirmation_depth_k(RuntimeOrigin::root(), 4),
+            Error::<Test>::ConfirmationDepthKTooLow
+        );
+        assert_noop!(
+            RuntimeConfigs::set_confirmation_depth_k(RuntimeOrigin::root(), 1001),
+            Error::<Test>::ConfirmationDepthKTooHigh
+        );
+
+        // The new value is only scheduled, the old one is kept for `old_k` blocks.
+        assert_ok!(RuntimeConfigs::set_confirmation_depth_k(
+            RuntimeOrigin::root(),
+            20
+        ));
+        assert_eq!(PendingConfirmationDepthK::<Test>::get(), Some((11, 20)));
+        assert_eq!(ConfirmationDepthK::<Test>::get(), 10);
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(
+            Event::ConfirmationDepthKScheduled {
+                confirmation_depth_k: 20,
+                effective_at: 11,
+            },
+        ));
+
+        RuntimeConfigs::on_initialize(10);
+        assert_eq!(ConfirmationDepthK::<Test>::get(), 10);
+
+        RuntimeConfigs::on_initialize(11);
+        assert_eq!(ConfirmationDepthK::<Test>::get(), 20);
+        assert_eq!(PendingConfirmationDepthK::<Test>::get(), None);
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(
+            Event::ConfirmationDepthKUpdated {
+                confirmation_depth_k: 20,
+            },
+        ));
+    });
+}
+
+#[test]
+fn set_confirmation_depth_k_replaces_pending_value() {
+    new_test_ext().execute_with(|| {
+        ConfirmationDepthK::<Test>::put(10);
+
+        assert_ok!(RuntimeConfigs::set_confirmation_depth_k(
+            RuntimeOrigin::root(),
+            20
+        ));
+
+        System::set_block_number(5);
+        assert_ok!(RuntimeConfigs::set_confirmation_depth_k(
+            RuntimeOrigin::root(),
+            30
+        ));
+        assert_eq!(PendingConfirmationDepthK::<Test>::get(), Some((15, 30)));
+
+        RuntimeConfigs::on_initialize(11);
+        assert_eq!(ConfirmationDepthK::<Test>::get(), 10);
+
+        RuntimeConfigs::on_initialize(15);
+        assert_eq!(ConfirmationDepthK::<Test>::get(), 30);
+    });
+}
+
+#[test]
+fn schedule_config_change() {
+    new_test_ext().execute_with(|| {
+        let enable_balance_transfers = ConfigChange::FeatureFlags(FeatureFlagSet {
+            enable_balance_transfers: Some(true),
+            ..Default::default()
+        });
+        let enable_non_root_calls = ConfigChange::FeatureFlags(FeatureFlagSet {
+            enable_non_root_calls: Some(true),
+            ..Default::default()
+        });
+
+        assert_noop!(
+            RuntimeConfigs::schedule_config_change(
+                RuntimeOrigin::signed(NOT_ROOT),
+                enable_balance_transfers.clone(),
+                5
+            ),
+            DispatchError::BadOrigin
+        );
+        // Current block is 1
+        for at in [0, 1] {
+            assert_noop!(
+                RuntimeConfigs::schedule_config_change(
+                    RuntimeOrigin::root(),
+                    enable_balance_transfers.clone(),
+                    at
+                ),
+                Error::<Test>::ScheduledInThePast
+            );
+        }
+
+        assert_ok!(RuntimeConfigs::schedule_config_change(
+            RuntimeOrigin::root(),
+            enable_balance_transfers.clone(),
+            5
+        ));
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(Event::ConfigChangeScheduled {
+            change: enable_balance_transfers.clone(),
+            at: 5,
+        }));
+        assert_ok!(RuntimeConfigs::schedule_config_change(
+            RuntimeOrigin::root(),
+            enable_non_root_calls.clone(),
+            5
+        ));
+        assert_noop!(
+            RuntimeConfigs::schedule_config_change(
+                RuntimeOrigin::root(),
+                enable_non_root_calls.clone(),
+                5
+            ),
+            Error::<Test>::TooManyScheduledConfigChanges
+        );
+
+        RuntimeConfigs::on_initialize(4);
+        assert!(!RuntimeConfigs::enable_balance_transfers());
+        assert!(!RuntimeConfigs::enable_non_root_calls());
+
+        RuntimeConfigs::on_initialize(5);
+        assert!(RuntimeConfigs::enable_balance_transfers());
+        assert!(RuntimeConfigs::enable_non_root_calls());
+        assert!(!ScheduledConfigChanges::<Test>::contains_key(5));
+
+        let events = System::events()
+            .into_iter()
+            .rev()
+            .take(2)
+            .map(|record| record.event)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                RuntimeEvent::RuntimeConfigs(Event::FeatureFlagsUpdated {
+                    feature_flags: FeatureFlagSet {
+                        enable_non_root_calls: Some(true),
+                        ..Default::default()
+                    },
+                }),
+                RuntimeEvent::RuntimeConfigs(Event::FeatureFlagsUpdated {
+                    feature_flags: FeatureFlagSet {
+                        enable_balance_transfers: Some(true),
+                        ..Default::default()
+                    },
+                }),
+            ]
+        );
+    });
+}
+
+#[test]
+fn cancel_scheduled_config_change() {
+    new_test_ext().execute_with(|| {
+        let change = ConfigChange::FeatureFlags(FeatureFlagSet {
+            enable_domains: Some(true),
+            ..Default::default()
+        });
+
+        assert_noop!(
+            RuntimeConfigs::cancel_scheduled_config_change(
+                RuntimeOrigin::root(),
+                change.clone(),
+                5
+            ),
+            Error::<Test>::ScheduledConfigChangeNotFound
+        );
+
+        assert_ok!(RuntimeConfigs::schedule_config_change(
+            RuntimeOrigin::root(),
+            change.clone(),
+            5
+        ));
+        assert_noop!(
+            RuntimeConfigs::cancel_scheduled_config_change(
+                RuntimeOrigin::signed(NOT_ROOT),
+                change.clone(),
+                5
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            RuntimeConfigs::cancel_scheduled_config_change(
+                RuntimeOrigin::root(),
+                ConfigChange::FeatureFlags(FeatureFlagSet {
+                    enable_non_root_calls: Some(true),
+                    ..Default::default()
+                }),
+                5
+            ),
+            Error::<Test>::ScheduledConfigChangeNotFound
+        );
+
+        assert_ok!(RuntimeConfigs::cancel_scheduled_config_change(
+            RuntimeOrigin::root(),
+            change.clone(),
+            5
+        ));
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(
+            Event::ScheduledConfigChangeCancelled { change, at: 5 },
+        ));
+        assert!(!ScheduledConfigChanges::<Test>::contains_key(5));
+
+        RuntimeConfigs::on_initialize(5);
+        assert!(!RuntimeConfigs::enable_domains());
+    });
+}
+
+#[test]
+fn set_configs() {
+    new_test_ext().execute_with(|| {
+        ConfirmationDepthK::<Test>::put(10);
+
+        let updates = ConfigUpdates {
+            enable_domains: Some(true),
+            enable_non_root_calls: Some(true),
+            confirmation_depth_k: Some(20),
+            ..Default::default()
+        };
+        assert_eq!(updates.count(), 3);
+
+        let changed_keys = changed_storage_keys(|| {
+            RuntimeConfigs::set_configs(RuntimeOrigin::root(), updates.clone())
+        });
+
+        assert_eq!(
+            changed_keys,
+            BTreeSet::from([
+                EnableDomains::<Test>::hashed_key().to_vec(),
+                EnableNonRootCalls::<Test>::hashed_key().to_vec(),
+                PendingConfirmationDepthK::<Test>::hashed_key().to_vec(),
+            ])
+        );
+        assert_eq!(PendingConfirmationDepthK::<Test>::get(), Some((11, 20)));
+
+        let events = System::events()
+            .into_iter()
+            .map(|record| record.event)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                RuntimeEvent::RuntimeConfigs(Event::FeatureFlagsUpdated {
+                    feature_flags: FeatureFlagSet {
+                        enable_domains: Some(true),
+                        ..Default::default()
+                    },
+                }),
+                RuntimeEvent::RuntimeConfigs(Event::FeatureFlagsUpdated {
+                    feature_flags: FeatureFlagSet {
+                        enable_non_root_calls: Some(true),
+                        ..Default::default()
+                    },
+                }),
+                RuntimeEvent::RuntimeConfigs(Event::ConfirmationDepthKScheduled {
+                    confirmation_depth_k: 20,
+                    effective_at: 11,
+                }),
+            ]
+        );
+
+        assert_noop!(
+            RuntimeConfigs::set_configs(RuntimeOrigin::signed(NOT_ROOT), updates),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn set_configs_is_atomic() {
+    new_test_ext().execute_with(|| {
+        // The invalid confirmation depth k is rejected before anything else is changed
+        assert_noop!(
+            RuntimeConfigs::set_configs(
+                RuntimeOrigin::root(),
+                ConfigUpdates {
+                    enable_balance_transfers: Some(true),
+                    confirmation_depth_k: Some(1),
+                    ..Default::default()
+                }
+            ),
+            Error::<Test>::ConfirmationDepthKTooLow
+        );
+        assert!(!RuntimeConfigs::enable_balance_transfers());
+    });
+}
+
+#[test]
+fn configured_origin() {
+    new_test_ext().execute_with(|| {
+        let admin = RuntimeOrigin::signed(BalanceTransfersAdmin::get());
+
+        assert_ok!(RuntimeConfigs::set_enable_balance_transfers(
+            admin.clone(),
+            true
+        ));
+        assert!(RuntimeConfigs::enable_balance_transfers());
+        assert_ok!(RuntimeConfigs::set_feature_flags(
+            admin.clone(),
+            FeatureFlagSet {
+                enable_balance_transfers: Some(false),
+                ..Default::default()
+            }
+        ));
+        assert!(!RuntimeConfigs::enable_balance_transfers());
+
+        // Other configs are still root only
+        assert_noop!(
+            RuntimeConfigs::set_enable_domains(admin.clone(), true),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            RuntimeConfigs::set_feature_flags(
+                admin.clone(),
+                FeatureFlagSet {
+                    enable_domains: Some(true),
+                    enable_balance_transfers: Some(true),
+                    ..Default::default()
+                }
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            RuntimeConfigs::set_configs(admin.clone(), ConfigUpdates::default()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            RuntimeConfigs::set_enable_balance_transfers(RuntimeOrigin::signed(NOT_ROOT), true),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn runtime_configs() {
+    new_test_ext().execute_with(|| {
+        ConfirmationDepthK::<Test>::put(10);
+        assert_eq!(
+            RuntimeConfigs::runtime_configs(),
+            sp_runtime_configs::RuntimeConfigs {
+                confirmation_depth_k: 10,
+                cost_of_storage_parameters: DefaultCostOfStorageParameters::get(),
+                ..Default::default()
+            }
+        );
+
+        assert_ok!(RuntimeConfigs::set_enable_balance_transfers(
+            RuntimeOrigin::root(),
+            true
+        ));
+        assert_eq!(
+            RuntimeConfigs::runtime_configs(),
+            sp_runtime_configs::RuntimeConfigs {
+                enable_balance_transfers: true,
+                confirmation_depth_k: 10,
+                cost_of_storage_parameters: DefaultCostOfStorageParameters::get(),
+                ..Default::default()
+            }
+        );
+    });
+}
+
+#[test]
+fn transfer_allowlist() {
+    new_test_ext().execute_with(|| {
+        assert!(!RuntimeConfigs::can_transfer(&1));
+
+        assert_noop!(
+            RuntimeConfigs::add_to_transfer_allowlist(RuntimeOrigin::signed(NOT_ROOT), 1),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RuntimeConfigs::add_to_transfer_allowlist(
+            RuntimeOrigin::root(),
+            1
+        ));
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(
+            Event::AddedToTransferAllowlist { who: 1 },
+        ));
+        assert!(RuntimeConfigs::can_transfer(&1));
+        assert!(!RuntimeConfigs::can_transfer(&2));
+
+        assert_noop!(
+            RuntimeConfigs::add_to_transfer_allowlist(RuntimeOrigin::root(), 1),
+            Error::<Test>::AlreadyInTransferAllowlist
+        );
+        assert_ok!(RuntimeConfigs::add_to_transfer_allowlist(
+            RuntimeOrigin::root(),
+            2
+        ));
+        assert_noop!(
+            RuntimeConfigs::add_to_transfer_allowlist(RuntimeOrigin::root(), 3),
+            Error::<Test>::TransferAllowlistFull
+        );
+        assert_eq!(TransferAllowlistSize::<Test>::get(), 2);
+
+        // Everyone can transfer once transfers are enabled
+        EnableBalanceTransfers::<Test>::put(true);
+        assert!(RuntimeConfigs::can_transfer(&3));
+        EnableBalanceTransfers::<Test>::put(false);
+
+        assert_noop!(
+            RuntimeConfigs::remove_from_transfer_allowlist(RuntimeOrigin::signed(NOT_ROOT), 1),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RuntimeConfigs::remove_from_transfer_allowlist(
+            RuntimeOrigin::root(),
+            1
+        ));
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(
+            Event::RemovedFromTransferAllowlist { who: 1 },
+        ));
+        assert!(!RuntimeConfigs::can_transfer(&1));
+        assert_eq!(TransferAllowlistSize::<Test>::get(), 1);
+        assert_noop!(
+            RuntimeConfigs::remove_from_transfer_allowlist(RuntimeOrigin::root(), 1),
+            Error::<Test>::NotInTransferAllowlist
+        );
+
+        assert_ok!(RuntimeConfigs::add_to_transfer_allowlist(
+            RuntimeOrigin::root(),
+            3
+        ));
+    });
+}
+
+#[test]
+fn disabled_calls() {
+    new_test_ext().execute_with(|| {
+        let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let remark_with_event =
+            RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![] });
+        let system_hash = twox_128(System::name().as_bytes());
+        let remark_hash = twox_128(remark.get_call_metadata().function_name.as_bytes());
+        assert!(EnabledCalls::<Test>::contains(&remark));
+
+        assert_noop!(
+            RuntimeConfigs::disable_call(RuntimeOrigin::signed(NOT_ROOT), system_hash, remark_hash),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RuntimeConfigs::disable_call(
+            RuntimeOrigin::root(),
+            system_hash,
+            remark_hash
+        ));
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(Event::CallDisabled {
+            pallet_name_hash: system_hash,
+            call_name_hash: remark_hash,
+        }));
+        assert!(!EnabledCalls::<Test>::contains(&remark));
+        assert!(EnabledCalls::<Test>::contains(&remark_with_event));
+        assert_noop!(
+            RuntimeConfigs::disable_call(RuntimeOrigin::root(), system_hash, remark_hash),
+            Error::<Test>::CallAlreadyDisabled
+        );
+
+        assert_noop!(
+            RuntimeConfigs::enable_call(RuntimeOrigin::signed(NOT_ROOT), system_hash, remark_hash),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(RuntimeConfigs::enable_call(
+            RuntimeOrigin::root(),
+            system_hash,
+            remark_hash
+        ));
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(Event::CallEnabled {
+            pallet_name_hash: system_hash,
+            call_name_hash: remark_hash,
+        }));
+        assert!(EnabledCalls::<Test>::contains(&remark));
+        assert_noop!(
+            RuntimeConfigs::enable_call(RuntimeOrigin::root(), system_hash, remark_hash),
+            Error::<Test>::CallNotDisabled
+        );
+    });
+}
+
+#[test]
+fn own_calls_can_not_be_disabled() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            RuntimeConfigs::disable_call(
+                RuntimeOrigin::root(),
+                twox_128(RuntimeConfigs::name().as_bytes()),
+                [0; 16]
+            ),
+            Error::<Test>::CannotDisableOwnCalls
+        );
+    });
+}
+
+#[test]
+fn non_disableable_calls_can_not_be_disabled() {
+    new_test_ext().execute_with(|| {
+        let set_code = RuntimeCall::System(frame_system::Call::set_code { code: vec![] });
+        let CallMetadata {
+            function_name,
+            pallet_name,
+        } = set_code.get_call_metadata();
+        let pallet_name_hash = twox_128(pallet_name.as_bytes());
+        let call_name_hash = twox_128(function_name.as_bytes());
+
+        assert_noop!(
+            RuntimeConfigs::disable_call(RuntimeOrigin::root(), pallet_name_hash, call_name_hash),
+            Error::<Test>::CannotDisableCall
+        );
+        assert!(EnabledCalls::<Test>::contains(&set_code));
+
+        // Other calls of the same pallet can still be disabled
+        assert_ok!(RuntimeConfigs::disable_call(
+            RuntimeOrigin::root(),
+            pallet_name_hash,
+            twox_128("remark".as_bytes())
+        ));
+        assert!(EnabledCalls::<Test>::contains(&set_code));
+    });
+}
+
+#[test]
+fn config_change_log() {
+    new_test_ext().execute_with(|| {
+        let admin = BalanceTransfersAdmin::get();
+        assert!(RuntimeConfigs::config_change_log().is_empty());
+
+        assert_ok!(RuntimeConfigs::set_enable_balance_transfers(
+            RuntimeOrigin::root(),
+            true
+        ));
+        assert_eq!(
+            RuntimeConfigs::last_config_change(ConfigKey::EnableBalanceTransfers),
+            Some((1, None))
+        );
+
+        // Only the latest change is kept, with the account of a signed origin
+        System::set_block_number(3);
+        assert_ok!(RuntimeConfigs::set_feature_flags(
+            RuntimeOrigin::signed(admin),
+            FeatureFlagSet {
+                enable_balance_transfers: Some(false),
+                ..Default::default()
+            }
+        ));
+        assert_eq!(
+            RuntimeConfigs::last_config_change(ConfigKey::EnableBalanceTransfers),
+            Some((3, Some(admin)))
+        );
+
+        // Failed calls are not recorded
+        assert_noop!(
+            RuntimeConfigs::set_confirmation_depth_k(RuntimeOrigin::root(), 0),
+            Error::<Test>::ZeroConfirmationDepthK
+        );
+        assert_eq!(
+            RuntimeConfigs::last_config_change(ConfigKey::ConfirmationDepthK),
+            None
+        );
+
+        // Scheduled changes are applied without account
+        assert_ok!(RuntimeConfigs::schedule_config_change(
+            RuntimeOrigin::root(),
+            ConfigChange::FeatureFlags(FeatureFlagSet {
+                enable_non_root_calls: Some(true),
+                ..Default::default()
+            }),
+            5
+        ));
+        System::set_block_number(5);
+        RuntimeConfigs::on_initialize(5);
+        assert_eq!(
+            RuntimeConfigs::last_config_change(ConfigKey::EnableNonRootCalls),
+            Some((5, None))
+        );
+
+        assert_ok!(RuntimeConfigs::add_to_transfer_allowlist(
+            RuntimeOrigin::signed(admin),
+            2
+        ));
+
+        let mut config_change_log = RuntimeConfigs::config_change_log();
+        config_change_log.sort();
+        assert_eq!(
+            config_change_log,
+            vec![
+                (ConfigKey::EnableBalanceTransfers, 3, Some(admin)),
+                (ConfigKey::EnableNonRootCalls, 5, None),
+                (ConfigKey::TransferAllowlist, 5, Some(admin)),
+            ]
+        );
+    });
+}
+
+#[test]
+fn set_maintenance_mode() {
+    new_test_ext().execute_with(|| {
+        assert!(!RuntimeConfigs::enable_chain_maintenance_mode());
+
+        assert_noop!(
+            RuntimeConfigs::set_maintenance_mode(RuntimeOrigin::signed(NOT_ROOT), true),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(RuntimeConfigs::set_maintenance_mode(
+            RuntimeOrigin::root(),
+            true
+        ));
+        assert!(RuntimeConfigs::enable_chain_maintenance_mode());
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(Event::MaintenanceModeEntered));
+
+        assert_ok!(RuntimeConfigs::set_maintenance_mode(
+            RuntimeOrigin::root(),
+            false
+        ));
+        assert!(!RuntimeConfigs::enable_chain_maintenance_mode());
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(Event::MaintenanceModeExited));
+    });
+}
+
+#[test]
+fn maintenance_mode_filter() {
+    new_test_ext().execute_with(|| {
+        let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let set_maintenance_mode =
+            RuntimeCall::RuntimeConfigs(crate::Call::set_maintenance_mode { enabled: false });
+
+        assert!(MaintenanceModeFilter::<Test, Nothing>::contains(&remark));
+        assert!(MaintenanceModeFilter::<Test, Nothing>::contains(
+            &set_maintenance_mode
+        ));
+
+        EnableChainMaintenanceMode::<Test>::put(true);
+
+        assert!(!MaintenanceModeFilter::<Test, Nothing>::contains(&remark));
+        assert!(MaintenanceModeFilter::<Test, Everything>::contains(&remark));
+        // Calls of the pallet are always allowed, so that maintenance mode can be exited
+        assert!(MaintenanceModeFilter::<Test, Nothing>::contains(
+            &set_maintenance_mode
+        ));
+    });
+}
+
+#[test]
+fn freeze_domain() {
+    new_test_ext().execute_with(|| {
+        let domain_id = DomainId::new(0);
+        assert!(!RuntimeConfigs::is_domain_frozen(domain_id));
+
+        assert_noop!(
+            RuntimeConfigs::freeze_domain(RuntimeOrigin::signed(NOT_ROOT), domain_id),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            RuntimeConfigs::unfreeze_domain(RuntimeOrigin::root(), domain_id),
+            Error::<Test>::DomainNotFrozen
+        );
+
+        assert_ok!(RuntimeConfigs::freeze_domain(
+            RuntimeOrigin::root(),
+            domain_id
+        ));
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(Event::DomainFrozen {
+            domain_id,
+        }));
+        assert!(RuntimeConfigs::is_domain_frozen(domain_id));
+        assert!(<RuntimeConfigs as Contains<DomainId>>::contains(&domain_id));
+        assert!(!RuntimeConfigs::is_domain_frozen(DomainId::new(1)));
+        assert_noop!(
+            RuntimeConfigs::freeze_domain(RuntimeOrigin::root(), domain_id),
+            Error::<Test>::DomainAlreadyFrozen
+        );
+
+        // Domains that don't exist (yet) can be frozen too, up to the limit
+        assert_ok!(RuntimeConfigs::freeze_domain(
+            RuntimeOrigin::root(),
+            DomainId::new(100)
+        ));
+        assert_noop!(
+            RuntimeConfigs::freeze_domain(RuntimeOrigin::root(), DomainId::new(101)),
+            Error::<Test>::TooManyFrozenDomains
+        );
+
+        assert_ok!(RuntimeConfigs::unfreeze_domain(
+            RuntimeOrigin::root(),
+            domain_id
+        ));
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(Event::DomainUnfrozen {
+            domain_id,
+        }));
+        assert!(!RuntimeConfigs::is_domain_frozen(domain_id));
+        assert_eq!(FrozenDomains::<Test>::get().len(), 1);
+    });
+}
+
+#[test]
+fn set_cost_of_storage_parameters() {
+    new_test_ext().execute_with(|| {
+        let old = DefaultCostOfStorageParameters::get();
+        assert!(!CostOfStorageParams::<Test>::exists());
+        assert_eq!(RuntimeConfigs::cost_of_storage_parameters(), old);
+
+        let params = CostOfStorageParameters {
+            replication_factor: 10,
+            min_multiplier: 2,
+            max_multiplier: 2,
+        };
+        assert_noop!(
+            RuntimeConfigs::set_cost_of_storage_parameters(RuntimeOrigin::signed(NOT_ROOT), params),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            RuntimeConfigs::set_cost_of_storage_parameters(
+                RuntimeOrigin::root(),
+                CostOfStorageParameters {
+                    replication_factor: 0,
+                    ..params
+                }
+            ),
+            Error::<Test>::ZeroReplicationFactor
+        );
+        assert_noop!(
+            RuntimeConfigs::set_cost_of_storage_parameters(
+                RuntimeOrigin::root(),
+                CostOfStorageParameters {
+                    min_multiplier: 3,
+                    ..params
+                }
+            ),
+            Error::<Test>::InvalidMultiplierRange
+        );
+
+        assert_ok!(RuntimeConfigs::set_cost_of_storage_parameters(
+            RuntimeOrigin::root(),
+            params
+        ));
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(
+            Event::CostOfStorageParametersUpdated { old, new: params },
+        ));
+        assert_eq!(RuntimeConfigs::cost_of_storage_parameters(), params);
+        assert_eq!(
+            RuntimeConfigs::runtime_configs().cost_of_storage_parameters,
+            params
+        );
+    });
+}
+
+#[test]
+fn clamp_transaction_byte_fee() {
+    let params = CostOfStorageParameters {
+        replication_factor: 10,
+        min_multiplier: 2,
+        max_multiplier: 5,
+    };
+
+    assert_eq!(params.clamp_transaction_byte_fee(1, 10), 20);
+    assert_eq!(params.clamp_transaction_byte_fee(30, 10), 30);
+    assert_eq!(params.clamp_transaction_byte_fee(100, 10), 50);
+}
+
+#[test]
+fn genesis_confirmation_depth_k_within_bounds() {
+    let storage = RuntimeGenesisConfig {
+        runtime_configs: crate::GenesisConfig {
+            confirmation_depth_k: 1000,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+    .build_storage()
+    .unwrap();
+    sp_io::TestExternalities::new(storage).execute_with(|| {
+        assert_eq!(ConfirmationDepthK::<Test>::get(), 1000);
+        assert_eq!(RuntimeConfigs::confirmation_depth_k(), 1000);
+    });
+}
+
+#[test]
+#[should_panic(expected = "ConfirmationDepthK can not be larger than MaxConfirmationDepthK")]
+fn genesis_confirmation_depth_k_too_high() {
+    RuntimeGenesisConfig {
+        runtime_configs: crate::GenesisConfig {
+            confirmation_depth_k: 1001,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+    .build_storage()
+    .unwrap();
+}
+
+#[test]
+fn enable_non_root_calls_at() {
+    let storage = RuntimeGenesisConfig {
+        runtime_configs: crate::GenesisConfig {
+            enable_non_root_calls_at: Some(3),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+    .build_storage()
+    .unwrap();
+    sp_io::TestExternalities::new(storage).execute_with(|| {
+        System::set_block_number(1);
+        assert_eq!(RuntimeConfigs::enable_non_root_calls_at(), Some(3));
+
+        RuntimeConfigs::on_initialize(2);
+        assert!(!RuntimeConfigs::enable_non_root_calls());
+
+        RuntimeConfigs::on_initialize(3);
+        assert!(RuntimeConfigs::enable_non_root_calls());
+        assert!(!EnableNonRootCallsAt::<Test>::exists());
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(Event::FeatureFlagsUpdated {
+            feature_flags: FeatureFlagSet {
+                enable_non_root_calls: Some(true),
+                ..Default::default()
+            },
+        }));
+    });
+
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            RuntimeConfigs::set_enable_non_root_calls_at(RuntimeOrigin::signed(NOT_ROOT), Some(5)),
+            DispatchError::BadOrigin
+        );
+        // Current block is 1
+        assert_noop!(
+            RuntimeConfigs::set_enable_non_root_calls_at(RuntimeOrigin::root(), Some(1)),
+            Error::<Test>::ScheduledInThePast
+        );
+
+        assert_ok!(RuntimeConfigs::set_enable_non_root_calls_at(
+            RuntimeOrigin::root(),
+            Some(5)
+        ));
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(
+            Event::EnableNonRootCallsAtUpdated { at: Some(5) },
+        ));
+
+        // The flag can still be changed before the activation
+        assert_ok!(RuntimeConfigs::set_enable_non_root_calls(
+            RuntimeOrigin::root(),
+            true
+        ));
+        assert_eq!(RuntimeConfigs::enable_non_root_calls_at(), Some(5));
+
+        assert_ok!(RuntimeConfigs::set_enable_non_root_calls(
+            RuntimeOrigin::root(),
+            false
+        ));
+        assert_ok!(RuntimeConfigs::set_enable_non_root_calls_at(
+            RuntimeOrigin::root(),
+            None
+        ));
+        System::assert_last_event(RuntimeEvent::RuntimeConfigs(
+            Event::EnableNonRootCallsAtUpdated { at: None },
+        ));
+
+        // Cancelled activation doesn't happen
+        RuntimeConfigs::on_initialize(5);
+        assert!(!RuntimeConfigs::enable_non_root_calls());
+    });
+}
+
+#[test]
+fn every_call_has_weight_info() {
+    let change = ConfigChange::FeatureFlags(FeatureFlagSet::default());
+    let calls = [
+        (
+            crate::Call::<Test>::set_enable_domains {
+                enable_domains: true,
+            },
+            <() as WeightInfo>::set_enable_domains(),
+        ),
+        (
+            crate::Call::set_enable_dynamic_cost_of_storage {
+                enable_dynamic_cost_of_storage: true,
+            },
+            <() as WeightInfo>::set_enable_dynamic_cost_of_storage(),
+        ),
+        (
+            crate::Call::set_enable_balance_transfers {
+                enable_balance_transfers: true,
+            },
+            <() as WeightInfo>::set_enable_balance_transfers(),
+        ),
+        (
+            crate::Call::set_enable_non_root_calls {
+                enable_non_root_calls: true,
+            },
+            <() as WeightInfo>::set_enable_non_root_calls(),
+        ),
+        (
+            crate::Call::set_feature_flags {
+                feature_flags: FeatureFlagSet::default(),
+            },
+            <() as WeightInfo>::set_feature_flags(),
+        ),
+        (
+            crate::Call::set_confirmation_depth_k {
+                confirmation_depth_k: 10,
+            },
+            <() as WeightInfo>::set_confirmation_depth_k(),
+        ),
+        (
+            crate::Call::schedule_config_change {
+                change: change.clone(),
+                at: 10,
+            },
+            <() as WeightInfo>::schedule_config_change(),
+        ),
+        (
+            crate::Call::cancel_scheduled_config_change { change, at: 10 },
+            <() as WeightInfo>::cancel_scheduled_config_change(),
+        ),
+        (
+            crate::Call::set_configs {
+                updates: ConfigUpdates {
+                    enable_domains: Some(true),
+                    ..Default::default()
+                },
+            },
+            <() as WeightInfo>::set_configs(1),
+        ),
+        (
+            crate::Call::add_to_transfer_allowlist { who: 1 },
+            <() as WeightInfo>::add_to_transfer_allowlist(),
+        ),
+        (
+            crate::Call::remove_from_transfer_allowlist { who: 1 },
+            <() as WeightInfo>::remove_from_transfer_allowlist(),
+        ),
+        (
+            crate::Call::disable_call {
+                pallet_name_hash: [0; 16],
+                call_name_hash: [0; 16],
+            },
+            <() as WeightInfo>::disable_call(),
+        ),
+        (
+            crate::Call::enable_call {
+                pallet_name_hash: [0; 16],
+                call_name_hash: [0; 16],
+            },
+            <() as WeightInfo>::enable_call(),
+        ),
+        (
+            crate::Call::set_cost_of_storage_parameters {
+                params: DefaultCostOfStorageParameters::get(),
+            },
+            <() as WeightInfo>::set_cost_of_storage_parameters(),
+        ),
+        (
+            crate::Call::set_enable_non_root_calls_at { at: Some(10) },
+            <() as WeightInfo>::set_enable_non_root_calls_at(),
+        ),
+        (
+            crate::Call::set_maintenance_mode { enabled: true },
+            <() as WeightInfo>::set_maintenance_mode(),
+        ),
+        (
+            crate::Call::freeze_domain {
+                domain_id: DomainId::new(0),
+            },
+            <() as WeightInfo>::freeze_domain(),
+        ),
+        (
+            crate::Call::unfreeze_domain {
+                domain_id: DomainId::new(0),
+            },
+            <() as WeightInfo>::unfreeze_domain(),
+        ),
+    ];
+
+    let call_names = calls
+        .iter()
+        .map(|(call, _weight)| call.get_call_name())
+        .collect::<BTreeSet<_>>();
+    assert_eq!(
+        call_names,
+        crate::Call::<Test>::get_call_names()
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>(),
+        "Every call must be listed here together with its weight"
+    );
+    for (call, weight) in calls {
+        assert_eq!(
+            call.get_dispatch_info().weight,
+            weight,
+            "Unexpected weight of {}",
+            call.get_call_name()
+        );
+    }
+}
+
+#[test]
+fn try_state() {
+    let storage = RuntimeGenesisConfig::default().build_storage().unwrap();
+    let corruptions: [fn(); 10] = [
+        || ConfirmationDepthK::<Test>::put(0),
+        || EnableNonRootCallsAt::<Test>::put(1),
+        || PendingConfirmationDepthK::<Test>::put((1, 10)),
+        || PendingConfirmationDepthK::<Test>::put((2, 1)),
+        || {
+            ScheduledConfigChanges::<Test>::insert(
+                1,
+                BoundedVec::truncate_from(vec![ConfigChange::FeatureFlags(
+                    FeatureFlagSet::default(),
+                )]),
+            )
+        },
+        || TransferAllowlistSize::<Test>::put(1),
+        || {
+            TransferAllowlist::<Test>::insert(1, ());
+        },
+        || {
+            DisabledCalls::<Test>::insert(
+                (twox_128(RuntimeConfigs::name().as_bytes()), [0; 16]),
+                (),
+            )
+        },
+        || {
+            DisabledCalls::<Test>::insert(
+                (
+                    twox_128(System::name().as_bytes()),
+                    twox_128("set_code".as_bytes()),
+                ),
+                (),
+            )
+        },
+        || {
+            CostOfStorageParams::<Test>::put(CostOfStorageParameters {
+                replication_factor: 0,
+                min_multiplier: 0,
+                max_multiplier: 1,
+            })
+        },
+    ];
+
+    for (index, corrupt) in corruptions.into_iter().enumerate() {
+        sp_io::TestExternalities::new(storage.clone()).execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(RuntimeConfigs::do_try_state(1));
+
+            corrupt();
+            assert!(
+                RuntimeConfigs::do_try_state(1).is_err(),
+                "Corruption {index} was not detected"
+            );
+        });
+    }
+}
+
+#[test]
+fn migrate_to_v1() {
+    let storage = RuntimeGenesisConfig::default().build_storage().unwrap();
+    sp_io::TestExternalities::new(storage).execute_with(|| {
+        assert_eq!(RuntimeConfigs::on_chain_storage_version(), 1);
+
+        // Storage of v0 didn't have this item
+        StorageVersion::new(0).put::<RuntimeConfigs>();
+        TransferAllowlistSize::<Test>::kill();
+        EnableBalanceTransfers::<Test>::put(true);
+
+        crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(RuntimeConfigs::on_chain_storage_version(), 1);
+        assert!(TransferAllowlistSize::<Test>::exists());
+        // Existing values are kept
+        assert!(RuntimeConfigs::enable_balance_transfers());
+
+        // Running the migration again is a no-op
+        TransferAllowlistSize::<Test>::put(1);
+        crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+        assert_eq!(TransferAllowlistSize::<Test>::get(), 1);
+    });
+}
@@ -6,6 +6,9 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `nazar-pc`, CPU: `13th Gen Intel(R) Core(TM) i9-13900K`
 //! EXECUTION: , WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! NOTE: only the `set_enable_*` weights come from the run above, the weights of the calls added
+//! since then are estimated from their storage accesses until the command below is re-run.
 
 // Executed Command:
 // target/release/subspace-node
@@ -33,93 +36,425 @@ pub trait WeightInfo {
 	fn set_enable_dynamic_cost_of_storage() -> Weight;
 	fn set_enable_balance_transfers() -> Weight;
 	fn set_enable_non_root_calls() -> Weight;
+	fn set_feature_flags() -> Weight;
+	fn set_confirmation_depth_k() -> Weight;
+	fn schedule_config_change() -> Weight;
+	fn cancel_scheduled_config_change() -> Weight;
+	fn set_configs(n: u32, ) -> Weight;
+	fn add_to_transfer_allowlist() -> Weight;
+	fn remove_from_transfer_allowlist() -> Weight;
+	fn disable_call() -> Weight;
+	fn enable_call() -> Weight;
+	fn set_cost_of_storage_parameters() -> Weight;
+	fn set_enable_non_root_calls_at() -> Weight;
+	fn set_maintenance_mode() -> Weight;
+	fn freeze_domain() -> Weight;
+	fn unfreeze_domain() -> Weight;
 }
 
 /// Weights for pallet_runtime_configs using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	/// Storage: `RuntimeConfigs::EnableBalanceTransfers` (r:0 w:1)
-	/// Proof: `RuntimeConfigs::EnableBalanceTransfers` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableDomains` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableDomains` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
 	fn set_enable_domains() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
-		//  Estimated: `0`
+		//  Estimated: `1486`
 		// Minimum execution time: 5_640_000 picoseconds.
-		Weight::from_parts(5_782_000, 0)
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+		Weight::from_parts(5_782_000, 1486)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
-	/// Storage: `RuntimeConfigs::EnableDynamicCostOfStorage` (r:0 w:1)
+	/// Storage: `RuntimeConfigs::EnableDynamicCostOfStorage` (r:1 w:1)
 	/// Proof: `RuntimeConfigs::EnableDynamicCostOfStorage` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
 	fn set_enable_dynamic_cost_of_storage() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
-		//  Estimated: `0`
+		//  Estimated: `1486`
 		// Minimum execution time: 5_726_000 picoseconds.
-		Weight::from_parts(5_890_000, 0)
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+		Weight::from_parts(5_890_000, 1486)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
-	/// Storage: `RuntimeConfigs::EnableBalanceTransfers` (r:0 w:1)
+	/// Storage: `RuntimeConfigs::EnableBalanceTransfers` (r:1 w:1)
 	/// Proof: `RuntimeConfigs::EnableBalanceTransfers` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
 	fn set_enable_balance_transfers() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
-		//  Estimated: `0`
+		//  Estimated: `1486`
 		// Minimum execution time: 5_726_000 picoseconds.
-		Weight::from_parts(5_890_000, 0)
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+		Weight::from_parts(5_890_000, 1486)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
-	/// Storage: `RuntimeConfigs::EnableNonRootCalls` (r:0 w:1)
+	/// Storage: `RuntimeConfigs::EnableNonRootCalls` (r:1 w:1)
 	/// Proof: `RuntimeConfigs::EnableNonRootCalls` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
 	fn set_enable_non_root_calls() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
-		//  Estimated: `0`
+		//  Estimated: `1486`
 		// Minimum execution time: 5_726_000 picoseconds.
-		Weight::from_parts(5_890_000, 0)
+		Weight::from_parts(5_890_000, 1486)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::EnableDomains` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableDomains` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableDynamicCostOfStorage` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableDynamicCostOfStorage` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableBalanceTransfers` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableBalanceTransfers` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableNonRootCalls` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableNonRootCalls` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:4)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn set_feature_flags() -> Weight {
+		Weight::from_parts(8_412_000, 1486)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: `RuntimeConfigs::ConfirmationDepthK` (r:1 w:0)
+	/// Proof: `RuntimeConfigs::ConfirmationDepthK` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::PendingConfirmationDepthK` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::PendingConfirmationDepthK` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn set_confirmation_depth_k() -> Weight {
+		Weight::from_parts(7_541_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::ScheduledConfigChanges` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::ScheduledConfigChanges` (`max_values`: None, `max_size`: Some(107), added: 2582, mode: `MaxEncodedLen`)
+	fn schedule_config_change() -> Weight {
+		Weight::from_parts(9_781_000, 3572)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `RuntimeConfigs::ScheduledConfigChanges` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::ScheduledConfigChanges` (`max_values`: None, `max_size`: Some(107), added: 2582, mode: `MaxEncodedLen`)
+	fn cancel_scheduled_config_change() -> Weight {
+		Weight::from_parts(11_398_000, 3572)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `RuntimeConfigs::ConfirmationDepthK` (r:1 w:0)
+	/// Proof: `RuntimeConfigs::ConfirmationDepthK` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::PendingConfirmationDepthK` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::PendingConfirmationDepthK` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableDomains` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableDomains` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableDynamicCostOfStorage` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableDynamicCostOfStorage` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableBalanceTransfers` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableBalanceTransfers` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableNonRootCalls` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableNonRootCalls` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 5]`.
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:5)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn set_configs(n: u32, ) -> Weight {
+		Weight::from_parts(3_562_114, 1489)
+			// Standard Error: 2_317
+			.saturating_add(Weight::from_parts(1_687_402, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `RuntimeConfigs::TransferAllowlist` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::TransferAllowlist` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::TransferAllowlistSize` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::TransferAllowlistSize` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn add_to_transfer_allowlist() -> Weight {
+		Weight::from_parts(11_203_000, 3513)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `RuntimeConfigs::TransferAllowlist` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::TransferAllowlist` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::TransferAllowlistSize` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::TransferAllowlistSize` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn remove_from_transfer_allowlist() -> Weight {
+		Weight::from_parts(12_017_000, 3513)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `RuntimeConfigs::DisabledCalls` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::DisabledCalls` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn disable_call() -> Weight {
+		Weight::from_parts(9_340_000, 3513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::DisabledCalls` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::DisabledCalls` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn enable_call() -> Weight {
+		Weight::from_parts(10_452_000, 3513)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::CostOfStorageParams` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::CostOfStorageParams` (`max_values`: Some(1), `max_size`: Some(34), added: 529, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn set_cost_of_storage_parameters() -> Weight {
+		Weight::from_parts(7_468_000, 1519)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::EnableNonRootCallsAt` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::EnableNonRootCallsAt` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn set_enable_non_root_calls_at() -> Weight {
+		Weight::from_parts(6_237_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::EnableChainMaintenanceMode` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableChainMaintenanceMode` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn set_maintenance_mode() -> Weight {
+		Weight::from_parts(5_894_000, 1486)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::FrozenDomains` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::FrozenDomains` (`max_values`: Some(1), `max_size`: Some(402), added: 897, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn freeze_domain() -> Weight {
+		Weight::from_parts(11_032_000, 1887)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::FrozenDomains` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::FrozenDomains` (`max_values`: Some(1), `max_size`: Some(402), added: 897, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn unfreeze_domain() -> Weight {
+		Weight::from_parts(10_701_000, 1887)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	/// Storage: `RuntimeConfigs::EnableBalanceTransfers` (r:0 w:1)
-	/// Proof: `RuntimeConfigs::EnableBalanceTransfers` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableDomains` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableDomains` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
 	fn set_enable_domains() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
-		//  Estimated: `0`
+		//  Estimated: `1486`
 		// Minimum execution time: 5_640_000 picoseconds.
-		Weight::from_parts(5_782_000, 0)
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+		Weight::from_parts(5_782_000, 1486)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
-	/// Storage: `RuntimeConfigs::EnableDynamicCostOfStorage` (r:0 w:1)
+	/// Storage: `RuntimeConfigs::EnableDynamicCostOfStorage` (r:1 w:1)
 	/// Proof: `RuntimeConfigs::EnableDynamicCostOfStorage` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
 	fn set_enable_dynamic_cost_of_storage() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
-		//  Estimated: `0`
+		//  Estimated: `1486`
 		// Minimum execution time: 5_726_000 picoseconds.
-		Weight::from_parts(5_890_000, 0)
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+		Weight::from_parts(5_890_000, 1486)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
-	/// Storage: `RuntimeConfigs::EnableBalanceTransfers` (r:0 w:1)
+	/// Storage: `RuntimeConfigs::EnableBalanceTransfers` (r:1 w:1)
 	/// Proof: `RuntimeConfigs::EnableBalanceTransfers` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
 	fn set_enable_balance_transfers() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
-		//  Estimated: `0`
+		//  Estimated: `1486`
 		// Minimum execution time: 5_726_000 picoseconds.
-		Weight::from_parts(5_890_000, 0)
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+		Weight::from_parts(5_890_000, 1486)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
-	/// Storage: `RuntimeConfigs::EnableNonRootCalls` (r:0 w:1)
+	/// Storage: `RuntimeConfigs::EnableNonRootCalls` (r:1 w:1)
 	/// Proof: `RuntimeConfigs::EnableNonRootCalls` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
 	fn set_enable_non_root_calls() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
-		//  Estimated: `0`
+		//  Estimated: `1486`
 		// Minimum execution time: 5_726_000 picoseconds.
-		Weight::from_parts(5_890_000, 0)
+		Weight::from_parts(5_890_000, 1486)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::EnableDomains` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableDomains` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableDynamicCostOfStorage` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableDynamicCostOfStorage` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableBalanceTransfers` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableBalanceTransfers` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableNonRootCalls` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableNonRootCalls` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:4)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn set_feature_flags() -> Weight {
+		Weight::from_parts(8_412_000, 1486)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: `RuntimeConfigs::ConfirmationDepthK` (r:1 w:0)
+	/// Proof: `RuntimeConfigs::ConfirmationDepthK` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::PendingConfirmationDepthK` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::PendingConfirmationDepthK` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn set_confirmation_depth_k() -> Weight {
+		Weight::from_parts(7_541_000, 1489)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::ScheduledConfigChanges` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::ScheduledConfigChanges` (`max_values`: None, `max_size`: Some(107), added: 2582, mode: `MaxEncodedLen`)
+	fn schedule_config_change() -> Weight {
+		Weight::from_parts(9_781_000, 3572)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `RuntimeConfigs::ScheduledConfigChanges` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::ScheduledConfigChanges` (`max_values`: None, `max_size`: Some(107), added: 2582, mode: `MaxEncodedLen`)
+	fn cancel_scheduled_config_change() -> Weight {
+		Weight::from_parts(11_398_000, 3572)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `RuntimeConfigs::ConfirmationDepthK` (r:1 w:0)
+	/// Proof: `RuntimeConfigs::ConfirmationDepthK` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::PendingConfirmationDepthK` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::PendingConfirmationDepthK` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableDomains` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableDomains` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableDynamicCostOfStorage` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableDynamicCostOfStorage` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableBalanceTransfers` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableBalanceTransfers` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::EnableNonRootCalls` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableNonRootCalls` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 5]`.
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:5)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn set_configs(n: u32, ) -> Weight {
+		Weight::from_parts(3_562_114, 1489)
+			// Standard Error: 2_317
+			.saturating_add(Weight::from_parts(1_687_402, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `RuntimeConfigs::TransferAllowlist` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::TransferAllowlist` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::TransferAllowlistSize` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::TransferAllowlistSize` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn add_to_transfer_allowlist() -> Weight {
+		Weight::from_parts(11_203_000, 3513)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `RuntimeConfigs::TransferAllowlist` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::TransferAllowlist` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::TransferAllowlistSize` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::TransferAllowlistSize` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn remove_from_transfer_allowlist() -> Weight {
+		Weight::from_parts(12_017_000, 3513)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `RuntimeConfigs::DisabledCalls` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::DisabledCalls` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn disable_call() -> Weight {
+		Weight::from_parts(9_340_000, 3513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::DisabledCalls` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::DisabledCalls` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn enable_call() -> Weight {
+		Weight::from_parts(10_452_000, 3513)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::CostOfStorageParams` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::CostOfStorageParams` (`max_values`: Some(1), `max_size`: Some(34), added: 529, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn set_cost_of_storage_parameters() -> Weight {
+		Weight::from_parts(7_468_000, 1519)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::EnableNonRootCallsAt` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::EnableNonRootCallsAt` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn set_enable_non_root_calls_at() -> Weight {
+		Weight::from_parts(6_237_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::EnableChainMaintenanceMode` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::EnableChainMaintenanceMode` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn set_maintenance_mode() -> Weight {
+		Weight::from_parts(5_894_000, 1486)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::FrozenDomains` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::FrozenDomains` (`max_values`: Some(1), `max_size`: Some(402), added: 897, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn freeze_domain() -> Weight {
+		Weight::from_parts(11_032_000, 1887)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `RuntimeConfigs::FrozenDomains` (r:1 w:1)
+	/// Proof: `RuntimeConfigs::FrozenDomains` (`max_values`: Some(1), `max_size`: Some(402), added: 897, mode: `MaxEncodedLen`)
+	/// Storage: `RuntimeConfigs::ConfigChangeLog` (r:0 w:1)
+	/// Proof: `RuntimeConfigs::ConfigChangeLog` (`max_values`: None, `max_size`: Some(54), added: 2529, mode: `MaxEncodedLen`)
+	fn unfreeze_domain() -> Weight {
+		Weight::from_parts(10_701_000, 1887)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
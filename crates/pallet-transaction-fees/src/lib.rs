@@ -29,6 +29,7 @@ use frame_support::weights::Weight;
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
 use scale_info::TypeInfo;
+use sp_runtime_configs::RuntimeConfigsProvider;
 use subspace_runtime_primitives::FindBlockRewardAddress;
 
 type BalanceOf<T> =
@@ -97,8 +98,8 @@ mod pallet {
 
         type FindBlockRewardAddress: FindBlockRewardAddress<Self::AccountId>;
 
-        /// Whether dynamic cost of storage should be used
-        type DynamicCostOfStorage: Get<bool>;
+        /// Runtime configs, used to check whether dynamic cost of storage should be used
+        type RuntimeConfigs: RuntimeConfigsProvider<BlockNumberFor<Self>>;
 
         type WeightInfo: WeightInfo;
     }
@@ -243,7 +244,7 @@ where
     /// return the next `transaction_byte_fee` value for validating extrinsic to be
     /// included in the next block
     pub fn transaction_byte_fee() -> BalanceOf<T> {
-        if !T::DynamicCostOfStorage::get() {
+        if !T::RuntimeConfigs::dynamic_cost_of_storage() {
             return BalanceOf::<T>::from(1);
         }
 
@@ -0,0 +1,100 @@
+// Copyright (C) 2023 Subspace Labs, Inc.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC api for the runtime configs.
+
+#![warn(missing_docs)]
+
+use jsonrpsee::core::{Error as JsonRpseeError, RpcResult};
+use jsonrpsee::proc_macros::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use sp_runtime::AccountId32;
+use sp_runtime_configs::{ConfigKey, RuntimeConfigs, RuntimeConfigsApi};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use subspace_core_primitives::BlockNumber;
+use tracing::error;
+
+/// Provides rpc methods for reading the runtime configs.
+#[rpc(client, server)]
+pub trait RuntimeConfigsRpcApi<BlockHash> {
+    /// Returns the runtime configs at the given block, or at the best block if not specified.
+    #[method(name = "runtimeConfigs_get")]
+    fn get(&self, at: Option<BlockHash>) -> RpcResult<RuntimeConfigs<BlockNumber>>;
+
+    /// Returns the block number and the account, `None` unless the origin was signed, of the last
+    /// change to each config at the given block, or at the best block if not specified.
+    #[method(name = "runtimeConfigs_changeLog")]
+    fn change_log(
+        &self,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(ConfigKey, BlockNumber, Option<AccountId32>)>>;
+}
+
+/// Implements the [`RuntimeConfigsRpcApiServer`] trait for reading the runtime configs.
+pub struct RuntimeConfigsRpc<Block, Client> {
+    client: Arc<Client>,
+    _block: PhantomData<Block>,
+}
+
+impl<Block, Client> RuntimeConfigsRpc<Block, Client> {
+    /// Creates a new instance of the `RuntimeConfigsRpc` handler.
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            _block: PhantomData,
+        }
+    }
+}
+
+impl<Block, Client> RuntimeConfigsRpcApiServer<Block::Hash> for RuntimeConfigsRpc<Block, Client>
+where
+    Block: BlockT,
+    Client: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    Client::Api: RuntimeConfigsApi<Block>,
+{
+    fn get(&self, at: Option<Block::Hash>) -> RpcResult<RuntimeConfigs<BlockNumber>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .runtime_configs(at)
+            .map_err(|error| {
+                error!("Failed to get runtime configs from runtime API: {}", error);
+                JsonRpseeError::Custom("Internal error".to_string())
+            })
+    }
+
+    fn change_log(
+        &self,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<(ConfigKey, BlockNumber, Option<AccountId32>)>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .config_change_log(at)
+            .map_err(|error| {
+                error!(
+                    "Failed to get config change log from runtime API: {}",
+                    error
+                );
+                JsonRpseeError::Custom("Internal error".to_string())
+            })
+    }
+}
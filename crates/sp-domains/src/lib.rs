@@ -655,6 +655,23 @@ pub struct GenesisDomain<AccountId: Ord> {
 pub enum RuntimeType {
     #[default]
     Evm,
+    AutoId,
+}
+
+/// Metadata of a registered domain runtime, without its (large) raw genesis storage.
+#[derive(TypeInfo, Debug, Encode, Decode, Clone, PartialEq, Eq)]
+pub struct RuntimeObjectInfo<Number, Hash> {
+    pub runtime_name: String,
+    pub runtime_type: RuntimeType,
+    pub version: RuntimeVersion,
+    /// Hash of the runtime code.
+    pub hash: Hash,
+    /// Number of upgrades applied to the runtime since its registration.
+    pub runtime_upgrades: u32,
+    pub created_at: Number,
+    pub updated_at: Number,
+    /// The size of the runtime code, in bytes.
+    pub code_size: u32,
 }
 
 /// Type representing the runtime ID.
@@ -689,6 +706,12 @@ pub enum DomainsHoldIdentifier {
 pub enum DomainDigestItem {
     DomainRuntimeUpgraded(RuntimeId),
     DomainInstantiated(DomainId),
+    /// An upgrade of the domain runtime to the given spec version was scheduled, it is applied
+    /// after the domain runtime upgrade delay.
+    DomainRuntimeUpgradeScheduled {
+        runtime_id: RuntimeId,
+        new_spec_version: u32,
+    },
 }
 
 /// Domains specific digest items.
@@ -698,6 +721,9 @@ pub trait DomainsDigestItem {
 
     fn domain_instantiation(domain_id: DomainId) -> Self;
     fn as_domain_instantiation(&self) -> Option<DomainId>;
+
+    fn domain_runtime_upgrade_scheduled(runtime_id: RuntimeId, new_spec_version: u32) -> Self;
+    fn as_domain_runtime_upgrade_scheduled(&self) -> Option<(RuntimeId, u32)>;
 }
 
 impl DomainsDigestItem for DigestItem {
@@ -722,6 +748,26 @@ impl DomainsDigestItem for DigestItem {
             _ => None,
         }
     }
+
+    fn domain_runtime_upgrade_scheduled(runtime_id: RuntimeId, new_spec_version: u32) -> Self {
+        Self::Other(
+            DomainDigestItem::DomainRuntimeUpgradeScheduled {
+                runtime_id,
+                new_spec_version,
+            }
+            .encode(),
+        )
+    }
+
+    fn as_domain_runtime_upgrade_scheduled(&self) -> Option<(RuntimeId, u32)> {
+        match self.try_to::<DomainDigestItem>(OpaqueDigestItemId::Other) {
+            Some(DomainDigestItem::DomainRuntimeUpgradeScheduled {
+                runtime_id,
+                new_spec_version,
+            }) => Some((runtime_id, new_spec_version)),
+            _ => None,
+        }
+    }
 }
 
 /// EVM chain Id storage key.
@@ -951,6 +997,7 @@ pub type ExecutionReceiptFor<DomainHeader, CBlock, Balance> = ExecutionReceipt<
 
 sp_api::decl_runtime_apis! {
     /// API necessary for domains pallet.
+    #[api_version(2)]
     pub trait DomainsApi<DomainHeader: HeaderT> {
         /// Submits the transaction bundle via an unsigned extrinsic.
         fn submit_bundle_unsigned(opaque_bundle: OpaqueBundle<NumberFor<Block>, Block::Hash, DomainHeader, Balance>);
@@ -1030,6 +1077,25 @@ sp_api::decl_runtime_apis! {
         /// Reture the consensus chain byte fee that will used to charge the domain transaction for consensus
         /// chain storage fee
         fn consensus_chain_byte_fee() -> Balance;
+
+        /// Returns the on-chain storage version of the domains pallet, tools should check it
+        /// before decoding the `RuntimeObject`s of the runtime registry
+        #[api_version(2)]
+        fn runtime_registry_storage_version() -> u16;
+
+        /// Returns the block number, runtime id and target version of all the pending domain
+        /// runtime upgrades, ordered by block number.
+        #[api_version(2)]
+        fn scheduled_runtime_upgrades() -> Vec<(NumberFor<Block>, RuntimeId, RuntimeVersion)>;
+
+        /// Returns the metadata of the given domain runtime, without its raw genesis storage.
+        #[api_version(2)]
+        fn domain_runtime_object(runtime_id: RuntimeId) -> Option<RuntimeObjectInfo<NumberFor<Block>, Block::Hash>>;
+
+        /// Returns the block number, target version and code hash of the pending upgrade of the
+        /// given domain runtime, if any.
+        #[api_version(2)]
+        fn pending_runtime_upgrade(runtime_id: RuntimeId) -> Option<(NumberFor<Block>, RuntimeVersion, Block::Hash)>;
     }
 
     pub trait BundleProducerElectionApi<Balance: Encode + Decode> {
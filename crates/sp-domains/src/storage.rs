@@ -57,7 +57,7 @@ impl RawGenesis {
             .insert(evm_chain_id_storage_key(), StorageData(chain_id.encode()));
     }
 
-    fn set_runtime_code(&mut self, code: Vec<u8>) {
+    pub fn set_runtime_code(&mut self, code: Vec<u8>) {
         let _ = self.top.insert(
             StorageKey(well_known_keys::CODE.to_vec()),
             StorageData(code),
@@ -0,0 +1,184 @@
+// Copyright (C) 2023 Subspace Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Primitives for runtime configs.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::AccountId32;
+use subspace_core_primitives::BlockNumber;
+
+/// Current values of the runtime configs.
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeConfigs<BlockNumber> {
+    /// Whether calls in pallet-domains are enabled.
+    pub enable_domains: bool,
+    /// Whether dynamic cost of storage is enabled.
+    pub enable_dynamic_cost_of_storage: bool,
+    /// Whether balance transfers are enabled.
+    pub enable_balance_transfers: bool,
+    /// Whether calls from non-root accounts are enabled.
+    pub enable_non_root_calls: bool,
+    /// Confirmation depth k used in the archiving process.
+    pub confirmation_depth_k: BlockNumber,
+    /// Parameters of the dynamic cost of storage.
+    pub cost_of_storage_parameters: CostOfStorageParameters,
+}
+
+/// Parameters of the dynamic cost of storage.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    TypeInfo,
+    MaxEncodedLen,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct CostOfStorageParameters {
+    /// Reference number of replicas of the blockchain history to be stored by the network, the
+    /// transaction byte fee grows as the space left for that many replicas shrinks.
+    pub replication_factor: u16,
+    /// Lower bound of the transaction byte fee, as a multiple of the byte fee used when dynamic
+    /// cost of storage is disabled.
+    pub min_multiplier: u128,
+    /// Upper bound of the transaction byte fee, as a multiple of the byte fee used when dynamic
+    /// cost of storage is disabled.
+    pub max_multiplier: u128,
+}
+
+impl CostOfStorageParameters {
+    /// Clamps `transaction_byte_fee` to the bounds given by the multipliers of
+    /// `base_transaction_byte_fee`.
+    pub fn clamp_transaction_byte_fee(
+        &self,
+        transaction_byte_fee: u128,
+        base_transaction_byte_fee: u128,
+    ) -> u128 {
+        transaction_byte_fee.clamp(
+            base_transaction_byte_fee.saturating_mul(self.min_multiplier),
+            base_transaction_byte_fee.saturating_mul(self.max_multiplier),
+        )
+    }
+}
+
+/// Configs whose last change is recorded, see [`RuntimeConfigsApi::config_change_log`].
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Encode,
+    Decode,
+    TypeInfo,
+    MaxEncodedLen,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigKey {
+    /// Whether calls in pallet-domains are enabled.
+    EnableDomains,
+    /// Whether dynamic cost of storage is enabled.
+    EnableDynamicCostOfStorage,
+    /// Whether balance transfers are enabled.
+    EnableBalanceTransfers,
+    /// Whether calls from non-root accounts are enabled.
+    EnableNonRootCalls,
+    /// Block number at which calls from non-root accounts are enabled automatically.
+    EnableNonRootCallsAt,
+    /// Confirmation depth k used in the archiving process.
+    ConfirmationDepthK,
+    /// Parameters of the dynamic cost of storage.
+    CostOfStorageParameters,
+    /// Accounts allowed to transfer balances while transfers are disabled.
+    TransferAllowlist,
+    /// Calls disabled by the call filter.
+    DisabledCalls,
+    /// Whether the chain is in maintenance mode.
+    ChainMaintenanceMode,
+    /// Domains whose bundles are rejected.
+    FrozenDomains,
+}
+
+/// Read access to the runtime configs, so that pallets can depend on the configs without depending
+/// on the pallet that stores them.
+pub trait RuntimeConfigsProvider<BlockNumber> {
+    /// Whether dynamic cost of storage is enabled.
+    fn dynamic_cost_of_storage() -> bool;
+    /// Whether balance transfers are enabled for all accounts.
+    fn transfers_enabled() -> bool;
+    /// Confirmation depth k used in the archiving process.
+    fn confirmation_depth_k() -> BlockNumber;
+}
+
+/// [`RuntimeConfigsProvider`] with constant configs, meant for tests and mock runtimes.
+pub struct ConstRuntimeConfigs<
+    const DYNAMIC_COST_OF_STORAGE: bool,
+    const TRANSFERS_ENABLED: bool,
+    const CONFIRMATION_DEPTH_K: u32,
+>;
+
+impl<
+        BlockNumber,
+        const DYNAMIC_COST_OF_STORAGE: bool,
+        const TRANSFERS_ENABLED: bool,
+        const CONFIRMATION_DEPTH_K: u32,
+    > RuntimeConfigsProvider<BlockNumber>
+    for ConstRuntimeConfigs<DYNAMIC_COST_OF_STORAGE, TRANSFERS_ENABLED, CONFIRMATION_DEPTH_K>
+where
+    BlockNumber: From<u32>,
+{
+    fn dynamic_cost_of_storage() -> bool {
+        DYNAMIC_COST_OF_STORAGE
+    }
+
+    fn transfers_enabled() -> bool {
+        TRANSFERS_ENABLED
+    }
+
+    fn confirmation_depth_k() -> BlockNumber {
+        BlockNumber::from(CONFIRMATION_DEPTH_K)
+    }
+}
+
+sp_api::decl_runtime_apis! {
+    pub trait RuntimeConfigsApi {
+        /// Returns the current values of the runtime configs.
+        fn runtime_configs() -> RuntimeConfigs<BlockNumber>;
+
+        /// Returns the block number and the account, `None` unless the origin was signed, of the
+        /// last change to each config that was changed at least once.
+        fn config_change_log() -> Vec<(ConfigKey, BlockNumber, Option<AccountId32>)>;
+    }
+}
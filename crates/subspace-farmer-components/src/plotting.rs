@@ -160,6 +160,21 @@ pub enum PlottingError {
     },
 }
 
+impl PlottingError {
+    /// String variant of the error, primarily for monitoring purposes
+    pub fn str_variant(&self) -> &str {
+        match self {
+            PlottingError::InvalidErasureCodingInstance => "InvalidErasureCodingInstance",
+            PlottingError::BadSectorOutputSize { .. } => "BadSectorOutputSize",
+            PlottingError::BadSectorMetadataOutputSize { .. } => "BadSectorMetadataOutputSize",
+            PlottingError::PieceNotFound { .. } => "PieceNotFound",
+            PlottingError::PieceRecoveryFailed { .. } => "PieceRecoveryFailed",
+            PlottingError::FailedToRetrievePiece { .. } => "FailedToRetrievePiece",
+            PlottingError::FailedToAcquirePermit { .. } => "FailedToAcquirePermit",
+        }
+    }
+}
+
 /// Options for plotting a sector.
 ///
 /// Sector output and sector metadata output should be either empty (in which case they'll be
@@ -2,7 +2,7 @@ mod dsn;
 mod metrics;
 
 use crate::commands::farm::dsn::configure_dsn;
-use crate::commands::farm::metrics::FarmerMetrics;
+use crate::commands::farm::metrics::{FarmState, FarmerMetrics, FarmerMetricsConfig};
 use crate::utils::shutdown_signal;
 use anyhow::anyhow;
 use bytesize::ByteSize;
@@ -13,6 +13,7 @@ use futures::{FutureExt, StreamExt};
 use lru::LruCache;
 use parking_lot::Mutex;
 use prometheus_client::registry::Registry;
+use std::collections::HashMap;
 use std::fs;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::num::{NonZeroU8, NonZeroUsize};
@@ -20,13 +21,15 @@ use std::path::PathBuf;
 use std::pin::pin;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Instant;
 use subspace_core_primitives::crypto::kzg::{embedded_kzg_settings, Kzg};
 use subspace_core_primitives::{PublicKey, Record, SectorIndex};
 use subspace_erasure_coding::ErasureCoding;
 use subspace_farmer::piece_cache::PieceCache;
 use subspace_farmer::single_disk_farm::farming::FarmingNotification;
 use subspace_farmer::single_disk_farm::{
-    SectorPlottingDetails, SectorUpdate, SingleDiskFarm, SingleDiskFarmError, SingleDiskFarmOptions,
+    SectorExpirationDetails, SectorPlottingDetails, SectorUpdate, SingleDiskFarm,
+    SingleDiskFarmError, SingleDiskFarmOptions,
 };
 use subspace_farmer::utils::farmer_piece_getter::FarmerPieceGetter;
 use subspace_farmer::utils::piece_validator::SegmentCommitmentPieceValidator;
@@ -39,6 +42,7 @@ use subspace_farmer::utils::{
 };
 use subspace_farmer::{Identity, NodeClient, NodeRpcClient};
 use subspace_farmer_components::plotting::PlottedSector;
+use subspace_farmer_components::sector::{sector_size, SectorMetadataChecksummed};
 use subspace_metrics::{start_prometheus_metrics_server, RegistryAdapter};
 use subspace_networking::libp2p::identity::{ed25519, Keypair};
 use subspace_networking::libp2p::multiaddr::Protocol;
@@ -385,7 +389,10 @@ where
 
     // Metrics
     let mut prometheus_metrics_registry = Registry::default();
-    let farmer_metrics = FarmerMetrics::new(&mut prometheus_metrics_registry);
+    let farmer_metrics = FarmerMetrics::new(
+        &mut prometheus_metrics_registry,
+        FarmerMetricsConfig::default(),
+    );
     let metrics_endpoints_are_specified = !metrics_endpoints.is_empty();
 
     let (node, mut node_runner) = {
@@ -668,6 +675,12 @@ where
                         }
                     },
                 );
+
+            farmer_metrics.set_plotted_sectors(
+                single_disk_farm.id(),
+                single_disk_farm.plotted_sectors_count().await,
+            );
+            farmer_metrics.set_farm_state(single_disk_farm.id(), FarmState::Farming);
         }
 
         readers_and_pieces.lock().replace(future_readers_and_pieces);
@@ -708,32 +721,51 @@ where
                 .on_sector_update(Arc::new({
                     let single_disk_farm_id = *single_disk_farm.id();
                     let farmer_metrics = farmer_metrics.clone();
+                    // Downloading of the next sector starts before the current one is written, so
+                    // start time is tracked for every sector in the pipeline
+                    let sector_pipeline_starts = Mutex::new(HashMap::new());
 
-                    move |(_sector_index, sector_state)| match sector_state {
+                    move |(sector_index, sector_state)| match sector_state {
                         SectorUpdate::Plotting(SectorPlottingDetails::Starting { .. }) => {
-                            farmer_metrics.sector_plotting.inc();
+                            farmer_metrics
+                                .set_farm_state(&single_disk_farm_id, FarmState::Plotting);
+                            farmer_metrics.sector.sector_plotting.inc();
                         }
                         SectorUpdate::Plotting(SectorPlottingDetails::Downloading) => {
-                            farmer_metrics.sector_downloading.inc();
+                            sector_pipeline_starts
+                                .lock()
+                                .insert(*sector_index, Instant::now());
+                            farmer_metrics.sector.sector_downloading.inc();
                         }
                         SectorUpdate::Plotting(SectorPlottingDetails::Downloaded(time)) => {
                             farmer_metrics
                                 .observe_sector_downloading_time(&single_disk_farm_id, time);
-                            farmer_metrics.sector_downloaded.inc();
+                            farmer_metrics.sector.sector_downloaded.inc();
                         }
                         SectorUpdate::Plotting(SectorPlottingDetails::Encoding) => {
-                            farmer_metrics.sector_encoding.inc();
+                            farmer_metrics.sector.sector_encoding.inc();
                         }
                         SectorUpdate::Plotting(SectorPlottingDetails::Encoded(time)) => {
                             farmer_metrics.observe_sector_encoding_time(&single_disk_farm_id, time);
-                            farmer_metrics.sector_encoded.inc();
+                            farmer_metrics.sector.sector_encoded.inc();
                         }
                         SectorUpdate::Plotting(SectorPlottingDetails::Writing) => {
-                            farmer_metrics.sector_writing.inc();
+                            farmer_metrics.sector.sector_writing.inc();
                         }
                         SectorUpdate::Plotting(SectorPlottingDetails::Written(time)) => {
+                            if let Some(start) = sector_pipeline_starts.lock().remove(sector_index)
+                            {
+                                farmer_metrics.observe_sector_pipeline_time(
+                                    &single_disk_farm_id,
+                                    &start.elapsed(),
+                                );
+                            }
                             farmer_metrics.observe_sector_writing_time(&single_disk_farm_id, time);
-                            farmer_metrics.sector_written.inc();
+                            farmer_metrics.sector.sector_written.inc();
+                        }
+                        SectorUpdate::Plotting(SectorPlottingDetails::MetadataWritten(time)) => {
+                            farmer_metrics
+                                .observe_sector_metadata_writing_time(&single_disk_farm_id, time);
                         }
                         SectorUpdate::Plotting(SectorPlottingDetails::Finished {
                             plotted_sector,
@@ -741,8 +773,39 @@ where
                             time,
                         }) => {
                             on_plotted_sector_callback(plotted_sector, old_plotted_sector);
-                            farmer_metrics.observe_sector_plotting_time(&single_disk_farm_id, time);
-                            farmer_metrics.sector_plotted.inc();
+                            // Replotted sector doesn't change the number of plotted sectors
+                            if old_plotted_sector.is_none() {
+                                farmer_metrics.inc_plotted_sectors(&single_disk_farm_id);
+                            }
+                            let sector_size =
+                                sector_size(plotted_sector.sector_metadata.pieces_in_sector);
+                            farmer_metrics.note_bytes_written(
+                                &single_disk_farm_id,
+                                (sector_size + SectorMetadataChecksummed::encoded_size()) as u64,
+                            );
+                            farmer_metrics.observe_sector_plotting_time(
+                                &single_disk_farm_id,
+                                time,
+                                sector_size,
+                            );
+                            farmer_metrics.sector.sector_plotted.inc();
+                            farmer_metrics.set_farm_state(&single_disk_farm_id, FarmState::Farming);
+                        }
+                        SectorUpdate::Plotting(SectorPlottingDetails::Error { stage, error }) => {
+                            sector_pipeline_starts.lock().remove(sector_index);
+                            farmer_metrics.note_sector_plotting_error(
+                                &single_disk_farm_id,
+                                *stage,
+                                error,
+                            );
+                            farmer_metrics
+                                .set_farm_state(&single_disk_farm_id, FarmState::PlottingError);
+                        }
+                        SectorUpdate::Expiration(SectorExpirationDetails::AboutToExpire) => {
+                            farmer_metrics.note_sector_about_to_expire(&single_disk_farm_id);
+                        }
+                        SectorUpdate::Expiration(SectorExpirationDetails::Expired) => {
+                            farmer_metrics.note_sector_expired(&single_disk_farm_id);
                         }
                         _ => {}
                     }
@@ -767,6 +830,10 @@ where
                                 &proving_details.time,
                                 proving_details.result,
                             );
+                            farmer_metrics.set_last_proving_result(
+                                &single_disk_farm_id,
+                                proving_details.result,
+                            );
                         }
                         FarmingNotification::NonFatalError(error) => {
                             farmer_metrics.note_farming_error(&single_disk_farm_id, error);
@@ -788,6 +855,7 @@ where
             while let Some(result) = single_disk_farms_stream.next().await {
                 let id = result?;
 
+                farmer_metrics.set_farm_state(&id, FarmState::Idle);
                 info!(%id, "Farm exited successfully");
             }
             anyhow::Ok(())
@@ -1,38 +1,111 @@
 use prometheus_client::metrics::counter::Counter;
 use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
 use prometheus_client::metrics::histogram::{exponential_buckets, Histogram};
 use prometheus_client::registry::{Registry, Unit};
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicI64, AtomicU64};
 use std::time::Duration;
 use subspace_farmer::single_disk_farm::farming::ProvingResult;
-use subspace_farmer::single_disk_farm::{FarmingError, SingleDiskFarmId};
+use subspace_farmer::single_disk_farm::{FarmingError, SectorPlottingStage, SingleDiskFarmId};
 
+/// State of a farm reported by the `farm_state` gauge.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(super) enum FarmState {
+    /// Farm is neither plotting nor farming
+    Idle = 0,
+    /// Farm is farming, but not plotting
+    Farming = 1,
+    /// Farm is plotting a sector
+    Plotting = 2,
+    /// Plotting of the last sector failed, already plotted sectors are still farmed
+    PlottingError = 3,
+}
+
+/// Value of the `last_proving_result` gauge for each proving result.
+fn proving_result_value(result: ProvingResult) -> i64 {
+    match result {
+        ProvingResult::Success => 0,
+        ProvingResult::Timeout => 1,
+        ProvingResult::Rejected => 2,
+    }
+}
+
+/// Buckets of a histogram, `count` buckets growing exponentially by `factor` from `start`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(super) struct HistogramBuckets {
+    pub(super) start: f64,
+    pub(super) factor: f64,
+    pub(super) count: u16,
+}
+
+impl Default for HistogramBuckets {
+    fn default() -> Self {
+        Self {
+            start: 0.0001,
+            factor: 2.0,
+            count: 15,
+        }
+    }
+}
+
+impl HistogramBuckets {
+    fn iter(&self) -> impl Iterator<Item = f64> {
+        exponential_buckets(self.start, self.factor, self.count)
+    }
+}
+
+/// Buckets of every histogram in [`FarmerMetrics`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(super) struct FarmerMetricsConfig {
+    pub(super) auditing_time: HistogramBuckets,
+    pub(super) proving_time: HistogramBuckets,
+    pub(super) sector_downloading_time: HistogramBuckets,
+    pub(super) sector_encoding_time: HistogramBuckets,
+    pub(super) sector_writing_time: HistogramBuckets,
+    pub(super) sector_metadata_writing_time: HistogramBuckets,
+    pub(super) sector_plotting_time: HistogramBuckets,
+    pub(super) sector_pipeline_time: HistogramBuckets,
+}
+
+impl Default for FarmerMetricsConfig {
+    fn default() -> Self {
+        Self {
+            auditing_time: HistogramBuckets::default(),
+            proving_time: HistogramBuckets::default(),
+            sector_downloading_time: HistogramBuckets::default(),
+            sector_encoding_time: HistogramBuckets::default(),
+            sector_writing_time: HistogramBuckets::default(),
+            sector_metadata_writing_time: HistogramBuckets::default(),
+            sector_plotting_time: HistogramBuckets::default(),
+            // Sector spends at least a few seconds in the pipeline and up to tens of minutes on
+            // slow hardware
+            sector_pipeline_time: HistogramBuckets {
+                start: 1.0,
+                factor: 2.0,
+                count: 12,
+            },
+        }
+    }
+}
+
+/// Metrics of auditing and proving.
 #[derive(Debug, Clone)]
-pub(super) struct FarmerMetrics {
+pub(super) struct FarmingMetrics {
     auditing_time: Family<Vec<(String, String)>, Histogram>,
+    auditing_total: Family<Vec<(String, String)>, Counter<u64, AtomicU64>>,
     proving_time: Family<Vec<(String, String)>, Histogram>,
+    proving_total: Family<Vec<(String, String)>, Counter<u64, AtomicU64>>,
+    proving_results: Family<Vec<(String, String)>, Counter<u64, AtomicU64>>,
+    last_proving_result: Family<Vec<(String, String)>, Gauge<i64, AtomicI64>>,
     farming_errors: Family<Vec<(String, String)>, Counter<u64, AtomicU64>>,
-    sector_downloading_time: Family<Vec<(String, String)>, Histogram>,
-    sector_encoding_time: Family<Vec<(String, String)>, Histogram>,
-    sector_writing_time: Family<Vec<(String, String)>, Histogram>,
-    sector_plotting_time: Family<Vec<(String, String)>, Histogram>,
-    pub(super) sector_downloading: Counter<u64, AtomicU64>,
-    pub(super) sector_downloaded: Counter<u64, AtomicU64>,
-    pub(super) sector_encoding: Counter<u64, AtomicU64>,
-    pub(super) sector_encoded: Counter<u64, AtomicU64>,
-    pub(super) sector_writing: Counter<u64, AtomicU64>,
-    pub(super) sector_written: Counter<u64, AtomicU64>,
-    pub(super) sector_plotting: Counter<u64, AtomicU64>,
-    pub(super) sector_plotted: Counter<u64, AtomicU64>,
 }
 
-impl FarmerMetrics {
-    pub(super) fn new(registry: &mut Registry) -> Self {
-        let sub_registry = registry.sub_registry_with_prefix("subspace_farmer");
-
-        let auditing_time = Family::<_, _>::new_with_constructor(|| {
-            Histogram::new(exponential_buckets(0.0001, 2.0, 15))
-        });
+impl FarmingMetrics {
+    fn new(sub_registry: &mut Registry, config: &FarmerMetricsConfig) -> Self {
+        let auditing_time = {
+            let buckets = config.auditing_time;
+            Family::<_, _>::new_with_constructor(move || Histogram::new(buckets.iter()))
+        };
 
         sub_registry.register_with_unit(
             "auditing_time",
@@ -41,9 +114,19 @@ impl FarmerMetrics {
             auditing_time.clone(),
         );
 
-        let proving_time = Family::<_, _>::new_with_constructor(|| {
-            Histogram::new(exponential_buckets(0.0001, 2.0, 15))
-        });
+        let auditing_total = Family::<_, _>::new_with_constructor(Counter::<_, _>::default);
+
+        sub_registry.register_with_unit(
+            "auditing",
+            "Number of audits",
+            Unit::Other("events".to_string()),
+            auditing_total.clone(),
+        );
+
+        let proving_time = {
+            let buckets = config.proving_time;
+            Family::<_, _>::new_with_constructor(move || Histogram::new(buckets.iter()))
+        };
 
         sub_registry.register_with_unit(
             "proving_time",
@@ -52,6 +135,31 @@ impl FarmerMetrics {
             proving_time.clone(),
         );
 
+        let proving_total = Family::<_, _>::new_with_constructor(Counter::<_, _>::default);
+
+        sub_registry.register_with_unit(
+            "proving",
+            "Number of proving attempts",
+            Unit::Other("events".to_string()),
+            proving_total.clone(),
+        );
+
+        let proving_results = Family::<_, _>::new_with_constructor(Counter::<_, _>::default);
+
+        sub_registry.register(
+            "proving_results",
+            "Number of proving attempts by result",
+            proving_results.clone(),
+        );
+
+        let last_proving_result = Family::<_, _>::new_with_constructor(Gauge::<_, _>::default);
+
+        sub_registry.register(
+            "last_proving_result",
+            "Result of the most recent proving (0 - success, 1 - timeout, 2 - rejected)",
+            last_proving_result.clone(),
+        );
+
         let farming_errors = Family::<_, _>::new_with_constructor(Counter::<_, _>::default);
 
         sub_registry.register(
@@ -60,9 +168,135 @@ impl FarmerMetrics {
             farming_errors.clone(),
         );
 
-        let sector_downloading_time = Family::<_, _>::new_with_constructor(|| {
-            Histogram::new(exponential_buckets(0.0001, 2.0, 15))
-        });
+        Self {
+            auditing_time,
+            auditing_total,
+            proving_time,
+            proving_total,
+            proving_results,
+            last_proving_result,
+            farming_errors,
+        }
+    }
+
+    pub(super) fn observe_auditing_time(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+    ) {
+        let labels = vec![("farm_id".to_string(), single_disk_farm_id.to_string())];
+        self.auditing_time
+            .get_or_create(&labels)
+            .observe(time.as_secs_f64());
+        self.auditing_total.get_or_create(&labels).inc();
+    }
+
+    pub(super) fn observe_proving_time(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+        result: ProvingResult,
+    ) {
+        let labels = vec![
+            ("farm_id".to_string(), single_disk_farm_id.to_string()),
+            ("result".to_string(), result.to_string()),
+        ];
+        self.proving_time
+            .get_or_create(&labels)
+            .observe(time.as_secs_f64());
+        self.proving_results.get_or_create(&labels).inc();
+        self.proving_total
+            .get_or_create(&vec![(
+                "farm_id".to_string(),
+                single_disk_farm_id.to_string(),
+            )])
+            .inc();
+    }
+
+    pub(super) fn set_last_proving_result(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        result: ProvingResult,
+    ) {
+        self.last_proving_result
+            .get_or_create(&vec![(
+                "farm_id".to_string(),
+                single_disk_farm_id.to_string(),
+            )])
+            .set(proving_result_value(result));
+    }
+
+    pub(super) fn note_farming_error(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        error: &FarmingError,
+    ) {
+        self.farming_errors
+            .get_or_create(&vec![
+                ("farm_id".to_string(), single_disk_farm_id.to_string()),
+                ("error".to_string(), error.str_variant().to_string()),
+            ])
+            .inc();
+    }
+}
+
+/// Metrics of plotting, expiration and the number of plotted sectors.
+#[derive(Debug, Clone)]
+pub(super) struct SectorMetrics {
+    sector_expired: Family<Vec<(String, String)>, Counter<u64, AtomicU64>>,
+    sector_about_to_expire: Family<Vec<(String, String)>, Counter<u64, AtomicU64>>,
+    sector_plotting_errors: Family<Vec<(String, String)>, Counter<u64, AtomicU64>>,
+    sector_downloading_time: Family<Vec<(String, String)>, Histogram>,
+    sector_encoding_time: Family<Vec<(String, String)>, Histogram>,
+    sector_writing_time: Family<Vec<(String, String)>, Histogram>,
+    sector_metadata_writing_time: Family<Vec<(String, String)>, Histogram>,
+    sector_plotting_time: Family<Vec<(String, String)>, Histogram>,
+    sector_pipeline_time: Family<Vec<(String, String)>, Histogram>,
+    sector_plotting_speed: Family<Vec<(String, String)>, Gauge<f64, AtomicU64>>,
+    plotted_sectors_current: Family<Vec<(String, String)>, Gauge<i64, AtomicI64>>,
+    bytes_written_total: Family<Vec<(String, String)>, Counter<u64, AtomicU64>>,
+    pub(super) sector_downloading: Counter<u64, AtomicU64>,
+    pub(super) sector_downloaded: Counter<u64, AtomicU64>,
+    pub(super) sector_encoding: Counter<u64, AtomicU64>,
+    pub(super) sector_encoded: Counter<u64, AtomicU64>,
+    pub(super) sector_writing: Counter<u64, AtomicU64>,
+    pub(super) sector_written: Counter<u64, AtomicU64>,
+    pub(super) sector_plotting: Counter<u64, AtomicU64>,
+    pub(super) sector_plotted: Counter<u64, AtomicU64>,
+}
+
+impl SectorMetrics {
+    fn new(sub_registry: &mut Registry, config: &FarmerMetricsConfig) -> Self {
+        let sector_expired = Family::<_, _>::new_with_constructor(Counter::<_, _>::default);
+
+        sub_registry.register_with_unit(
+            "sector_expired",
+            "Number of sectors that expired and need to be replotted",
+            Unit::Other("sectors".to_string()),
+            sector_expired.clone(),
+        );
+
+        let sector_about_to_expire = Family::<_, _>::new_with_constructor(Counter::<_, _>::default);
+
+        sub_registry.register_with_unit(
+            "sector_about_to_expire",
+            "Number of sectors that are about to expire and should be replotted",
+            Unit::Other("sectors".to_string()),
+            sector_about_to_expire.clone(),
+        );
+
+        let sector_plotting_errors = Family::<_, _>::new_with_constructor(Counter::<_, _>::default);
+
+        sub_registry.register(
+            "sector_plotting_errors",
+            "Sector plotting errors by stage",
+            sector_plotting_errors.clone(),
+        );
+
+        let sector_downloading_time = {
+            let buckets = config.sector_downloading_time;
+            Family::<_, _>::new_with_constructor(move || Histogram::new(buckets.iter()))
+        };
 
         sub_registry.register_with_unit(
             "sector_downloading_time",
@@ -71,9 +305,10 @@ impl FarmerMetrics {
             sector_downloading_time.clone(),
         );
 
-        let sector_encoding_time = Family::<_, _>::new_with_constructor(|| {
-            Histogram::new(exponential_buckets(0.0001, 2.0, 15))
-        });
+        let sector_encoding_time = {
+            let buckets = config.sector_encoding_time;
+            Family::<_, _>::new_with_constructor(move || Histogram::new(buckets.iter()))
+        };
 
         sub_registry.register_with_unit(
             "sector_encoding_time",
@@ -82,9 +317,10 @@ impl FarmerMetrics {
             sector_encoding_time.clone(),
         );
 
-        let sector_writing_time = Family::<_, _>::new_with_constructor(|| {
-            Histogram::new(exponential_buckets(0.0001, 2.0, 15))
-        });
+        let sector_writing_time = {
+            let buckets = config.sector_writing_time;
+            Family::<_, _>::new_with_constructor(move || Histogram::new(buckets.iter()))
+        };
 
         sub_registry.register_with_unit(
             "sector_writing_time",
@@ -93,9 +329,22 @@ impl FarmerMetrics {
             sector_writing_time.clone(),
         );
 
-        let sector_plotting_time = Family::<_, _>::new_with_constructor(|| {
-            Histogram::new(exponential_buckets(0.0001, 2.0, 15))
-        });
+        let sector_metadata_writing_time = {
+            let buckets = config.sector_metadata_writing_time;
+            Family::<_, _>::new_with_constructor(move || Histogram::new(buckets.iter()))
+        };
+
+        sub_registry.register_with_unit(
+            "sector_metadata_writing_time",
+            "Sector metadata writing time",
+            Unit::Seconds,
+            sector_metadata_writing_time.clone(),
+        );
+
+        let sector_plotting_time = {
+            let buckets = config.sector_plotting_time;
+            Family::<_, _>::new_with_constructor(move || Histogram::new(buckets.iter()))
+        };
 
         sub_registry.register_with_unit(
             "sector_plotting_time",
@@ -104,6 +353,45 @@ impl FarmerMetrics {
             sector_plotting_time.clone(),
         );
 
+        let sector_pipeline_time = {
+            let buckets = config.sector_pipeline_time;
+            Family::<_, _>::new_with_constructor(move || Histogram::new(buckets.iter()))
+        };
+
+        sub_registry.register_with_unit(
+            "sector_pipeline_time",
+            "Time from the start of sector downloading to the end of sector writing",
+            Unit::Seconds,
+            sector_pipeline_time.clone(),
+        );
+
+        let sector_plotting_speed = Family::<_, _>::new_with_constructor(Gauge::<_, _>::default);
+
+        sub_registry.register_with_unit(
+            "sector_plotting_speed",
+            "Plotting speed of the most recently plotted sector",
+            Unit::Other("bytes_per_second".to_string()),
+            sector_plotting_speed.clone(),
+        );
+
+        let plotted_sectors_current = Family::<_, _>::new_with_constructor(Gauge::<_, _>::default);
+
+        sub_registry.register_with_unit(
+            "plotted_sectors_current",
+            "Number of sectors currently plotted",
+            Unit::Other("sectors".to_string()),
+            plotted_sectors_current.clone(),
+        );
+
+        let bytes_written_total = Family::<_, _>::new_with_constructor(Counter::<_, _>::default);
+
+        // Registered without a unit, prometheus-client would otherwise append `_bytes` to the name
+        sub_registry.register(
+            "bytes_written",
+            "Number of bytes of sectors and sector metadata written to disk",
+            bytes_written_total.clone(),
+        );
+
         let sector_downloading = Counter::<_, _>::default();
 
         sub_registry.register_with_unit(
@@ -117,7 +405,7 @@ impl FarmerMetrics {
 
         sub_registry.register_with_unit(
             "sector_downloaded_counter",
-            "Number of sectors being downloaded",
+            "Number of downloaded sectors",
             Unit::Other("sectors".to_string()),
             sector_downloaded.clone(),
         );
@@ -126,7 +414,7 @@ impl FarmerMetrics {
 
         sub_registry.register_with_unit(
             "sector_encoding_counter",
-            "Number of sectors being downloaded",
+            "Number of sectors being encoded",
             Unit::Other("sectors".to_string()),
             sector_encoding.clone(),
         );
@@ -135,7 +423,7 @@ impl FarmerMetrics {
 
         sub_registry.register_with_unit(
             "sector_encoded_counter",
-            "Number of sectors being downloaded",
+            "Number of encoded sectors",
             Unit::Other("sectors".to_string()),
             sector_encoded.clone(),
         );
@@ -144,7 +432,7 @@ impl FarmerMetrics {
 
         sub_registry.register_with_unit(
             "sector_writing_counter",
-            "Number of sectors being downloaded",
+            "Number of sectors being written",
             Unit::Other("sectors".to_string()),
             sector_writing.clone(),
         );
@@ -153,7 +441,7 @@ impl FarmerMetrics {
 
         sub_registry.register_with_unit(
             "sector_written_counter",
-            "Number of sectors being downloaded",
+            "Number of written sectors",
             Unit::Other("sectors".to_string()),
             sector_written.clone(),
         );
@@ -162,7 +450,7 @@ impl FarmerMetrics {
 
         sub_registry.register_with_unit(
             "sector_plotting_counter",
-            "Number of sectors being downloaded",
+            "Number of sectors being plotted",
             Unit::Other("sectors".to_string()),
             sector_plotting.clone(),
         );
@@ -171,19 +459,24 @@ impl FarmerMetrics {
 
         sub_registry.register_with_unit(
             "sector_plotted_counter",
-            "Number of sectors being downloaded",
+            "Number of plotted sectors",
             Unit::Other("sectors".to_string()),
             sector_plotted.clone(),
         );
 
         Self {
-            auditing_time,
-            proving_time,
-            farming_errors,
+            sector_expired,
+            sector_about_to_expire,
+            sector_plotting_errors,
             sector_downloading_time,
             sector_encoding_time,
             sector_writing_time,
+            sector_metadata_writing_time,
             sector_plotting_time,
+            sector_pipeline_time,
+            sector_plotting_speed,
+            plotted_sectors_current,
+            bytes_written_total,
             sector_downloading,
             sector_downloaded,
             sector_encoding,
@@ -195,42 +488,35 @@ impl FarmerMetrics {
         }
     }
 
-    pub(super) fn observe_auditing_time(
-        &self,
-        single_disk_farm_id: &SingleDiskFarmId,
-        time: &Duration,
-    ) {
-        self.auditing_time
+    pub(super) fn note_sector_expired(&self, single_disk_farm_id: &SingleDiskFarmId) {
+        self.sector_expired
             .get_or_create(&vec![(
                 "farm_id".to_string(),
                 single_disk_farm_id.to_string(),
             )])
-            .observe(time.as_secs_f64());
+            .inc();
     }
 
-    pub(super) fn observe_proving_time(
-        &self,
-        single_disk_farm_id: &SingleDiskFarmId,
-        time: &Duration,
-        result: ProvingResult,
-    ) {
-        self.proving_time
-            .get_or_create(&vec![
-                ("farm_id".to_string(), single_disk_farm_id.to_string()),
-                ("result".to_string(), result.to_string()),
-            ])
-            .observe(time.as_secs_f64());
+    pub(super) fn note_sector_about_to_expire(&self, single_disk_farm_id: &SingleDiskFarmId) {
+        self.sector_about_to_expire
+            .get_or_create(&vec![(
+                "farm_id".to_string(),
+                single_disk_farm_id.to_string(),
+            )])
+            .inc();
     }
 
-    pub(super) fn note_farming_error(
+    pub(super) fn note_sector_plotting_error(
         &self,
         single_disk_farm_id: &SingleDiskFarmId,
-        error: &FarmingError,
+        stage: SectorPlottingStage,
+        error: &str,
     ) {
-        self.farming_errors
+        self.sector_plotting_errors
             .get_or_create(&vec![
                 ("farm_id".to_string(), single_disk_farm_id.to_string()),
-                ("error".to_string(), error.str_variant().to_string()),
+                ("stage".to_string(), stage.as_str().to_string()),
+                ("error".to_string(), error.to_string()),
             ])
             .inc();
     }
@@ -274,10 +560,24 @@ impl FarmerMetrics {
             .observe(time.as_secs_f64());
     }
 
+    pub(super) fn observe_sector_metadata_writing_time(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+    ) {
+        self.sector_metadata_writing_time
+            .get_or_create(&vec![(
+                "farm_id".to_string(),
+                single_disk_farm_id.to_string(),
+            )])
+            .observe(time.as_secs_f64());
+    }
+
     pub(super) fn observe_sector_plotting_time(
         &self,
         single_disk_farm_id: &SingleDiskFarmId,
         time: &Duration,
+        sector_size: usize,
     ) {
         self.sector_plotting_time
             .get_or_create(&vec![(
@@ -285,5 +585,445 @@ impl FarmerMetrics {
                 single_disk_farm_id.to_string(),
             )])
             .observe(time.as_secs_f64());
+        self.update_sector_plotting_speed(single_disk_farm_id, time, sector_size);
+    }
+
+    pub(super) fn observe_sector_pipeline_time(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+    ) {
+        self.sector_pipeline_time
+            .get_or_create(&vec![(
+                "farm_id".to_string(),
+                single_disk_farm_id.to_string(),
+            )])
+            .observe(time.as_secs_f64());
+    }
+
+    pub(super) fn note_bytes_written(&self, single_disk_farm_id: &SingleDiskFarmId, bytes: u64) {
+        self.bytes_written_total
+            .get_or_create(&vec![(
+                "farm_id".to_string(),
+                single_disk_farm_id.to_string(),
+            )])
+            .inc_by(bytes);
+    }
+
+    pub(super) fn set_plotted_sectors(&self, single_disk_farm_id: &SingleDiskFarmId, count: usize) {
+        self.plotted_sectors_current
+            .get_or_create(&vec![(
+                "farm_id".to_string(),
+                single_disk_farm_id.to_string(),
+            )])
+            .set(i64::try_from(count).unwrap_or(i64::MAX));
+    }
+
+    pub(super) fn inc_plotted_sectors(&self, single_disk_farm_id: &SingleDiskFarmId) {
+        self.plotted_sectors_current
+            .get_or_create(&vec![(
+                "farm_id".to_string(),
+                single_disk_farm_id.to_string(),
+            )])
+            .inc();
+    }
+
+    fn update_sector_plotting_speed(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+        sector_size: usize,
+    ) {
+        let time = time.as_secs_f64();
+        // Sector can't be plotted instantly, but don't report infinite speed if it somehow was
+        if time == 0.0 {
+            return;
+        }
+
+        self.sector_plotting_speed
+            .get_or_create(&vec![(
+                "farm_id".to_string(),
+                single_disk_farm_id.to_string(),
+            )])
+            .set(sector_size as f64 / time);
+    }
+}
+
+/// Metrics of the farmer, composed of cheaply cloneable handles that can also be used
+/// independently.
+#[derive(Debug, Clone)]
+pub(super) struct FarmerMetrics {
+    pub(super) farming: FarmingMetrics,
+    pub(super) sector: SectorMetrics,
+    farm_state: Family<Vec<(String, String)>, Gauge<i64, AtomicI64>>,
+}
+
+impl FarmerMetrics {
+    pub(super) fn new(registry: &mut Registry, config: FarmerMetricsConfig) -> Self {
+        let sub_registry = registry.sub_registry_with_prefix("subspace_farmer");
+
+        let farming = FarmingMetrics::new(sub_registry, &config);
+        let sector = SectorMetrics::new(sub_registry, &config);
+
+        let farm_state = Family::<_, _>::new_with_constructor(Gauge::<_, _>::default);
+
+        sub_registry.register(
+            "farm_state",
+            "State of the farm (0 - idle, 1 - farming, 2 - plotting, 3 - plotting error)",
+            farm_state.clone(),
+        );
+
+        Self {
+            farming,
+            sector,
+            farm_state,
+        }
+    }
+
+    pub(super) fn observe_auditing_time(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+    ) {
+        self.farming
+            .observe_auditing_time(single_disk_farm_id, time);
+    }
+
+    pub(super) fn observe_proving_time(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+        result: ProvingResult,
+    ) {
+        self.farming
+            .observe_proving_time(single_disk_farm_id, time, result);
+    }
+
+    pub(super) fn set_last_proving_result(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        result: ProvingResult,
+    ) {
+        self.farming
+            .set_last_proving_result(single_disk_farm_id, result);
+    }
+
+    pub(super) fn note_farming_error(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        error: &FarmingError,
+    ) {
+        self.farming.note_farming_error(single_disk_farm_id, error);
+    }
+
+    pub(super) fn note_sector_expired(&self, single_disk_farm_id: &SingleDiskFarmId) {
+        self.sector.note_sector_expired(single_disk_farm_id);
+    }
+
+    pub(super) fn note_sector_about_to_expire(&self, single_disk_farm_id: &SingleDiskFarmId) {
+        self.sector.note_sector_about_to_expire(single_disk_farm_id);
+    }
+
+    pub(super) fn note_sector_plotting_error(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        stage: SectorPlottingStage,
+        error: &str,
+    ) {
+        self.sector
+            .note_sector_plotting_error(single_disk_farm_id, stage, error);
+    }
+
+    pub(super) fn observe_sector_downloading_time(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+    ) {
+        self.sector
+            .observe_sector_downloading_time(single_disk_farm_id, time);
+    }
+
+    pub(super) fn observe_sector_encoding_time(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+    ) {
+        self.sector
+            .observe_sector_encoding_time(single_disk_farm_id, time);
+    }
+
+    pub(super) fn observe_sector_writing_time(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+    ) {
+        self.sector
+            .observe_sector_writing_time(single_disk_farm_id, time);
+    }
+
+    pub(super) fn observe_sector_metadata_writing_time(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+    ) {
+        self.sector
+            .observe_sector_metadata_writing_time(single_disk_farm_id, time);
+    }
+
+    pub(super) fn observe_sector_plotting_time(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+        sector_size: usize,
+    ) {
+        self.sector
+            .observe_sector_plotting_time(single_disk_farm_id, time, sector_size);
+    }
+
+    pub(super) fn observe_sector_pipeline_time(
+        &self,
+        single_disk_farm_id: &SingleDiskFarmId,
+        time: &Duration,
+    ) {
+        self.sector
+            .observe_sector_pipeline_time(single_disk_farm_id, time);
+    }
+
+    pub(super) fn note_bytes_written(&self, single_disk_farm_id: &SingleDiskFarmId, bytes: u64) {
+        self.sector.note_bytes_written(single_disk_farm_id, bytes);
+    }
+
+    pub(super) fn set_plotted_sectors(&self, single_disk_farm_id: &SingleDiskFarmId, count: usize) {
+        self.sector.set_plotted_sectors(single_disk_farm_id, count);
+    }
+
+    pub(super) fn inc_plotted_sectors(&self, single_disk_farm_id: &SingleDiskFarmId) {
+        self.sector.inc_plotted_sectors(single_disk_farm_id);
+    }
+
+    pub(super) fn set_farm_state(&self, single_disk_farm_id: &SingleDiskFarmId, state: FarmState) {
+        self.farm_state
+            .get_or_create(&vec![(
+                "farm_id".to_string(),
+                single_disk_farm_id.to_string(),
+            )])
+            .set(state as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FarmerMetrics, FarmerMetricsConfig, HistogramBuckets};
+    use prometheus_client::encoding::text::encode;
+    use prometheus_client::registry::Registry;
+    use std::time::Duration;
+    use subspace_farmer::single_disk_farm::farming::ProvingResult;
+    use subspace_farmer::single_disk_farm::{SectorPlottingStage, SingleDiskFarmId};
+
+    /// Registers farmer metrics with `config` in a new registry, updates them with `update` and
+    /// returns the text encoding of the registry.
+    fn encode_metrics(config: FarmerMetricsConfig, update: impl FnOnce(&FarmerMetrics)) -> String {
+        let mut registry = Registry::default();
+        let farmer_metrics = FarmerMetrics::new(&mut registry, config);
+
+        update(&farmer_metrics);
+
+        let mut encoded = String::new();
+        encode(&mut encoded, &registry).unwrap();
+        encoded
+    }
+
+    #[track_caller]
+    fn assert_has_line(encoded: &str, expected: &str) {
+        assert!(
+            encoded.lines().any(|line| line == expected),
+            "{expected} is missing in:\n{encoded}"
+        );
+    }
+
+    #[test]
+    fn sector_counters_help() {
+        let encoded = encode_metrics(FarmerMetricsConfig::default(), |_| {});
+
+        for (metric, help) in [
+            (
+                "sector_downloading_counter",
+                "Number of sectors being downloaded",
+            ),
+            ("sector_downloaded_counter", "Number of downloaded sectors"),
+            ("sector_encoding_counter", "Number of sectors being encoded"),
+            ("sector_encoded_counter", "Number of encoded sectors"),
+            ("sector_writing_counter", "Number of sectors being written"),
+            ("sector_written_counter", "Number of written sectors"),
+            ("sector_plotting_counter", "Number of sectors being plotted"),
+            ("sector_plotted_counter", "Number of plotted sectors"),
+        ] {
+            let help_line = encoded
+                .lines()
+                .find(|line| line.starts_with(&format!("# HELP subspace_farmer_{metric}")))
+                .unwrap_or_else(|| panic!("Help of {metric} is missing"));
+            assert!(
+                help_line.contains(help),
+                "Unexpected help of {metric}: {help_line}"
+            );
+        }
+    }
+
+    #[test]
+    fn auditing_and_proving_totals() {
+        let single_disk_farm_id = SingleDiskFarmId::new();
+        let encoded = encode_metrics(FarmerMetricsConfig::default(), |farmer_metrics| {
+            for _ in 0..3 {
+                farmer_metrics
+                    .observe_auditing_time(&single_disk_farm_id, &Duration::from_millis(1));
+            }
+            farmer_metrics.observe_proving_time(
+                &single_disk_farm_id,
+                &Duration::from_millis(1),
+                ProvingResult::Success,
+            );
+            farmer_metrics.observe_proving_time(
+                &single_disk_farm_id,
+                &Duration::from_millis(1),
+                ProvingResult::Timeout,
+            );
+        });
+
+        for (metric, expected) in [
+            ("subspace_farmer_auditing_events_total", 3),
+            ("subspace_farmer_proving_events_total", 2),
+        ] {
+            assert_has_line(
+                &encoded,
+                &format!("{metric}{{farm_id=\"{single_disk_farm_id}\"}} {expected}"),
+            );
+        }
+    }
+
+    #[test]
+    fn last_proving_result() {
+        let single_disk_farm_id = SingleDiskFarmId::new();
+        for (result, expected) in [
+            (ProvingResult::Success, 0),
+            (ProvingResult::Timeout, 1),
+            (ProvingResult::Rejected, 2),
+        ] {
+            let encoded = encode_metrics(FarmerMetricsConfig::default(), |farmer_metrics| {
+                farmer_metrics.set_last_proving_result(&single_disk_farm_id, result);
+            });
+
+            assert_has_line(
+                &encoded,
+                &format!(
+                    "subspace_farmer_last_proving_result{{farm_id=\"{single_disk_farm_id}\"}} {expected}"
+                ),
+            );
+        }
+    }
+
+    #[test]
+    fn sector_plotting_errors() {
+        let single_disk_farm_id = SingleDiskFarmId::new();
+        let errors = [
+            (SectorPlottingStage::Downloading, "FailedToRetrievePiece"),
+            (SectorPlottingStage::Encoding, "PieceRecoveryFailed"),
+            (SectorPlottingStage::Writing, "Io"),
+        ];
+        let encoded = encode_metrics(FarmerMetricsConfig::default(), |farmer_metrics| {
+            for (stage, error) in errors {
+                farmer_metrics.note_sector_plotting_error(&single_disk_farm_id, stage, error);
+            }
+        });
+
+        for (stage, error) in errors {
+            let line = encoded
+                .lines()
+                .find(|line| {
+                    line.starts_with("subspace_farmer_sector_plotting_errors_total{")
+                        && line.contains(&format!("stage=\"{}\"", stage.as_str()))
+                })
+                .unwrap_or_else(|| panic!("Error at {stage:?} stage is missing"));
+            assert!(line.contains(&format!("farm_id=\"{single_disk_farm_id}\"")));
+            assert!(line.contains(&format!("error=\"{error}\"")));
+            assert!(line.ends_with(" 1"));
+        }
+    }
+
+    #[test]
+    fn sector_pipeline_time() {
+        let single_disk_farm_id = SingleDiskFarmId::new();
+        let encoded = encode_metrics(FarmerMetricsConfig::default(), |farmer_metrics| {
+            farmer_metrics
+                .observe_sector_pipeline_time(&single_disk_farm_id, &Duration::from_secs(90));
+        });
+
+        let metric = "subspace_farmer_sector_pipeline_time_seconds";
+        assert_has_line(
+            &encoded,
+            &format!("{metric}_count{{farm_id=\"{single_disk_farm_id}\"}} 1"),
+        );
+        assert_has_line(
+            &encoded,
+            &format!("{metric}_sum{{farm_id=\"{single_disk_farm_id}\"}} 90.0"),
+        );
+        assert_eq!(
+            encoded
+                .lines()
+                .filter(|line| line.starts_with(&format!("{metric}_bucket")))
+                .count(),
+            usize::from(FarmerMetricsConfig::default().sector_pipeline_time.count) + 1
+        );
+    }
+
+    #[test]
+    fn bytes_written() {
+        let single_disk_farm_id = SingleDiskFarmId::new();
+        let encoded = encode_metrics(FarmerMetricsConfig::default(), |farmer_metrics| {
+            farmer_metrics.note_bytes_written(&single_disk_farm_id, 1000);
+            farmer_metrics.note_bytes_written(&single_disk_farm_id, 24);
+        });
+
+        assert_has_line(
+            &encoded,
+            &format!(
+                "subspace_farmer_bytes_written_total{{farm_id=\"{single_disk_farm_id}\"}} 1024"
+            ),
+        );
+    }
+
+    #[test]
+    fn custom_buckets() {
+        let single_disk_farm_id = SingleDiskFarmId::new();
+        let config = FarmerMetricsConfig {
+            sector_writing_time: HistogramBuckets {
+                start: 1.0,
+                factor: 10.0,
+                count: 3,
+            },
+            ..FarmerMetricsConfig::default()
+        };
+        let encoded = encode_metrics(config, |farmer_metrics| {
+            farmer_metrics
+                .observe_sector_writing_time(&single_disk_farm_id, &Duration::from_secs(50));
+            farmer_metrics
+                .observe_sector_encoding_time(&single_disk_farm_id, &Duration::from_secs(50));
+        });
+
+        let buckets = |metric: &str| {
+            encoded
+                .lines()
+                .filter(|line| line.starts_with(&format!("{metric}_bucket")))
+                .count()
+        };
+        // Configured buckets and the implicit `+Inf` one
+        assert_eq!(buckets("subspace_farmer_sector_writing_time_seconds"), 4);
+        assert_eq!(
+            buckets("subspace_farmer_sector_encoding_time_seconds"),
+            usize::from(HistogramBuckets::default().count) + 1
+        );
+        assert!(encoded.lines().any(|line| {
+            line.starts_with("subspace_farmer_sector_writing_time_seconds_bucket")
+                && line.contains("le=\"100.0\"")
+        }));
     }
 }
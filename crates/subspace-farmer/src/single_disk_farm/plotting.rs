@@ -69,6 +69,8 @@ pub enum SectorPlottingDetails {
     Writing,
     /// Written sector
     Written(Duration),
+    /// Written sector metadata, the time is also included in [`Self::Written`]
+    MetadataWritten(Duration),
     /// Finished plotting
     Finished {
         /// Information about plotted sector
@@ -78,6 +80,35 @@ pub enum SectorPlottingDetails {
         /// How much time it took to plot a sector
         time: Duration,
     },
+    /// Plotting failed
+    Error {
+        /// Stage of plotting that failed
+        stage: SectorPlottingStage,
+        /// String variant of the error, see [`PlottingError::str_variant()`]
+        error: String,
+    },
+}
+
+/// Stage of sector plotting
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum SectorPlottingStage {
+    /// Downloading sector pieces
+    Downloading,
+    /// Encoding sector pieces
+    Encoding,
+    /// Writing sector and its metadata
+    Writing,
+}
+
+impl SectorPlottingStage {
+    /// Short name of the stage, primarily for monitoring purposes
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SectorPlottingStage::Downloading => "downloading",
+            SectorPlottingStage::Encoding => "encoding",
+            SectorPlottingStage::Writing => "writing",
+        }
+    }
 }
 
 /// Details about sector expiration
@@ -145,6 +176,24 @@ pub enum PlottingError {
     BackgroundDownloadingPanicked,
 }
 
+impl PlottingError {
+    /// String variant of the error, primarily for monitoring purposes
+    pub fn str_variant(&self) -> &str {
+        match self {
+            PlottingError::FailedToGetFarmerInfo { .. } => "FailedToGetFarmerInfo",
+            PlottingError::FailedToGetSegmentHeader { .. } => "FailedToGetSegmentHeader",
+            PlottingError::MissingArchivedSegmentHeader { .. } => "MissingArchivedSegmentHeader",
+            PlottingError::FailedToSubscribeArchivedSegments { .. } => {
+                "FailedToSubscribeArchivedSegments"
+            }
+            PlottingError::FarmIsShuttingDown => "FarmIsShuttingDown",
+            PlottingError::LowLevel(error) => error.str_variant(),
+            PlottingError::Io(_) => "Io",
+            PlottingError::BackgroundDownloadingPanicked => "BackgroundDownloadingPanicked",
+        }
+    }
+}
+
 pub(super) struct PlottingOptions<'a, NC, PG> {
     pub(super) public_key: PublicKey,
     pub(super) node_client: &'a NC,
@@ -203,9 +252,8 @@ where
 
     let mut table_generator = PosTable::generator();
 
-    let mut maybe_next_downloaded_sector_fut = None::<
-        AsyncJoinOnDrop<Result<(OwnedSemaphorePermit, DownloadedSector), plotting::PlottingError>>,
-    >;
+    let mut maybe_next_downloaded_sector_fut =
+        None::<AsyncJoinOnDrop<Result<(OwnedSemaphorePermit, DownloadedSector), PlottingError>>>;
     while let Some(sector_to_plot) = sectors_to_plot_receiver.next().await {
         let SectorToPlot {
             sector_index,
@@ -300,7 +348,17 @@ where
                     pieces_in_sector,
                 });
 
-                let downloaded_sector = downloaded_sector_fut.await?;
+                let downloaded_sector = downloaded_sector_fut
+                    .await
+                    .map_err(PlottingError::from)
+                    .inspect_err(|error| {
+                        note_sector_plotting_error(
+                            &handlers,
+                            sector_index,
+                            SectorPlottingStage::Downloading,
+                            error,
+                        );
+                    })?;
 
                 handlers.sector_update.call_simple(&(
                     sector_index,
@@ -344,7 +402,17 @@ where
                             pieces_in_sector,
                         });
 
-                        let downloaded_sector = downloaded_sector_fut.await?;
+                        let downloaded_sector = downloaded_sector_fut
+                            .await
+                            .map_err(PlottingError::from)
+                            .inspect_err(|error| {
+                                note_sector_plotting_error(
+                                    &handlers,
+                                    sector_index,
+                                    SectorPlottingStage::Downloading,
+                                    error,
+                                );
+                            })?;
 
                         handlers.sector_update.call_simple(&(
                             sector_index,
@@ -394,7 +462,16 @@ where
                         Handle::current().block_on(async {
                             select! {
                                 plotting_result = plot_sector_fut.fuse() => {
-                                    plotting_result.map_err(PlottingError::from)
+                                    plotting_result
+                                        .map_err(PlottingError::from)
+                                        .inspect_err(|error| {
+                                            note_sector_plotting_error(
+                                                &handlers,
+                                                sector_index,
+                                                SectorPlottingStage::Encoding,
+                                                error,
+                                            );
+                                        })
                                 }
                                 _ = stop_receiver.recv().fuse() => {
                                     Err(PlottingError::FarmIsShuttingDown)
@@ -442,11 +519,37 @@ where
 
             let start = Instant::now();
 
-            plot_file.write_all_at(&sector, (sector_index as usize * sector_size) as u64)?;
-            metadata_file.write_all_at(
-                &sector_metadata,
-                RESERVED_PLOT_METADATA + (u64::from(sector_index) * sector_metadata_size as u64),
-            )?;
+            let note_writing_error = |error: io::Error| {
+                let error = PlottingError::from(error);
+                note_sector_plotting_error(
+                    &handlers,
+                    sector_index,
+                    SectorPlottingStage::Writing,
+                    &error,
+                );
+                error
+            };
+
+            plot_file
+                .write_all_at(&sector, (sector_index as usize * sector_size) as u64)
+                .map_err(note_writing_error)?;
+
+            let metadata_start = Instant::now();
+
+            metadata_file
+                .write_all_at(
+                    &sector_metadata,
+                    RESERVED_PLOT_METADATA
+                        + (u64::from(sector_index) * sector_metadata_size as u64),
+                )
+                .map_err(note_writing_error)?;
+
+            handlers.sector_update.call_simple(&(
+                sector_index,
+                SectorUpdate::Plotting(SectorPlottingDetails::MetadataWritten(
+                    metadata_start.elapsed(),
+                )),
+            ));
 
             handlers.sector_update.call_simple(&(
                 sector_index,
@@ -523,6 +626,21 @@ where
     Ok(())
 }
 
+fn note_sector_plotting_error(
+    handlers: &Handlers,
+    sector_index: SectorIndex,
+    stage: SectorPlottingStage,
+    error: &PlottingError,
+) {
+    handlers.sector_update.call_simple(&(
+        sector_index,
+        SectorUpdate::Plotting(SectorPlottingDetails::Error {
+            stage,
+            error: error.str_variant().to_string(),
+        }),
+    ));
+}
+
 pub(super) struct PlottingSchedulerOptions<NC> {
     pub(super) public_key_hash: Blake3Hash,
     pub(super) sectors_indices_left_to_plot: Range<SectorIndex>,
@@ -148,6 +148,7 @@ where
                     domain_message_receiver,
                     provider: eth_provider,
                     skip_empty_bundle_production: true,
+                    extrinsics_pre_validation_threads: 0,
                     // Always set it to `None` to not running the normal bundle producer
                     maybe_operator_id: None,
                 };
@@ -190,6 +191,7 @@ where
 
                 Ok(())
             }
+            RuntimeType::AutoId => Err("AutoId domain runtime is not supported yet".into()),
         }
     }
 }
@@ -452,6 +452,7 @@ fn subspace_genesis_config(
             enable_balance_transfers,
             enable_non_root_calls,
             confirmation_depth_k,
+            ..Default::default()
         },
         domains: DomainsConfig {
             genesis_domain: Some(sp_domains::GenesisDomain {
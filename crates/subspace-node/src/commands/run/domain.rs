@@ -121,6 +121,13 @@ pub(super) struct DomainOptions {
     #[clap(flatten)]
     pool_config: TransactionPoolParams,
 
+    /// Number of threads used to validate the extrinsics of a domain block in parallel before they
+    /// are applied, statically invalid extrinsics are reported in the logs and metrics.
+    ///
+    /// `0` disables the parallel pre-validation.
+    #[arg(long, default_value_t = 0)]
+    extrinsics_pre_validation_threads: usize,
+
     /// Additional args for domain.
     #[clap(raw = true)]
     additional_args: Vec<String>,
@@ -130,6 +137,7 @@ pub(super) struct DomainConfiguration {
     pub(super) domain_config: Configuration,
     pub(super) domain_id: DomainId,
     pub(super) operator_id: Option<OperatorId>,
+    pub(super) extrinsics_pre_validation_threads: usize,
     pub(super) additional_args: Vec<String>,
 }
 
@@ -149,6 +157,7 @@ pub(super) fn create_domain_configuration(
         mut keystore_suri,
         keystore_options,
         pool_config,
+        extrinsics_pre_validation_threads,
         additional_args,
     } = domain_options;
 
@@ -369,6 +378,7 @@ pub(super) fn create_domain_configuration(
         domain_config: Configuration::from(domain_config),
         domain_id,
         operator_id,
+        extrinsics_pre_validation_threads,
         additional_args,
     })
 }
@@ -409,6 +419,7 @@ where
         mut domain_config,
         domain_id,
         operator_id,
+        extrinsics_pre_validation_threads,
         additional_args,
     } = domain_configuration;
 
@@ -483,6 +494,7 @@ where
                 domain_message_receiver,
                 provider: eth_provider,
                 skip_empty_bundle_production: true,
+                extrinsics_pre_validation_threads,
                 maybe_operator_id: operator_id,
             };
 
@@ -506,5 +518,8 @@ where
 
             Ok(())
         }
+        RuntimeType::AutoId => Err(Error::Other(
+            "AutoId domain runtime is not supported yet".to_string(),
+        )),
     }
 }
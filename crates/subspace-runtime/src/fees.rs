@@ -1,4 +1,4 @@
-use crate::{Balances, Runtime, RuntimeCall, TransactionFees};
+use crate::{Balances, Runtime, RuntimeCall, RuntimeConfigs, TransactionFees};
 use codec::Encode;
 use frame_support::traits::{Currency, ExistenceRequirement, Get, Imbalance, WithdrawReasons};
 use pallet_balances::NegativeImbalance;
@@ -6,11 +6,23 @@ use sp_runtime::traits::{DispatchInfoOf, PostDispatchInfoOf, Zero};
 use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidityError};
 use subspace_runtime_primitives::{AccountId, Balance};
 
+/// Transaction byte fee used when dynamic cost of storage is disabled.
+const STATIC_TRANSACTION_BYTE_FEE: Balance = 1;
+
+/// Transaction byte fee, when dynamic cost of storage is enabled it is clamped to the bounds set in
+/// the cost of storage parameters of pallet-runtime-configs.
 pub struct TransactionByteFee;
 
 impl Get<Balance> for TransactionByteFee {
     fn get() -> Balance {
-        TransactionFees::transaction_byte_fee()
+        let transaction_byte_fee = TransactionFees::transaction_byte_fee();
+
+        if !RuntimeConfigs::enable_dynamic_cost_of_storage() {
+            return transaction_byte_fee;
+        }
+
+        RuntimeConfigs::cost_of_storage_parameters()
+            .clamp_transaction_byte_fee(transaction_byte_fee, STATIC_TRANSACTION_BYTE_FEE)
     }
 }
 
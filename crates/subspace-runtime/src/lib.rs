@@ -40,13 +40,14 @@ use domain_runtime_primitives::{
 };
 use frame_support::inherent::ProvideInherent;
 use frame_support::traits::{
-    ConstU16, ConstU32, ConstU64, ConstU8, Currency, Everything, Get, VariantCount,
+    ConstBool, ConstU16, ConstU32, ConstU64, ConstU8, Contains, Currency, Get, InsideBoth,
+    VariantCount,
 };
 use frame_support::weights::constants::{RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND};
 use frame_support::weights::{ConstantMultiplier, IdentityFee, Weight};
 use frame_support::{construct_runtime, parameter_types, PalletId};
 use frame_system::limits::{BlockLength, BlockWeights};
-use frame_system::EnsureNever;
+use frame_system::{EnsureNever, EnsureRoot};
 pub use pallet_subspace::{AllowAuthoringBy, EnableRewardsAt};
 use pallet_transporter::EndpointHandler;
 use scale_info::TypeInfo;
@@ -87,7 +88,7 @@ use subspace_core_primitives::{
 };
 use subspace_runtime_primitives::{
     AccountId, Balance, BlockNumber, FindBlockRewardAddress, Hash, Moment, Nonce, Signature,
-    SlowAdjustingFeeUpdate, MIN_REPLICATION_FACTOR, SHANNON, SSC,
+    SlowAdjustingFeeUpdate, SHANNON, SSC,
 };
 
 sp_runtime::impl_opaque_keys! {
@@ -250,14 +251,48 @@ parameter_types! {
 
 pub type SS58Prefix = ConstU16<2254>;
 
+/// Calls allowed in maintenance mode in addition to `pallet-runtime-configs` calls, see
+/// [`pallet_runtime_configs::MaintenanceModeFilter`].
+pub struct MaintenanceModeAllowedCalls;
+
+impl Contains<RuntimeCall> for MaintenanceModeAllowedCalls {
+    fn contains(call: &RuntimeCall) -> bool {
+        match call {
+            // Inherents are required to produce blocks
+            RuntimeCall::Timestamp(call) => Timestamp::is_inherent(call),
+            RuntimeCall::Subspace(call) => Subspace::is_inherent(call),
+            // Root calls are dispatched through sudo
+            RuntimeCall::Sudo(_) => true,
+            _ => false,
+        }
+    }
+}
+
+parameter_types! {
+    /// Calls that can't be disabled in `pallet-runtime-configs`, the same inherents and sudo calls
+    /// as [`MaintenanceModeAllowedCalls`].
+    pub const NonDisableableCalls: &'static [(&'static str, &'static str)] = &[
+        ("Timestamp", "set"),
+        ("Subspace", "store_segment_headers"),
+        ("Sudo", "sudo"),
+        ("Sudo", "sudo_unchecked_weight"),
+        ("Sudo", "sudo_as"),
+        ("Sudo", "set_key"),
+    ];
+}
+
 // Configure FRAME pallets to include in runtime.
 
 impl frame_system::Config for Runtime {
     /// The basic call filter to use in dispatchable.
     ///
-    /// `Everything` is used here as we use the signed extension
-    /// `DisablePallets` as the actual call filter.
-    type BaseCallFilter = Everything;
+    /// Calls disabled in `pallet-runtime-configs` and all calls except
+    /// [`MaintenanceModeAllowedCalls`] in maintenance mode are filtered here, the flags of
+    /// `pallet-runtime-configs` are enforced by the `DisablePallets` signed extension.
+    type BaseCallFilter = InsideBoth<
+        pallet_runtime_configs::EnabledCalls<Runtime>,
+        pallet_runtime_configs::MaintenanceModeFilter<Runtime, MaintenanceModeAllowedCalls>,
+    >;
     /// Block & extrinsics weights: base values and limits.
     type BlockWeights = SubspaceBlockWeights;
     /// The maximum length of a block (in bytes).
@@ -424,18 +459,19 @@ parameter_types! {
         sectors as u128 * MAX_PIECES_IN_SECTOR as u128 * Piece::SIZE as u128
     };
     pub BlockchainHistorySize: u128 = u128::from(Subspace::archived_history_size());
-    pub DynamicCostOfStorage: bool = RuntimeConfigs::enable_dynamic_cost_of_storage();
+    pub MinReplicationFactor: u16 =
+        RuntimeConfigs::cost_of_storage_parameters().replication_factor;
 }
 
 impl pallet_transaction_fees::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
-    type MinReplicationFactor = ConstU16<MIN_REPLICATION_FACTOR>;
+    type MinReplicationFactor = MinReplicationFactor;
     type CreditSupply = CreditSupply;
     type TotalSpacePledged = TotalSpacePledged;
     type BlockchainHistorySize = BlockchainHistorySize;
     type Currency = Balances;
     type FindBlockRewardAddress = Subspace;
-    type DynamicCostOfStorage = DynamicCostOfStorage;
+    type RuntimeConfigs = RuntimeConfigs;
     type WeightInfo = ();
 }
 
@@ -571,6 +607,10 @@ parameter_types! {
     pub const MaxBundlesPerBlock: u32 = 10;
     pub const DomainInstantiationDeposit: Balance = 100 * SSC;
     pub const MaxDomainNameLength: u32 = 32;
+    pub const MaxRuntimeUpgradeHistory: u32 = 16;
+    pub const MaxRuntimeUpgradesPerBlock: u32 = 5;
+    pub const MaxRuntimeNameLength: u32 = 32;
+    pub const MaxDomainRuntimeCodeSize: u32 = 4 * 1024 * 1024;
     pub const BlockTreePruningDepth: u32 = 14_400;
     pub const StakeWithdrawalLockingPeriod: DomainNumber = 14_400;
     // TODO: revisit these. For now epoch every 10 mins for a 6 second block and only 100 number of staking
@@ -615,6 +655,13 @@ impl pallet_domains::Config for Runtime {
     type MaxNominators = MaxNominators;
     type Randomness = Subspace;
     type SudoId = SudoId;
+    type RuntimeUpgradeOrigin = EnsureRoot<AccountId>;
+    type MaxRuntimeUpgradeHistory = MaxRuntimeUpgradeHistory;
+    type MaxRuntimeUpgradesPerBlock = MaxRuntimeUpgradesPerBlock;
+    type MaxRuntimeNameLength = MaxRuntimeNameLength;
+    type MaxDomainRuntimeCodeSize = MaxDomainRuntimeCodeSize;
+    type AllowImmediateRuntimeUpgrade = ConstBool<false>;
+    type FrozenDomains = RuntimeConfigs;
 }
 
 parameter_types! {
@@ -634,7 +681,24 @@ impl pallet_rewards::Config for Runtime {
 }
 
 impl pallet_runtime_configs::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
     type WeightInfo = pallet_runtime_configs::weights::SubstrateWeight<Runtime>;
+    // Smallest depth used by any of the chain specs (dev).
+    type MinConfirmationDepthK = ConstU32<5>;
+    // Roughly a day worth of blocks, anything larger is most likely a typo.
+    type MaxConfirmationDepthK = ConstU32<14_400>;
+    type MaxScheduledConfigChangesPerBlock = ConstU32<16>;
+    type MaxTransferAllowlistSize = ConstU32<100>;
+    type MaxFrozenDomains = ConstU32<100>;
+    type EnableDomainsOrigin = EnsureRoot<AccountId>;
+    type EnableDynamicCostOfStorageOrigin = EnsureRoot<AccountId>;
+    type EnableBalanceTransfersOrigin = EnsureRoot<AccountId>;
+    type EnableNonRootCallsOrigin = EnsureRoot<AccountId>;
+    type ConfirmationDepthKOrigin = EnsureRoot<AccountId>;
+    type DisabledCallsOrigin = EnsureRoot<AccountId>;
+    type NonDisableableCalls = NonDisableableCalls;
+    type MaintenanceModeOrigin = EnsureRoot<AccountId>;
+    type FrozenDomainsOrigin = EnsureRoot<AccountId>;
 }
 
 parameter_types! {
@@ -736,6 +800,10 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    (
+        pallet_runtime_configs::migrations::v1::MigrateToV1<Runtime>,
+        pallet_domains::migrations::v1::MigrateToV1<Runtime>,
+    ),
 >;
 
 fn extract_segment_headers(ext: &UncheckedExtrinsic) -> Option<Vec<SegmentHeader>> {
@@ -883,6 +951,17 @@ impl_runtime_apis! {
         }
     }
 
+    impl sp_runtime_configs::RuntimeConfigsApi<Block> for Runtime {
+        fn runtime_configs() -> sp_runtime_configs::RuntimeConfigs<BlockNumber> {
+            RuntimeConfigs::runtime_configs()
+        }
+
+        fn config_change_log(
+        ) -> Vec<(sp_runtime_configs::ConfigKey, BlockNumber, Option<AccountId>)> {
+            RuntimeConfigs::config_change_log()
+        }
+    }
+
     impl sp_consensus_subspace::SubspaceApi<Block, FarmerPublicKey> for Runtime {
         fn pot_parameters() -> PotParameters {
             Subspace::pot_parameters()
@@ -976,6 +1055,7 @@ impl_runtime_apis! {
         }
     }
 
+    #[api_version(2)]
     impl sp_domains::DomainsApi<Block, DomainHeader> for Runtime {
         fn submit_bundle_unsigned(
             opaque_bundle: sp_domains::OpaqueBundle<NumberFor<Block>, <Block as BlockT>::Hash, DomainHeader, Balance>,
@@ -1082,7 +1162,23 @@ impl_runtime_apis! {
         }
 
         fn consensus_chain_byte_fee() -> Balance {
-            DOMAIN_STORAGE_FEE_MULTIPLIER * TransactionFees::transaction_byte_fee()
+            DOMAIN_STORAGE_FEE_MULTIPLIER * TransactionByteFee::get()
+        }
+
+        fn runtime_registry_storage_version() -> u16 {
+            Domains::runtime_registry_storage_version()
+        }
+
+        fn scheduled_runtime_upgrades() -> Vec<(BlockNumber, sp_domains::RuntimeId, RuntimeVersion)> {
+            Domains::scheduled_runtime_upgrades()
+        }
+
+        fn domain_runtime_object(runtime_id: sp_domains::RuntimeId) -> Option<sp_domains::RuntimeObjectInfo<BlockNumber, Hash>> {
+            Domains::domain_runtime_object(runtime_id)
+        }
+
+        fn pending_runtime_upgrade(runtime_id: sp_domains::RuntimeId) -> Option<(BlockNumber, RuntimeVersion, Hash)> {
+            Domains::pending_runtime_upgrade(runtime_id)
         }
     }
 
@@ -1285,11 +1381,45 @@ impl_runtime_apis! {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Runtime, SubspaceBlockWeights as BlockWeights};
+    use crate::{Runtime, RuntimeCall, SubspaceBlockWeights as BlockWeights};
+    use frame_support::traits::Contains;
     use subspace_runtime_primitives::tests_utils::FeeMultiplierUtils;
 
+    type BaseCallFilter = <Runtime as frame_system::Config>::BaseCallFilter;
+
     #[test]
     fn multiplier_can_grow_from_zero() {
         FeeMultiplierUtils::<Runtime, BlockWeights>::multiplier_can_grow_from_zero()
     }
+
+    #[test]
+    fn maintenance_mode_filter() {
+        sp_io::TestExternalities::default().execute_with(|| {
+            let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+            let set_timestamp = RuntimeCall::Timestamp(pallet_timestamp::Call::set { now: 0 });
+            let sudo_remark = RuntimeCall::Sudo(pallet_sudo::Call::sudo {
+                call: Box::new(remark.clone()),
+            });
+            let exit_maintenance_mode =
+                RuntimeCall::RuntimeConfigs(pallet_runtime_configs::Call::set_maintenance_mode {
+                    enabled: false,
+                });
+
+            for call in [
+                &remark,
+                &set_timestamp,
+                &sudo_remark,
+                &exit_maintenance_mode,
+            ] {
+                assert!(BaseCallFilter::contains(call));
+            }
+
+            pallet_runtime_configs::EnableChainMaintenanceMode::<Runtime>::put(true);
+
+            assert!(!BaseCallFilter::contains(&remark));
+            for call in [&set_timestamp, &sudo_remark, &exit_maintenance_mode] {
+                assert!(BaseCallFilter::contains(call));
+            }
+        });
+    }
 }
@@ -63,12 +63,12 @@ impl SignedExtension for DisablePallets {
 
     fn validate(
         &self,
-        _who: &Self::AccountId,
+        who: &Self::AccountId,
         call: &Self::Call,
         _info: &DispatchInfoOf<Self::Call>,
         _len: usize,
     ) -> TransactionValidity {
-        // Disable normal balance transfers.
+        // Disable normal balance transfers, except for allowlisted accounts.
         if matches!(
             call,
             RuntimeCall::Balances(
@@ -76,7 +76,7 @@ impl SignedExtension for DisablePallets {
                     | pallet_balances::Call::transfer_keep_alive { .. }
                     | pallet_balances::Call::transfer_all { .. }
             )
-        ) && !RuntimeConfigs::enable_balance_transfers()
+        ) && !RuntimeConfigs::can_transfer(who)
         {
             InvalidTransaction::Call.into()
         } else {
@@ -93,6 +93,7 @@ use sp_mmr_primitives::MmrApi;
 use sp_objects::ObjectsApi;
 use sp_offchain::OffchainWorkerApi;
 use sp_runtime::traits::{Block as BlockT, BlockIdTo, Header, NumberFor, Zero};
+use sp_runtime_configs::RuntimeConfigsApi;
 use sp_session::SessionKeys;
 use sp_subspace_mmr::host_functions::{SubspaceMmrExtension, SubspaceMmrHostFunctionsImpl};
 use sp_transaction_pool::runtime_api::TaggedTransactionQueue;
@@ -642,7 +643,8 @@ where
         + DomainsApi<Block, DomainHeader>
         + FraudProofApi<Block, DomainHeader>
         + ObjectsApi<Block>
-        + MmrApi<Block, Hash, BlockNumber>,
+        + MmrApi<Block, Hash, BlockNumber>
+        + RuntimeConfigsApi<Block>,
 {
     let PartialComponents {
         client,
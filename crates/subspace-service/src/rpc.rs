@@ -34,6 +34,7 @@ use sc_consensus_subspace_rpc::{SubspaceRpc, SubspaceRpcApiServer, SubspaceRpcCo
 use sc_rpc::SubscriptionTaskExecutor;
 use sc_rpc_api::DenyUnsafe;
 use sc_rpc_spec_v2::chain_spec::{ChainSpec, ChainSpecApiServer};
+use sc_runtime_configs_rpc::{RuntimeConfigsRpc, RuntimeConfigsRpcApiServer};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -41,6 +42,7 @@ use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_consensus::SyncOracle;
 use sp_consensus_subspace::{FarmerPublicKey, SubspaceApi};
 use sp_objects::ObjectsApi;
+use sp_runtime_configs::RuntimeConfigsApi;
 use std::sync::Arc;
 use subspace_core_primitives::crypto::kzg::Kzg;
 use subspace_core_primitives::BlockNumber;
@@ -101,7 +103,8 @@ where
         + BlockBuilder<Block>
         + SubspaceApi<Block, FarmerPublicKey>
         + mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash, BlockNumber>
-        + ObjectsApi<Block>,
+        + ObjectsApi<Block>
+        + RuntimeConfigsApi<Block>,
     P: TransactionPool + 'static,
     SO: SyncOracle + Send + Sync + Clone + 'static,
     AS: AuxStore + Send + Sync + 'static,
@@ -132,6 +135,7 @@ where
 
     module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
     module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    module.merge(RuntimeConfigsRpc::new(client.clone()).into_rpc())?;
 
     module.merge(
         SubspaceRpc::new(SubspaceRpcConfig {
@@ -0,0 +1,84 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use domain_block_builder::{BlockBuilder, BuildMode, RecordProof};
+use sp_blockchain::HeaderBackend;
+use std::collections::VecDeque;
+use substrate_test_runtime_client::runtime::{Block, Extrinsic, Transfer};
+use substrate_test_runtime_client::{
+    AccountKeyring, Backend, DefaultTestClientBuilderExt, TestClient, TestClientBuilderExt,
+};
+
+const EXTRINSICS: u64 = 1000;
+
+fn transfer(nonce: u64) -> Extrinsic {
+    Transfer {
+        from: AccountKeyring::Alice.into(),
+        to: AccountKeyring::Bob.into(),
+        amount: 1,
+        nonce,
+    }
+    .into_unchecked_extrinsic()
+}
+
+fn new_block_builder<'a>(
+    client: &'a TestClient,
+    backend: &'a Backend,
+    extrinsics: VecDeque<Extrinsic>,
+) -> BlockBuilder<'a, Block, TestClient, Backend> {
+    BlockBuilder::new(
+        client,
+        client.info().best_hash,
+        client.info().best_number,
+        RecordProof::No,
+        Default::default(),
+        backend,
+        extrinsics,
+        None,
+    )
+    .unwrap()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let (client, backend) =
+        substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+    let extrinsics: VecDeque<_> = (0..EXTRINSICS).map(transfer).collect();
+
+    let mut group = c.benchmark_group("state-root-1000-extrinsics");
+    group.sample_size(10);
+
+    group.bench_function("build-state-only", |b| {
+        b.iter_batched(
+            || new_block_builder(&client, &backend, extrinsics.clone()),
+            |block_builder| {
+                let (storage_changes, _proof) = block_builder.build_state_only().unwrap();
+                black_box(storage_changes.transaction_storage_root);
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("storage-root-after-finalize", |b| {
+        b.iter_batched(
+            || new_block_builder(&client, &backend, extrinsics.clone()),
+            |mut block_builder| {
+                black_box(block_builder.storage_root_after_finalize().unwrap());
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("storage-root-after-finalize-then-build", |b| {
+        b.iter_batched(
+            || new_block_builder(&client, &backend, extrinsics.clone()),
+            |mut block_builder| {
+                black_box(block_builder.storage_root_after_finalize().unwrap());
+                black_box(block_builder.build(BuildMode::BestEffort).unwrap());
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
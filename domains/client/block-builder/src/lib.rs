@@ -26,17 +26,140 @@
 
 #![warn(missing_docs)]
 
-use codec::Encode;
-use sc_client_api::backend;
+mod metrics;
+
+pub use crate::metrics::BlockBuilderMetrics;
+use codec::{Decode, Encode};
+use domain_runtime_primitives::{block_weights, DomainCoreApi};
+use frame_support::dispatch::DispatchClass;
+use frame_system::limits::BlockWeights;
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use sc_client_api::backend::{self, StateBackend};
+use sc_consensus::{BlockImportParams, ForkChoiceStrategy, StateAction};
 use sp_api::{
-    ApiExt, ApiRef, Core, ProvideRuntimeApi, StorageChanges, StorageProof, TransactionOutcome,
+    ApiError, ApiExt, ApiRef, Core, ProvideRuntimeApi, StorageChanges, StorageProof,
+    TransactionOutcome,
 };
 pub use sp_block_builder::BlockBuilder as BlockBuilderApi;
-use sp_blockchain::{ApplyExtrinsicFailed, Error};
+use sp_blockchain::{ApplyExtrinsicFailed, Error, HeaderBackend};
+use sp_consensus::BlockOrigin;
+use sp_inherents::{InherentData, InherentIdentifier};
 use sp_runtime::generic::BlockId;
 use sp_runtime::traits::{Block as BlockT, Hash, HashingFor, Header as HeaderT, NumberFor, One};
-use sp_runtime::Digest;
-use std::collections::VecDeque;
+use sp_runtime::transaction_validity::{
+    InvalidTransaction, TransactionSource, TransactionValidityError,
+};
+use sp_runtime::{Digest, DigestItem, Weight};
+use sp_transaction_pool::runtime_api::TaggedTransactionQueue;
+use std::cell::Cell;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Block builder specific errors, surfaced as [`Error::Application`].
+#[derive(Debug, thiserror::Error)]
+pub enum BlockBuilderError {
+    /// The extrinsics root in the header differs from the root of the block body.
+    #[error("Extrinsics root mismatch, header: {header_root}, computed: {computed_root}")]
+    ExtrinsicsRootMismatch {
+        /// The extrinsics root in the header.
+        header_root: String,
+        /// The extrinsics root computed from the block body.
+        computed_root: String,
+    },
+    /// The runtime rejected the inherents of the built block.
+    #[error("Inherent check failed for inherents: {}", format_identifiers(.0))]
+    InherentCheckFailed(Vec<InherentIdentifier>),
+    /// The runtime failed to create the inherent extrinsics.
+    #[error(
+        "Failed to create inherents from inherent data of {}: {error}",
+        format_identifiers(.identifiers)
+    )]
+    InherentCreationFailed {
+        /// Identifiers of the inherent data the inherents were created from.
+        identifiers: Vec<InherentIdentifier>,
+        /// The runtime api error, usually carries the panic message of the failed inherent.
+        error: ApiError,
+    },
+    /// The recorded proof exceeded the size limit given by [`RecordProof::YesWithSizeLimit`].
+    #[error(
+        "Proof size {proof_size} exceeded limit {size_limit} after extrinsic at index {extrinsic_index}"
+    )]
+    ProofSizeLimitExceeded {
+        /// Index of the extrinsic whose execution made the proof exceed the limit.
+        extrinsic_index: usize,
+        /// Estimated encoded size of the recorded proof.
+        proof_size: usize,
+        /// The size limit.
+        size_limit: usize,
+    },
+    /// The queued extrinsics were modified after some of them were already executed.
+    #[error("Extrinsics can not be modified after they were executed")]
+    ExtrinsicsAlreadyExecuted,
+    /// The recorded proof is not a valid proof of the storage root of the built block.
+    #[error("Invalid storage proof: {0}")]
+    InvalidProof(String),
+}
+
+impl From<BlockBuilderError> for Error {
+    #[inline]
+    fn from(error: BlockBuilderError) -> Self {
+        Error::Application(Box::new(error))
+    }
+}
+
+/// Returns the identifiers of all the inherent data in `inherent_data`.
+///
+/// `InherentData` doesn't expose its identifiers, it is however encoded as the map of the inherent
+/// data by identifier so the identifiers are extracted from its encoding.
+fn inherent_identifiers(inherent_data: &InherentData) -> Vec<InherentIdentifier> {
+    BTreeMap::<InherentIdentifier, Vec<u8>>::decode(&mut inherent_data.encode().as_slice())
+        .map(|data| data.into_keys().collect())
+        .unwrap_or_default()
+}
+
+fn format_identifiers(identifiers: &[InherentIdentifier]) -> String {
+    identifiers
+        .iter()
+        .map(|identifier| String::from_utf8_lossy(identifier).into_owned())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Check that the extrinsics root in the header of `block` is the root of its body.
+fn check_extrinsics_root<Block: BlockT>(block: &Block) -> Result<(), BlockBuilderError> {
+    let header_root = *block.header().extrinsics_root();
+    let computed_root = HashingFor::<Block>::ordered_trie_root(
+        block.extrinsics().iter().map(Encode::encode).collect(),
+        sp_core::storage::StateVersion::V1,
+    );
+
+    if header_root != computed_root {
+        return Err(BlockBuilderError::ExtrinsicsRootMismatch {
+            header_root: format!("{header_root:?}"),
+            computed_root: format!("{computed_root:?}"),
+        });
+    }
+
+    Ok(())
+}
+
+/// Assemble a `Block` from a trusted `header` and the `extrinsics` of its body, without
+/// initializing the block nor executing the extrinsics.
+///
+/// Only the extrinsics root of `header` is checked against `extrinsics`. As nothing is executed
+/// there are no storage changes nor proof, use [`BlockBuilder`] when they are needed.
+pub fn build_unexecuted<Block: BlockT>(
+    header: Block::Header,
+    extrinsics: Vec<Block::Extrinsic>,
+) -> Result<Block, Error> {
+    let block = <Block as BlockT>::new(header, extrinsics);
+
+    check_extrinsics_root(&block)?;
+
+    Ok(block)
+}
 
 /// Used as parameter to [`BlockBuilderProvider`] to express if proof recording should be enabled.
 ///
@@ -47,14 +170,25 @@ use std::collections::VecDeque;
 pub enum RecordProof {
     /// `Yes`, record a proof.
     Yes,
+    /// `Yes`, record a proof, but fail as soon as its estimated encoded size exceeds the given
+    /// number of bytes.
+    YesWithSizeLimit(usize),
     /// `No`, don't record any proof.
     No,
 }
 
 impl RecordProof {
-    /// Returns if `Self` == `Yes`.
+    /// Returns if `Self` == `Yes` or `YesWithSizeLimit`.
     pub fn yes(&self) -> bool {
-        matches!(self, Self::Yes)
+        matches!(self, Self::Yes | Self::YesWithSizeLimit(_))
+    }
+
+    /// Returns the proof size limit if any.
+    pub fn size_limit(&self) -> Option<usize> {
+        match self {
+            Self::YesWithSizeLimit(size_limit) => Some(*size_limit),
+            Self::Yes | Self::No => None,
+        }
     }
 }
 
@@ -77,6 +211,74 @@ impl From<bool> for RecordProof {
     }
 }
 
+/// Options of the storage proof recorded while building a block, nothing is excluded from the
+/// proof by default.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProofRecordingOptions {
+    /// Keys whose values are excluded from the recorded proof.
+    ///
+    /// NOTE: only values stored as separate trie nodes, i.e. values larger than 32 bytes with
+    /// state version 1, can be excluded, smaller values are inlined into their trie node.
+    pub ignore_keys: Vec<Vec<u8>>,
+}
+
+impl ProofRecordingOptions {
+    /// Options that exclude the runtime code, i.e. the value of `:code`, from the proof.
+    pub fn exclude_runtime_code() -> Self {
+        Self {
+            ignore_keys: vec![sp_core::storage::well_known_keys::CODE.to_vec()],
+        }
+    }
+}
+
+/// Used as parameter to [`BlockBuilder::build`] to express how extrinsics that fail to apply
+/// are handled.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BuildMode {
+    /// Log the failure and continue with the next extrinsic.
+    BestEffort,
+    /// Abort the build with the error of the first extrinsic that failed to apply.
+    Strict,
+}
+
+/// Will return [`BuildMode::BestEffort`] as default value.
+impl Default for BuildMode {
+    #[inline]
+    fn default() -> Self {
+        Self::BestEffort
+    }
+}
+
+/// Used as parameter to [`BlockBuilder::prepare_storage_changes_before`] to express how
+/// extrinsics that fail to apply while replaying the block are handled.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OnApplyFailure {
+    /// Skip extrinsics that are invalid, like [`BlockBuilder::build`] does, only abort on
+    /// runtime api errors.
+    Skip,
+    /// Abort on any error.
+    Abort,
+}
+
+/// Execution profile of a single extrinsic, collected when profiling is enabled with
+/// [`BlockBuilder::enable_profiling`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtrinsicProfile {
+    /// Index of the extrinsic in the block.
+    pub index: usize,
+    /// Wall-clock time spent applying the extrinsic.
+    pub duration: Duration,
+    /// Growth of the estimated encoded size of the storage proof caused by the extrinsic.
+    ///
+    /// Always `0` when proof recording is disabled.
+    pub proof_delta: usize,
+    /// Encoded size of the extrinsic.
+    pub encoded_size: usize,
+}
+
+/// Key of the storage proof intermediate set by [`BuiltBlock::into_import_params`].
+pub const STORAGE_PROOF_INTERMEDIATE_KEY: &[u8] = b"storage_proof";
+
 /// A block that was build by [`BlockBuilder`] plus some additional data.
 ///
 /// This additional data includes the `storage_changes`, these changes can be applied to the
@@ -90,6 +292,13 @@ pub struct BuiltBlock<Block: BlockT> {
     pub storage_changes: StorageChanges<Block>,
     /// An optional proof that was recorded while building the block.
     pub proof: Option<StorageProof>,
+    /// State root of the parent block, the state the `proof` was recorded against.
+    pub parent_state_root: Block::Hash,
+    /// Per-extrinsic execution profiles, only set when profiling was enabled.
+    pub extrinsic_profiles: Option<Vec<ExtrinsicProfile>>,
+    /// Extrinsics left out of the block because the block size limit was reached, in the order
+    /// they were given to the [`BlockBuilder`].
+    pub remaining_extrinsics: VecDeque<Block::Extrinsic>,
 }
 
 impl<Block: BlockT> BuiltBlock<Block> {
@@ -97,6 +306,94 @@ impl<Block: BlockT> BuiltBlock<Block> {
     pub fn into_inner(self) -> (Block, StorageChanges<Block>, Option<StorageProof>) {
         (self.block, self.storage_changes, self.proof)
     }
+
+    /// Returns the state root after applying the block.
+    pub fn transaction_storage_root(&self) -> Block::Hash {
+        self.storage_changes.transaction_storage_root
+    }
+
+    /// Verify the recorded proof, if any, by creating a proof check backend from it against
+    /// [`BuiltBlock::parent_state_root`], the proof covers the reads from the parent state.
+    ///
+    /// Returns [`BlockBuilderError::InvalidProof`] if the storage root is not part of the proof.
+    pub fn verify_proof(&self) -> Result<(), Error> {
+        let Some(proof) = &self.proof else {
+            return Ok(());
+        };
+
+        sp_state_machine::create_proof_check_backend::<HashingFor<Block>>(
+            self.parent_state_root,
+            proof.clone(),
+        )
+        .map(|_backend| ())
+        .map_err(|error| BlockBuilderError::InvalidProof(error.to_string()).into())
+    }
+
+    /// Convert into [`BlockImportParams`] that import the block by applying its storage changes.
+    ///
+    /// The proof, if any, is attached as intermediate under [`STORAGE_PROOF_INTERMEDIATE_KEY`].
+    /// The post-digests are left empty since the header returned by the runtime already contains
+    /// all digest items and the block is not sealed.
+    pub fn into_import_params(
+        self,
+        origin: BlockOrigin,
+        fork_choice: ForkChoiceStrategy,
+    ) -> BlockImportParams<Block> {
+        let (header, body) = self.block.deconstruct();
+
+        let mut import_params = BlockImportParams::new(origin, header);
+        import_params.body = Some(body);
+        import_params.state_action =
+            StateAction::ApplyChanges(sc_consensus::StorageChanges::Changes(self.storage_changes));
+        import_params.fork_choice = Some(fork_choice);
+        if let Some(proof) = self.proof {
+            import_params.insert_intermediate(STORAGE_PROOF_INTERMEDIATE_KEY, proof);
+        }
+
+        import_params
+    }
+}
+
+/// A block executed and finalized by the [`BlockBuilder`], kept until the block is built.
+struct FinalizedBlock<Block: BlockT> {
+    header: Block::Header,
+    extrinsic_profiles: Option<Vec<ExtrinsicProfile>>,
+    /// Error of the first extrinsic that failed to apply while executing with
+    /// [`BuildMode::BestEffort`], returned if the block is then built with [`BuildMode::Strict`].
+    first_failure: Option<Error>,
+    /// The storage changes of the block, collected on first use.
+    storage_changes: Option<StorageChanges<Block>>,
+}
+
+/// Weight limit of the block and the runtime api calls used to meter the weight of the applied
+/// extrinsics, set with [`BlockBuilder::set_block_weight_limit`].
+struct WeightMeter<Block: BlockT, Api> {
+    limit: Weight,
+    base_block: Weight,
+    base_extrinsic: Weight,
+    /// Returns the pre-dispatch weight of the extrinsic.
+    extrinsic_weight: fn(&Api, Block::Hash, &Block::Extrinsic) -> Result<Weight, ApiError>,
+    /// Returns the weight consumed by the block, `None` if the runtime doesn't support it.
+    block_weight: Option<fn(&Api, Block::Hash) -> Result<Weight, ApiError>>,
+}
+
+impl<Block: BlockT, Api> WeightMeter<Block, Api> {
+    /// Returns the actual weight consumed by the block so far if the runtime supports it.
+    fn consumed_weight(&self, api: &Api, at: Block::Hash) -> Result<Option<Weight>, ApiError> {
+        self.block_weight
+            .map(|block_weight| block_weight(api, at))
+            .transpose()
+    }
+
+    /// Returns the pre-dispatch weight of the extrinsic including the base extrinsic weight.
+    fn pre_dispatch_weight(
+        &self,
+        api: &Api,
+        at: Block::Hash,
+        xt: &Block::Extrinsic,
+    ) -> Result<Weight, ApiError> {
+        Ok((self.extrinsic_weight)(api, at, xt)?.saturating_add(self.base_extrinsic))
+    }
 }
 
 /// Block builder provider
@@ -117,6 +414,27 @@ where
         parent: &BlockId<Block>,
         inherent_digests: Digest,
         record_proof: R,
+    ) -> sp_blockchain::Result<BlockBuilder<Block, RA, B>> {
+        self.new_block_at_with_extrinsics(
+            parent,
+            inherent_digests,
+            record_proof,
+            VecDeque::new(),
+            None,
+        )
+    }
+
+    /// Create a new block, built on top of `parent`, seeded with the given `extrinsics`.
+    ///
+    /// When `inherent_data` is `Some`, the inherent extrinsics created by the runtime are
+    /// prepended to `extrinsics`.
+    fn new_block_at_with_extrinsics<R: Into<RecordProof>>(
+        &self,
+        parent: &BlockId<Block>,
+        inherent_digests: Digest,
+        record_proof: R,
+        extrinsics: VecDeque<Block::Extrinsic>,
+        inherent_data: Option<InherentData>,
     ) -> sp_blockchain::Result<BlockBuilder<Block, RA, B>>;
 
     /// Create a new block, built on the head of the chain.
@@ -126,14 +444,219 @@ where
     ) -> sp_blockchain::Result<BlockBuilder<Block, RA, B>>;
 }
 
+/// [`BlockBuilderProvider`] building blocks with a client and its backend, the inherents are
+/// only created from the inherent data explicitly given to
+/// [`BlockBuilderProvider::new_block_at_with_extrinsics`].
+pub struct ClientBlockBuilderProvider<Client, Backend> {
+    client: Arc<Client>,
+    backend: Arc<Backend>,
+}
+
+impl<Client, Backend> Clone for ClientBlockBuilderProvider<Client, Backend> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            backend: self.backend.clone(),
+        }
+    }
+}
+
+impl<Client, Backend> ClientBlockBuilderProvider<Client, Backend> {
+    /// Create a new provider building blocks with `client` and `backend`.
+    pub fn new(client: Arc<Client>, backend: Arc<Backend>) -> Self {
+        Self { client, backend }
+    }
+}
+
+impl<Block, Client, Backend> BlockBuilderProvider<Backend, Block, Client>
+    for ClientBlockBuilderProvider<Client, Backend>
+where
+    Block: BlockT,
+    Client: HeaderBackend<Block> + ProvideRuntimeApi<Block>,
+    Client::Api: BlockBuilderApi<Block> + ApiExt<Block>,
+    Backend: backend::Backend<Block>,
+{
+    fn new_block_at_with_extrinsics<R: Into<RecordProof>>(
+        &self,
+        parent: &BlockId<Block>,
+        inherent_digests: Digest,
+        record_proof: R,
+        extrinsics: VecDeque<Block::Extrinsic>,
+        inherent_data: Option<InherentData>,
+    ) -> sp_blockchain::Result<BlockBuilder<Block, Client, Backend>> {
+        BlockBuilder::new(
+            &*self.client,
+            self.client.expect_block_hash_from_id(parent)?,
+            self.client.expect_block_number_from_id(parent)?,
+            record_proof.into(),
+            inherent_digests,
+            &*self.backend,
+            extrinsics,
+            inherent_data,
+        )
+    }
+
+    fn new_block(
+        &self,
+        inherent_digests: Digest,
+    ) -> sp_blockchain::Result<BlockBuilder<Block, Client, Backend>> {
+        let best_hash = self.client.info().best_hash;
+        self.new_block_at(&BlockId::Hash(best_hash), inherent_digests, RecordProof::No)
+    }
+}
+
+/// [`BlockBuilderProvider`] that creates the inherents of every new block from the
+/// [`InherentData`] returned by `inherent_data_provider`.
+///
+/// `inherent_data_provider` is called with the hash of the parent block, its result is cached and
+/// reused for the following blocks built on the same parent. The inherent data explicitly passed
+/// to [`BlockBuilderProvider::new_block_at_with_extrinsics`] takes precedence.
+///
+/// ```
+/// use domain_block_builder::{BlockBuilderProvider, BuildMode, InherentDataBlockBuilderProvider};
+/// use sp_inherents::InherentData;
+/// use std::sync::Arc;
+/// use substrate_test_runtime_client::runtime::Block;
+/// use substrate_test_runtime_client::{DefaultTestClientBuilderExt, TestClientBuilderExt};
+///
+/// let (client, backend) =
+///     substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+/// let provider = InherentDataBlockBuilderProvider::<Block, _, _, _>::new(
+///     Arc::new(client),
+///     backend,
+///     |_parent_hash| Ok(InherentData::new()),
+/// );
+///
+/// let built_block = provider
+///     .new_block(Default::default())?
+///     .build(BuildMode::Strict)?;
+/// # Ok::<(), sp_blockchain::Error>(())
+/// ```
+pub struct InherentDataBlockBuilderProvider<Block: BlockT, Client, Backend, F> {
+    client: Arc<Client>,
+    backend: Arc<Backend>,
+    inherent_data_provider: F,
+    /// The inherent data last returned by `inherent_data_provider` and the parent hash it was
+    /// created for.
+    cached_inherent_data: Mutex<Option<(Block::Hash, InherentData)>>,
+}
+
+impl<Block, Client, Backend, F> InherentDataBlockBuilderProvider<Block, Client, Backend, F>
+where
+    Block: BlockT,
+    F: Fn(Block::Hash) -> sp_blockchain::Result<InherentData>,
+{
+    /// Create a new provider building blocks with `client` and `backend`.
+    pub fn new(client: Arc<Client>, backend: Arc<Backend>, inherent_data_provider: F) -> Self {
+        Self {
+            client,
+            backend,
+            inherent_data_provider,
+            cached_inherent_data: Mutex::new(None),
+        }
+    }
+
+    /// Returns the inherent data for a block built on top of `parent_hash`, from the cache if
+    /// possible.
+    fn inherent_data(&self, parent_hash: Block::Hash) -> sp_blockchain::Result<InherentData> {
+        let mut cached_inherent_data = self
+            .cached_inherent_data
+            .lock()
+            .expect("Lock is never held across a panic; qed");
+
+        if let Some((cached_parent_hash, inherent_data)) = cached_inherent_data.as_ref() {
+            if *cached_parent_hash == parent_hash {
+                return Ok(inherent_data.clone());
+            }
+        }
+
+        let inherent_data = (self.inherent_data_provider)(parent_hash)?;
+        *cached_inherent_data = Some((parent_hash, inherent_data.clone()));
+
+        Ok(inherent_data)
+    }
+}
+
+impl<Block, Client, Backend, F> BlockBuilderProvider<Backend, Block, Client>
+    for InherentDataBlockBuilderProvider<Block, Client, Backend, F>
+where
+    Block: BlockT,
+    Client: HeaderBackend<Block> + ProvideRuntimeApi<Block>,
+    Client::Api: BlockBuilderApi<Block> + ApiExt<Block>,
+    Backend: backend::Backend<Block>,
+    F: Fn(Block::Hash) -> sp_blockchain::Result<InherentData>,
+{
+    fn new_block_at_with_extrinsics<R: Into<RecordProof>>(
+        &self,
+        parent: &BlockId<Block>,
+        inherent_digests: Digest,
+        record_proof: R,
+        extrinsics: VecDeque<Block::Extrinsic>,
+        inherent_data: Option<InherentData>,
+    ) -> sp_blockchain::Result<BlockBuilder<Block, Client, Backend>> {
+        let parent_hash = self.client.expect_block_hash_from_id(parent)?;
+        let inherent_data = match inherent_data {
+            Some(inherent_data) => inherent_data,
+            None => self.inherent_data(parent_hash)?,
+        };
+
+        BlockBuilder::new(
+            &*self.client,
+            parent_hash,
+            self.client.expect_block_number_from_id(parent)?,
+            record_proof.into(),
+            inherent_digests,
+            &*self.backend,
+            extrinsics,
+            Some(inherent_data),
+        )
+    }
+
+    fn new_block(
+        &self,
+        inherent_digests: Digest,
+    ) -> sp_blockchain::Result<BlockBuilder<Block, Client, Backend>> {
+        let best_hash = self.client.info().best_hash;
+        self.new_block_at(&BlockId::Hash(best_hash), inherent_digests, RecordProof::No)
+    }
+}
+
 /// Utility for building new (valid) blocks from a stream of extrinsics.
 pub struct BlockBuilder<'a, Block: BlockT, A: ProvideRuntimeApi<Block>, B> {
     extrinsics: VecDeque<Block::Extrinsic>,
     api: ApiRef<'a, A::Api>,
+    api_provider: &'a A,
+    /// The header the block was initialized with.
+    header: Block::Header,
+    record_proof: RecordProof,
+    proof_recording_options: ProofRecordingOptions,
     parent_hash: Block::Hash,
     backend: &'a B,
     /// The estimated size of the block header.
     estimated_header_size: usize,
+    /// Whether to collect an [`ExtrinsicProfile`] for each applied extrinsic.
+    profiling: bool,
+    /// The block size limit set with [`BlockBuilder::set_block_size_limit`].
+    block_size_limit: Option<usize>,
+    /// The proof size budget set with [`BlockBuilder::set_max_proof_size`].
+    max_proof_size: Option<usize>,
+    /// Number of inherent extrinsics at the front of `extrinsics`.
+    inherents_count: usize,
+    weight_meter: Option<WeightMeter<Block, A::Api>>,
+    /// Extrinsics that did not fit into the block.
+    remaining_extrinsics: VecDeque<Block::Extrinsic>,
+    /// Time spent initializing the block, reported once the block is built.
+    initialization_time: Duration,
+    metrics: Option<BlockBuilderMetrics>,
+    /// Called with the index and execution time of each applied extrinsic.
+    extrinsic_observer: Option<Box<dyn Fn(usize, Duration) + Send>>,
+    /// Called on each fresh runtime api instance created to execute the block, set with
+    /// [`BlockBuilder::set_api_setup`].
+    api_setup: Option<Box<dyn Fn(&mut A::Api) + Send + 'a>>,
+    /// Whether any extrinsic was executed on `api`, set from `&self` methods as well.
+    extrinsics_executed: Cell<bool>,
+    /// Set once the block is finalized so that it is not executed and finalized twice.
+    finalized: Option<FinalizedBlock<Block>>,
 }
 
 impl<'a, Block, A, B> BlockBuilder<'a, Block, A, B>
@@ -156,9 +679,56 @@ where
         record_proof: RecordProof,
         inherent_digests: Digest,
         backend: &'a B,
+        extrinsics: VecDeque<Block::Extrinsic>,
+        maybe_inherent_data: Option<InherentData>,
+    ) -> Result<Self, Error> {
+        let runtime_api = api.runtime_api();
+        Self::with_api(
+            api,
+            runtime_api,
+            false,
+            parent_hash,
+            parent_number,
+            record_proof,
+            inherent_digests,
+            backend,
+            extrinsics,
+            maybe_inherent_data,
+        )
+    }
+
+    /// Create a new instance of builder like [`BlockBuilder::new`], but on the given runtime
+    /// `api` instance instead of a fresh one from `api_provider`, so that the extensions
+    /// registered on it are available while executing the block.
+    ///
+    /// When `block_initialized` is `true` the caller already initialized the block on `api`,
+    /// with the header built from `parent_hash`, `parent_number` and `inherent_digests`, and
+    /// enabled proof recording as requested by `record_proof`, thus neither is done again.
+    ///
+    /// NOTE: `api_provider` is still used for the work that needs a fresh runtime api instance,
+    /// like re-initializing the block in [`BlockBuilder::push_digest_item`], use
+    /// [`BlockBuilder::set_api_setup`] to register the same extensions on these instances.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_api(
+        api_provider: &'a A,
+        mut api: ApiRef<'a, A::Api>,
+        block_initialized: bool,
+        parent_hash: Block::Hash,
+        parent_number: NumberFor<Block>,
+        record_proof: RecordProof,
+        inherent_digests: Digest,
+        backend: &'a B,
         mut extrinsics: VecDeque<Block::Extrinsic>,
-        maybe_inherent_data: Option<sp_inherents::InherentData>,
+        maybe_inherent_data: Option<InherentData>,
     ) -> Result<Self, Error> {
+        let _enter = tracing::debug_span!(
+            "block_builder_new",
+            ?parent_hash,
+            extrinsics = extrinsics.len()
+        )
+        .entered();
+        let start = Instant::now();
+
         let header = <<Block as BlockT>::Header as HeaderT>::new(
             parent_number + One::one(),
             Default::default(),
@@ -169,16 +739,18 @@ where
 
         let estimated_header_size = header.encoded_size();
 
-        let mut api = api.runtime_api();
+        if !block_initialized {
+            if record_proof.yes() {
+                api.record_proof();
+            }
 
-        if record_proof.yes() {
-            api.record_proof();
+            api.initialize_block(parent_hash, &header)?;
         }
 
-        api.initialize_block(parent_hash, &header)?;
-
+        let mut inherents_count = 0;
         if let Some(inherent_data) = maybe_inherent_data {
             let inherent_extrinsics = Self::create_inherents(parent_hash, &api, inherent_data)?;
+            inherents_count = inherent_extrinsics.len();
             for inherent_extrinsic in inherent_extrinsics {
                 extrinsics.push_front(inherent_extrinsic)
             }
@@ -188,160 +760,2058 @@ where
             parent_hash,
             extrinsics,
             api,
+            api_provider,
+            header,
+            record_proof,
+            proof_recording_options: ProofRecordingOptions::default(),
             backend,
             estimated_header_size,
+            profiling: false,
+            block_size_limit: None,
+            max_proof_size: None,
+            inherents_count,
+            weight_meter: None,
+            remaining_extrinsics: VecDeque::new(),
+            initialization_time: start.elapsed(),
+            metrics: None,
+            extrinsic_observer: None,
+            api_setup: None,
+            extrinsics_executed: Cell::new(false),
+            finalized: None,
         })
     }
 
-    /// Execute the block's list of extrinsics.
-    fn execute_extrinsics(&self) -> Result<(), Error> {
-        let parent_hash = self.parent_hash;
+    /// Report the durations of the block building stages and the number of rejected extrinsics
+    /// to the given `metrics`.
+    pub fn set_metrics(&mut self, metrics: BlockBuilderMetrics) {
+        self.metrics = Some(metrics);
+    }
 
-        for (index, xt) in self.extrinsics.iter().enumerate() {
-            let res = self.api.execute_in_transaction(|api| {
-                match api.apply_extrinsic(parent_hash, xt.clone()) {
-                    Ok(Ok(_)) => TransactionOutcome::Commit(Ok(())),
-                    Ok(Err(tx_validity)) => TransactionOutcome::Rollback(Err(
-                        ApplyExtrinsicFailed::Validity(tx_validity).into(),
-                    )),
-                    Err(e) => TransactionOutcome::Rollback(Err(Error::from(e))),
-                }
-            });
+    /// Call `extrinsic_observer` with the index and execution time of each extrinsic applied
+    /// while building the block, including the extrinsics that failed to apply.
+    pub fn set_extrinsic_observer(
+        &mut self,
+        extrinsic_observer: impl Fn(usize, Duration) + Send + 'static,
+    ) {
+        self.extrinsic_observer = Some(Box::new(extrinsic_observer));
+    }
 
-            if let Err(e) = res {
-                tracing::debug!("Apply extrinsic at index {index} failed: {e}");
-            }
+    /// Call `api_setup` on each fresh runtime api instance the builder creates to execute the
+    /// block after construction, typically to register the same extensions as on the `api`
+    /// given to [`BlockBuilder::with_api`].
+    pub fn set_api_setup(&mut self, api_setup: impl Fn(&mut A::Api) + Send + 'a) {
+        self.api_setup = Some(Box::new(api_setup));
+    }
+
+    /// Returns a fresh runtime api instance with [`BlockBuilder::set_api_setup`] applied.
+    fn fresh_runtime_api(&self) -> ApiRef<'a, A::Api> {
+        let mut api = self.api_provider.runtime_api();
+        if let Some(api_setup) = &self.api_setup {
+            api_setup(&mut api);
         }
+        api
+    }
 
-        Ok(())
+    /// Limit the estimated size of the block, excluding the storage proof, to `block_size_limit`
+    /// bytes.
+    ///
+    /// The extrinsics are applied in order until the next one would exceed the limit, it and all
+    /// the following extrinsics are left out of the block and returned in
+    /// [`BuiltBlock::remaining_extrinsics`] in their original relative order, so the highest
+    /// priority leftovers can be picked up first by the next block. An extrinsic that failed to
+    /// apply stays in the block body and thus counts towards the limit. The inherents created by
+    /// the block builder are always applied.
+    pub fn set_block_size_limit(&mut self, block_size_limit: usize) {
+        self.block_size_limit = Some(block_size_limit);
     }
 
-    fn collect_storage_changes(&self) -> Result<StorageChanges<Block>, Error> {
-        let state = self.backend.state_at(self.parent_hash)?;
-        let parent_hash = self.parent_hash;
-        self.api
-            .into_storage_changes(&state, parent_hash)
-            .map_err(Error::StorageChanges)
+    /// Limit the estimated encoded size of the recorded storage proof to `max_proof_size` bytes,
+    /// only used when proof recording is enabled.
+    ///
+    /// Unlike [`RecordProof::YesWithSizeLimit`], reaching the budget doesn't fail the build: the
+    /// extrinsics are applied in order until the proof size reaches the budget, all the following
+    /// extrinsics are left out of the block and returned in [`BuiltBlock::remaining_extrinsics`].
+    /// The inherents created by the block builder are always applied.
+    pub fn set_max_proof_size(&mut self, max_proof_size: usize) {
+        self.max_proof_size = Some(max_proof_size);
     }
 
-    /// Returns the state before executing the extrinsic at given extrinsic index.
-    pub fn prepare_storage_changes_before(
-        &self,
-        extrinsic_index: usize,
-    ) -> Result<StorageChanges<Block>, Error> {
-        for (index, xt) in self.extrinsics.iter().enumerate() {
-            if index == extrinsic_index {
-                return self.collect_storage_changes();
-            }
+    /// Set the options of the storage proof, only used when proof recording is enabled.
+    pub fn set_proof_recording_options(&mut self, proof_recording_options: ProofRecordingOptions) {
+        self.proof_recording_options = proof_recording_options;
+    }
 
-            // TODO: rethink what to do if an error occurs when executing the transaction.
-            self.api.execute_in_transaction(|api| {
-                let res = api.apply_extrinsic(self.parent_hash, xt.clone());
-                match res {
-                    Ok(Ok(_)) => TransactionOutcome::Commit(Ok(())),
-                    Ok(Err(tx_validity)) => TransactionOutcome::Rollback(Err(
-                        ApplyExtrinsicFailed::Validity(tx_validity).into(),
-                    )),
-                    Err(e) => TransactionOutcome::Rollback(Err(Error::from(e))),
-                }
-            })?;
+    /// Push an additional digest item into the header of the block.
+    ///
+    /// The item is placed after the inherent digests given to [`BlockBuilder::new`] and the items
+    /// pushed before it, digest items deposited by the runtime while building the block come
+    /// after all of them.
+    ///
+    /// The digest is an input of `initialize_block`, so the block is re-initialized on a fresh
+    /// runtime api instance with the updated header and [`BlockBuilder::set_api_setup`] applied.
+    /// Only allowed before any extrinsic was executed, otherwise returns
+    /// [`BlockBuilderError::ExtrinsicsAlreadyExecuted`].
+    pub fn push_digest_item(&mut self, item: DigestItem) -> Result<(), Error> {
+        self.ensure_extrinsics_not_executed()?;
+
+        let start = Instant::now();
+        self.header.digest_mut().push(item);
+        self.estimated_header_size = self.header.encoded_size();
+        // The cached header was finalized without the new item.
+        self.finalized = None;
+
+        let mut api = self.fresh_runtime_api();
+
+        if self.record_proof.yes() {
+            api.record_proof();
         }
 
-        Err(Error::Execution(Box::new(format!(
-            "Invalid extrinsic index, got: {}, max: {}",
-            extrinsic_index,
-            self.extrinsics.len()
-        ))))
+        api.initialize_block(self.parent_hash, &self.header)?;
+
+        self.api = api;
+        self.initialization_time += start.elapsed();
+
+        Ok(())
     }
 
-    /// Returns the state before finalizing the block.
-    pub fn prepare_storage_changes_before_finalize_block(
-        &self,
-    ) -> Result<StorageChanges<Block>, Error> {
-        self.execute_extrinsics()?;
-        self.collect_storage_changes()
+    /// Enable the collection of an [`ExtrinsicProfile`] for each extrinsic applied while
+    /// building the block, the profiles are returned in [`BuiltBlock::extrinsic_profiles`].
+    pub fn enable_profiling(&mut self) {
+        self.profiling = true;
     }
 
-    /// Consume the builder to build a valid `Block` containing all pushed extrinsics.
+    /// Returns the extrinsics that will be applied when the block is built, including the
+    /// inherent extrinsics created during construction.
     ///
-    /// Returns the build `Block`, the changes to the storage and an optional `StorageProof`
-    /// supplied by `self.api`, combined as [`BuiltBlock`].
-    /// The storage proof will be `Some(_)` when proof recording was enabled.
-    pub fn build(mut self) -> Result<BuiltBlock<Block>, Error> {
-        self.execute_extrinsics()?;
-
-        let header = self.api.finalize_block(self.parent_hash)?;
+    /// NOTE: there is no way to fork a builder for speculative execution, the runtime api
+    /// instance owns the overlay of the initialized block and can neither be cloned nor shared,
+    /// so each attempt requires constructing a new [`BlockBuilder`].
+    pub fn pending_extrinsics(&self) -> &VecDeque<Block::Extrinsic> {
+        &self.extrinsics
+    }
 
-        debug_assert_eq!(
-            header.extrinsics_root().clone(),
-            HashingFor::<Block>::ordered_trie_root(
-                self.extrinsics.iter().map(Encode::encode).collect(),
-                sp_core::storage::StateVersion::V1
-            ),
-        );
+    /// Remove the queued extrinsic at `index`, the following extrinsics shift down by one, which
+    /// is reflected by the indices given to [`BlockBuilder::prepare_storage_changes_before`].
+    ///
+    /// Only allowed before any extrinsic was executed, otherwise returns
+    /// [`BlockBuilderError::ExtrinsicsAlreadyExecuted`].
+    pub fn remove_extrinsic(&mut self, index: usize) -> Result<Block::Extrinsic, Error> {
+        self.ensure_extrinsics_not_executed()?;
 
-        let proof = self.api.extract_proof();
+        let xt = self
+            .extrinsics
+            .remove(index)
+            .ok_or_else(|| self.invalid_extrinsic_index(index))?;
 
-        let storage_changes = self.collect_storage_changes()?;
+        if index < self.inherents_count {
+            self.inherents_count -= 1;
+        }
 
-        Ok(BuiltBlock {
-            block: <Block as BlockT>::new(header, self.extrinsics.into()),
-            storage_changes,
-            proof,
-        })
+        Ok(xt)
     }
 
-    /// Create the inherents for the block.
+    /// Replace the queued extrinsic at `index` with `xt`, returning the replaced extrinsic.
     ///
-    /// Returns the inherents created by the runtime or an error if something failed.
-    pub fn create_inherents(
-        parent_hash: Block::Hash,
-        api: &ApiRef<A::Api>,
-        inherent_data: sp_inherents::InherentData,
-    ) -> Result<VecDeque<Block::Extrinsic>, Error> {
-        let exts = api
-            .execute_in_transaction(move |api| {
-                // `create_inherents` should not change any state, to ensure this we always rollback
-                // the transaction.
-                TransactionOutcome::Rollback(api.inherent_extrinsics(parent_hash, inherent_data))
-            })
-            .map_err(|e| Error::Application(Box::new(e)))?;
-        Ok(VecDeque::from(exts))
+    /// Only allowed before any extrinsic was executed, otherwise returns
+    /// [`BlockBuilderError::ExtrinsicsAlreadyExecuted`].
+    pub fn replace_extrinsic(
+        &mut self,
+        index: usize,
+        xt: Block::Extrinsic,
+    ) -> Result<Block::Extrinsic, Error> {
+        self.ensure_extrinsics_not_executed()?;
+
+        match self.extrinsics.get_mut(index) {
+            Some(queued_xt) => Ok(std::mem::replace(queued_xt, xt)),
+            None => Err(self.invalid_extrinsic_index(index)),
+        }
     }
 
-    /// Estimate the size of the block in the current state.
+    fn ensure_extrinsics_not_executed(&self) -> Result<(), Error> {
+        if self.extrinsics_executed.get() {
+            return Err(BlockBuilderError::ExtrinsicsAlreadyExecuted.into());
+        }
+
+        Ok(())
+    }
+
+    /// Apply the given extrinsic in a storage transaction, the transaction is rolled back if
+    /// the extrinsic failed to apply.
+    fn apply_extrinsic_in_transaction(
+        api: &ApiRef<'a, A::Api>,
+        parent_hash: Block::Hash,
+        xt: &Block::Extrinsic,
+    ) -> Result<(), Error> {
+        api.execute_in_transaction(|api| match api.apply_extrinsic(parent_hash, xt.clone()) {
+            Ok(Ok(_)) => TransactionOutcome::Commit(Ok(())),
+            Ok(Err(tx_validity)) => TransactionOutcome::Rollback(Err(
+                ApplyExtrinsicFailed::Validity(tx_validity).into(),
+            )),
+            Err(e) => TransactionOutcome::Rollback(Err(Error::from(e))),
+        })
+    }
+
+    /// Execute the block's list of extrinsics.
+    ///
+    /// Extrinsics that failed to apply are rolled back but kept in the list, so the block body is
+    /// the list of extrinsics given to the builder. Extrinsics that don't fit into the
+    /// block size or weight limit or come after the proof size budget was reached are moved to
+    /// `remaining_extrinsics`.
+    ///
+    /// Returns the profile of each executed extrinsic if profiling is enabled, along with the
+    /// error of the first extrinsic that failed to apply with [`BuildMode::BestEffort`].
+    #[allow(clippy::type_complexity)]
+    fn execute_extrinsics(
+        &mut self,
+        build_mode: BuildMode,
+    ) -> Result<(Option<Vec<ExtrinsicProfile>>, Option<Error>), Error> {
+        let _enter = tracing::debug_span!(
+            "execute_extrinsics",
+            parent_hash = ?self.parent_hash,
+            extrinsics = self.extrinsics.len()
+        )
+        .entered();
+        let execution_start = Instant::now();
+        self.extrinsics_executed.set(true);
+
+        let mut failed_extrinsics = Vec::new();
+        let mut first_failure = None;
+        let mut extrinsic_profiles = self
+            .profiling
+            .then(|| Vec::with_capacity(self.extrinsics.len()));
+        let mut block_size = self.estimated_header_size;
+        let mut block_weight = match &self.weight_meter {
+            Some(weight_meter) => weight_meter
+                .consumed_weight(&self.api, self.parent_hash)?
+                .unwrap_or(weight_meter.base_block),
+            None => Weight::zero(),
+        };
+        let mut exhausted_at = None;
+
+        for (index, xt) in self.extrinsics.iter().enumerate() {
+            let encoded_size = xt.encoded_size();
+            if let Some(block_size_limit) = self.block_size_limit {
+                if index >= self.inherents_count && block_size + encoded_size > block_size_limit {
+                    exhausted_at.replace(index);
+                    break;
+                }
+            }
+
+            let pre_dispatch_weight = match &self.weight_meter {
+                Some(weight_meter) => {
+                    let pre_dispatch_weight =
+                        weight_meter.pre_dispatch_weight(&self.api, self.parent_hash, xt)?;
+                    if index >= self.inherents_count
+                        && block_weight
+                            .saturating_add(pre_dispatch_weight)
+                            .any_gt(weight_meter.limit)
+                    {
+                        exhausted_at.replace(index);
+                        break;
+                    }
+                    Some(pre_dispatch_weight)
+                }
+                None => None,
+            };
+
+            let proof_size_before = self.estimate_proof_size();
+            let start = Instant::now();
+
+            let res = Self::apply_extrinsic_in_transaction(&self.api, self.parent_hash, xt);
+
+            if let Some(extrinsic_profiles) = extrinsic_profiles.as_mut() {
+                extrinsic_profiles.push(ExtrinsicProfile {
+                    index,
+                    duration: start.elapsed(),
+                    proof_delta: self.estimate_proof_size().saturating_sub(proof_size_before),
+                    encoded_size,
+                });
+            }
+
+            if let Some(extrinsic_observer) = &self.extrinsic_observer {
+                extrinsic_observer(index, start.elapsed());
+            }
+
+            if let (Some(weight_meter), Some(pre_dispatch_weight)) =
+                (&self.weight_meter, pre_dispatch_weight)
+            {
+                // Prefer the actual post-dispatch weight tracked by the runtime.
+                block_weight = match weight_meter.consumed_weight(&self.api, self.parent_hash)? {
+                    Some(consumed_weight) => consumed_weight,
+                    None if res.is_ok() => block_weight.saturating_add(pre_dispatch_weight),
+                    None => block_weight,
+                };
+            }
+
+            if let Err(e) = res {
+                if build_mode == BuildMode::Strict {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.inc_rejected_extrinsics(1);
+                    }
+                    return Err(e);
+                }
+                tracing::debug!("Apply extrinsic at index {index} failed: {e}");
+                failed_extrinsics.push(index);
+                first_failure.get_or_insert(e);
+            }
+
+            // A failed extrinsic is rolled back but stays in the block body, so it takes up
+            // block space all the same.
+            block_size += encoded_size;
+
+            if let Some(size_limit) = self.record_proof.size_limit() {
+                let proof_size = self.estimate_proof_size();
+                if proof_size > size_limit {
+                    return Err(BlockBuilderError::ProofSizeLimitExceeded {
+                        extrinsic_index: index,
+                        proof_size,
+                        size_limit,
+                    }
+                    .into());
+                }
+            }
+
+            if let Some(max_proof_size) = self.max_proof_size {
+                if self.record_proof.yes()
+                    && index >= self.inherents_count
+                    && self.estimate_proof_size() >= max_proof_size
+                {
+                    exhausted_at.replace(index + 1);
+                    break;
+                }
+            }
+        }
+
+        if let Some(index) = exhausted_at {
+            tracing::debug!(
+                "Block size or weight limit or proof size budget reached, {} extrinsics left out",
+                self.extrinsics.len() - index
+            );
+            self.remaining_extrinsics = self.extrinsics.split_off(index);
+        }
+
+        if let Some(metrics) = &self.metrics {
+            metrics.observe_extrinsics_execution_time(execution_start.elapsed());
+            metrics.inc_rejected_extrinsics(failed_extrinsics.len());
+        }
+
+        tracing::debug!(
+            applied = self.extrinsics.len() - failed_extrinsics.len(),
+            failed = failed_extrinsics.len(),
+            remaining = self.remaining_extrinsics.len(),
+            ?block_weight,
+            "Executed extrinsics"
+        );
+
+        Ok((extrinsic_profiles, first_failure))
+    }
+
+    fn estimate_proof_size(&self) -> usize {
+        self.api
+            .proof_recorder()
+            .map(|pr| pr.estimate_encoded_size())
+            .unwrap_or(0)
+    }
+
+    fn collect_storage_changes(&self) -> Result<StorageChanges<Block>, Error> {
+        let _enter = tracing::debug_span!(
+            "collect_storage_changes",
+            parent_hash = ?self.parent_hash,
+            extrinsics = self.extrinsics.len()
+        )
+        .entered();
+
+        let state = self.backend.state_at(self.parent_hash)?;
+        let parent_hash = self.parent_hash;
+        self.api
+            .into_storage_changes(&state, parent_hash)
+            .map_err(Error::StorageChanges)
+    }
+
+    /// Replay the given extrinsic, handling a failure as requested by `on_apply_failure`.
+    fn replay_extrinsic(
+        api: &ApiRef<'a, A::Api>,
+        parent_hash: Block::Hash,
+        index: usize,
+        xt: &Block::Extrinsic,
+        on_apply_failure: OnApplyFailure,
+    ) -> Result<(), Error> {
+        match Self::apply_extrinsic_in_transaction(api, parent_hash, xt) {
+            Err(Error::ApplyExtrinsicFailed(ApplyExtrinsicFailed::Validity(e)))
+                if on_apply_failure == OnApplyFailure::Skip =>
+            {
+                tracing::debug!("Skipping invalid extrinsic at index {index}: {e:?}");
+                Ok(())
+            }
+            res => res,
+        }
+    }
+
+    /// Returns the state before executing the extrinsic at given extrinsic index.
+    ///
+    /// The `on_apply_failure` decides whether an invalid extrinsic before `extrinsic_index` is
+    /// skipped, which reproduces the state of a block built with [`BuildMode::BestEffort`], or
+    /// aborts with an error.
+    pub fn prepare_storage_changes_before(
+        &self,
+        extrinsic_index: usize,
+        on_apply_failure: OnApplyFailure,
+    ) -> Result<StorageChanges<Block>, Error> {
+        self.extrinsics_executed.set(true);
+
+        for (index, xt) in self.extrinsics.iter().enumerate() {
+            if index == extrinsic_index {
+                return self.collect_storage_changes();
+            }
+
+            Self::replay_extrinsic(&self.api, self.parent_hash, index, xt, on_apply_failure)?;
+        }
+
+        Err(self.invalid_extrinsic_index(extrinsic_index))
+    }
+
+    /// Same as [`BlockBuilder::prepare_storage_changes_before`], but also returns the state root
+    /// after applying the returned storage changes, i.e. the intermediate state root before the
+    /// extrinsic at given extrinsic index.
+    pub fn prepare_storage_changes_before_with_root(
+        &self,
+        extrinsic_index: usize,
+        on_apply_failure: OnApplyFailure,
+    ) -> Result<(StorageChanges<Block>, Block::Hash), Error> {
+        let storage_changes =
+            self.prepare_storage_changes_before(extrinsic_index, on_apply_failure)?;
+        let root = storage_changes.transaction_storage_root;
+        Ok((storage_changes, root))
+    }
+
+    /// Returns the state before executing the extrinsic at each of the given extrinsic indices,
+    /// replaying the extrinsics from the start of the block for every index.
+    ///
+    /// The indices are deduplicated and sorted, the result is ordered by extrinsic index. Apply
+    /// failures are handled as in [`BlockBuilder::prepare_storage_changes_before`].
+    ///
+    /// NOTE: this is not a single pass, the cost is the same as calling
+    /// [`BlockBuilder::prepare_storage_changes_before`] for each index. Collecting the storage
+    /// changes drains the overlay of the runtime api instance and the overlay can neither be
+    /// cloned nor restored, so the execution can not continue on the same instance after a
+    /// snapshot is taken. The state before the smallest index is collected on the builder's own
+    /// instance, the state before every other index is rebuilt on a fresh instance with
+    /// [`BlockBuilder::set_api_setup`] applied, thus the same extensions are available to every
+    /// replay.
+    pub fn replay_storage_changes_before_indices(
+        &self,
+        extrinsic_indices: &[usize],
+        on_apply_failure: OnApplyFailure,
+    ) -> Result<Vec<(usize, StorageChanges<Block>)>, Error> {
+        let mut extrinsic_indices = extrinsic_indices.to_vec();
+        extrinsic_indices.sort_unstable();
+        extrinsic_indices.dedup();
+
+        if let Some(&max_index) = extrinsic_indices.last() {
+            if max_index >= self.extrinsics.len() {
+                return Err(self.invalid_extrinsic_index(max_index));
+            }
+        }
+
+        let mut storage_changes = Vec::with_capacity(extrinsic_indices.len());
+        let mut extrinsic_indices = extrinsic_indices.into_iter();
+
+        if let Some(extrinsic_index) = extrinsic_indices.next() {
+            storage_changes.push((
+                extrinsic_index,
+                self.prepare_storage_changes_before(extrinsic_index, on_apply_failure)?,
+            ));
+        }
+
+        for extrinsic_index in extrinsic_indices {
+            let api = self.fresh_runtime_api();
+            api.initialize_block(self.parent_hash, &self.header)?;
+
+            for (index, xt) in self.extrinsics.iter().enumerate().take(extrinsic_index) {
+                Self::replay_extrinsic(&api, self.parent_hash, index, xt, on_apply_failure)?;
+            }
+
+            let state = self.backend.state_at(self.parent_hash)?;
+            let changes = api
+                .into_storage_changes(&state, self.parent_hash)
+                .map_err(Error::StorageChanges)?;
+            storage_changes.push((extrinsic_index, changes));
+        }
+
+        Ok(storage_changes)
+    }
+
+    fn invalid_extrinsic_index(&self, extrinsic_index: usize) -> Error {
+        Error::Execution(Box::new(format!(
+            "Invalid extrinsic index, got: {}, max: {}",
+            extrinsic_index,
+            self.extrinsics.len()
+        )))
+    }
+
+    /// Returns the state before finalizing the block.
+    pub fn prepare_storage_changes_before_finalize_block(
+        &mut self,
+    ) -> Result<StorageChanges<Block>, Error> {
+        self.execute_extrinsics(BuildMode::BestEffort)?;
+        self.collect_storage_changes()
+    }
+
+    /// Remove the values of the ignored keys at the parent state from the given `proof`.
+    fn exclude_ignored_values(&self, proof: StorageProof) -> Result<StorageProof, Error> {
+        if self.proof_recording_options.ignore_keys.is_empty() {
+            return Ok(proof);
+        }
+
+        let state = self.backend.state_at(self.parent_hash)?;
+        let mut ignored_values = Vec::new();
+        for key in &self.proof_recording_options.ignore_keys {
+            if let Some(value) = state
+                .storage(key)
+                .map_err(|e| Error::Storage(e.to_string()))?
+            {
+                ignored_values.push(value);
+            }
+        }
+
+        Ok(StorageProof::new(
+            proof
+                .into_iter_nodes()
+                .filter(|node| !ignored_values.contains(node)),
+        ))
+    }
+
+    /// Finalize the block, reporting the initialization and finalization durations to the
+    /// metrics if any.
+    fn finalize_block(&self) -> Result<Block::Header, Error> {
+        let _enter = tracing::debug_span!(
+            "finalize_block",
+            parent_hash = ?self.parent_hash,
+            extrinsics = self.extrinsics.len()
+        )
+        .entered();
+        let start = Instant::now();
+
+        let header = self.api.finalize_block(self.parent_hash)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.observe_block_initialization_time(self.initialization_time);
+            metrics.observe_block_finalization_time(start.elapsed());
+        }
+
+        Ok(header)
+    }
+
+    /// Execute the extrinsics and finalize the block, unless it is already finalized.
+    fn ensure_finalized(
+        &mut self,
+        build_mode: BuildMode,
+    ) -> Result<&mut FinalizedBlock<Block>, Error> {
+        if self.finalized.is_none() {
+            let (extrinsic_profiles, first_failure) = self.execute_extrinsics(build_mode)?;
+            let header = self.finalize_block()?;
+            self.finalized = Some(FinalizedBlock {
+                header,
+                extrinsic_profiles,
+                first_failure,
+                storage_changes: None,
+            });
+        }
+
+        Ok(self
+            .finalized
+            .as_mut()
+            .expect("Block is finalized above; qed"))
+    }
+
+    /// Take the finalized block along with its storage changes, which are collected unless they
+    /// are already cached.
+    fn take_finalized(&mut self) -> Result<(FinalizedBlock<Block>, StorageChanges<Block>), Error> {
+        let mut finalized = self
+            .finalized
+            .take()
+            .expect("Only called once the block is finalized; qed");
+        let storage_changes = match finalized.storage_changes.take() {
+            Some(storage_changes) => storage_changes,
+            None => self.collect_storage_changes()?,
+        };
+
+        Ok((finalized, storage_changes))
+    }
+
+    /// Execute the extrinsics with [`BuildMode::BestEffort`] and finalize the block, then return
+    /// the state root of the finalized header.
+    ///
+    /// The state root is computed by the runtime in `finalize_block`, so this is much cheaper
+    /// than collecting the storage changes. The finalized block is kept, a following build
+    /// reuses it instead of executing and finalizing the block again. If an extrinsic failed to
+    /// apply, building with [`BuildMode::Strict`] afterwards returns its error.
+    pub fn storage_root_after_finalize(&mut self) -> Result<Block::Hash, Error> {
+        Ok(*self
+            .ensure_finalized(BuildMode::BestEffort)?
+            .header
+            .state_root())
+    }
+
+    /// Execute the extrinsics with [`BuildMode::BestEffort`] and finalize the block like
+    /// [`BlockBuilder::storage_root_after_finalize`], then return its storage changes.
+    ///
+    /// The storage changes are collected once and cached, a following build reuses them.
+    pub fn storage_changes_after_finalize(&mut self) -> Result<&StorageChanges<Block>, Error> {
+        self.ensure_finalized(BuildMode::BestEffort)?;
+        let (mut finalized, storage_changes) = self.take_finalized()?;
+        finalized.storage_changes = Some(storage_changes);
+
+        Ok(self
+            .finalized
+            .insert(finalized)
+            .storage_changes
+            .as_ref()
+            .expect("Storage changes are set above; qed"))
+    }
+
+    /// Consume the builder to build a valid `Block` containing all pushed extrinsics.
+    ///
+    /// Returns the build `Block`, the changes to the storage and an optional `StorageProof`
+    /// supplied by `self.api`, combined as [`BuiltBlock`].
+    /// The storage proof will be `Some(_)` when proof recording was enabled.
+    ///
+    /// The `build_mode` decides whether an extrinsic that fails to apply aborts the build.
+    pub fn build(mut self, build_mode: BuildMode) -> Result<BuiltBlock<Block>, Error> {
+        let finalized = self.ensure_finalized(build_mode)?;
+        // The block was finalized with `BuildMode::BestEffort` before.
+        if build_mode == BuildMode::Strict {
+            if let Some(e) = finalized.first_failure.take() {
+                return Err(e);
+            }
+        }
+        let (
+            FinalizedBlock {
+                header,
+                extrinsic_profiles,
+                ..
+            },
+            storage_changes,
+        ) = self.take_finalized()?;
+
+        debug_assert_eq!(
+            header.extrinsics_root().clone(),
+            HashingFor::<Block>::ordered_trie_root(
+                self.extrinsics.iter().map(Encode::encode).collect(),
+                sp_core::storage::StateVersion::V1
+            ),
+        );
+
+        let proof = self
+            .api
+            .extract_proof()
+            .map(|proof| self.exclude_ignored_values(proof))
+            .transpose()?;
+        let parent_state_root = *self
+            .backend
+            .blockchain()
+            .expect_header(self.parent_hash)?
+            .state_root();
+
+        Ok(BuiltBlock {
+            block: <Block as BlockT>::new(header, self.extrinsics.into()),
+            storage_changes,
+            proof,
+            parent_state_root,
+            extrinsic_profiles,
+            remaining_extrinsics: self.remaining_extrinsics,
+        })
+    }
+
+    /// Consume the builder to build a valid `Block` like [`BlockBuilder::build`] with the default
+    /// [`BuildMode`], then verify the extrinsics root in the header against the block body.
+    ///
+    /// Unlike the check in [`BlockBuilder::build`], which is only done in debug builds, the root is
+    /// always verified and [`BlockBuilderError::ExtrinsicsRootMismatch`] is returned if it differs.
+    pub fn build_checked(self) -> Result<BuiltBlock<Block>, Error> {
+        let built_block = self.build(BuildMode::default())?;
+
+        check_extrinsics_root(&built_block.block)?;
+
+        Ok(built_block)
+    }
+
+    /// Consume the builder to execute and finalize the block like [`BlockBuilder::build`] with
+    /// [`BuildMode::BestEffort`], but only return the storage changes and the optional proof
+    /// without assembling the `Block`.
+    pub fn build_state_only(
+        mut self,
+    ) -> Result<(StorageChanges<Block>, Option<StorageProof>), Error> {
+        self.ensure_finalized(BuildMode::BestEffort)?;
+        let (_, storage_changes) = self.take_finalized()?;
+
+        let proof = self
+            .api
+            .extract_proof()
+            .map(|proof| self.exclude_ignored_values(proof))
+            .transpose()?;
+
+        Ok((storage_changes, proof))
+    }
+
+    /// Consume the builder to build a valid `Block` like [`BlockBuilder::build`] with the default
+    /// [`BuildMode`], appending the `post_digests` to the finalized header.
+    ///
+    /// The post-digests are not seen by the runtime, so the storage changes and proof are the
+    /// same as for [`BlockBuilder::build`], only the header (and thus the block hash) differs.
+    pub fn build_with_post_digests(
+        self,
+        post_digests: Vec<DigestItem>,
+    ) -> Result<BuiltBlock<Block>, Error> {
+        let mut built_block = self.build(BuildMode::default())?;
+
+        let (mut header, extrinsics) = built_block.block.deconstruct();
+        for item in post_digests {
+            header.digest_mut().push(item);
+        }
+        built_block.block = <Block as BlockT>::new(header, extrinsics);
+
+        Ok(built_block)
+    }
+
+    /// Consume the builder to build a valid `Block` like [`BlockBuilder::build`], then run the
+    /// runtime's `check_inherents` against the built block, the same check performed on import.
+    ///
+    /// Returns [`BlockBuilderError::InherentCheckFailed`] if the runtime rejects any inherent.
+    pub fn build_with_inherent_check(
+        self,
+        inherent_data: InherentData,
+    ) -> Result<BuiltBlock<Block>, Error> {
+        let parent_hash = self.parent_hash;
+        let api_provider = self.api_provider;
+        let built_block = self.build(BuildMode::default())?;
+
+        let check_inherents_result = api_provider.runtime_api().check_inherents(
+            parent_hash,
+            built_block.block.clone(),
+            inherent_data,
+        )?;
+
+        if !check_inherents_result.ok() {
+            let identifiers = check_inherents_result
+                .into_errors()
+                .map(|(identifier, _)| identifier)
+                .collect();
+            return Err(BlockBuilderError::InherentCheckFailed(identifiers).into());
+        }
+
+        Ok(built_block)
+    }
+
+    /// Create the inherents for the block.
+    ///
+    /// Returns the inherents created by the runtime or [`BlockBuilderError::InherentCreationFailed`]
+    /// with the identifiers of the given inherent data if something failed. The runtime is not
+    /// called when `inherent_data` is empty, no inherent is created in that case.
+    pub fn create_inherents(
+        parent_hash: Block::Hash,
+        api: &ApiRef<A::Api>,
+        inherent_data: InherentData,
+    ) -> Result<VecDeque<Block::Extrinsic>, Error> {
+        let identifiers = inherent_identifiers(&inherent_data);
+        if identifiers.is_empty() {
+            return Ok(VecDeque::new());
+        }
+        let exts = api
+            .execute_in_transaction(move |api| {
+                // `create_inherents` should not change any state, to ensure this we always rollback
+                // the transaction.
+                TransactionOutcome::Rollback(api.inherent_extrinsics(parent_hash, inherent_data))
+            })
+            .map_err(|error| BlockBuilderError::InherentCreationFailed { identifiers, error })?;
+        Ok(VecDeque::from(exts))
+    }
+
+    /// Returns the estimated encoded size of the block header.
+    pub fn estimated_header_size(&self) -> usize {
+        self.estimated_header_size
+    }
+
+    /// Returns the estimated encoded size of the block body in the current state.
+    pub fn estimated_body_size(&self) -> usize {
+        self.extrinsics.encoded_size()
+    }
+
+    /// Estimate the size of the block in the current state.
     ///
     /// If `include_proof` is `true`, the estimated size of the storage proof will be added
     /// to the estimation.
     pub fn estimate_block_size(&self, include_proof: bool) -> usize {
-        let size = self.estimated_header_size + self.extrinsics.encoded_size();
+        let size = self.estimated_header_size() + self.estimated_body_size();
+
+        if include_proof {
+            size + self.estimate_proof_size()
+        } else {
+            size
+        }
+    }
+}
+
+impl<'a, Block, A, B> BlockBuilder<'a, Block, A, B>
+where
+    Block: BlockT,
+    A: ProvideRuntimeApi<Block> + 'a,
+    A::Api: BlockBuilderApi<Block> + ApiExt<Block> + DomainCoreApi<Block>,
+    B: backend::Backend<Block>,
+{
+    /// Returns the version of the `DomainCoreApi` supported by the runtime.
+    fn domain_core_api_version(&self) -> Result<u32, Error> {
+        Ok(self
+            .api
+            .api_version::<dyn DomainCoreApi<Block>>(self.parent_hash)?
+            .unwrap_or_default())
+    }
+
+    /// Returns whether the runtime supports querying the weight consumed by the block.
+    fn supports_block_weight(&self) -> Result<bool, Error> {
+        Ok(self.domain_core_api_version()? >= 2)
+    }
+
+    /// Returns the `BlockWeights` of the runtime, runtimes that don't expose them yet use the
+    /// default domain block weights.
+    fn runtime_block_weights(&self) -> Result<BlockWeights, Error> {
+        if self.domain_core_api_version()? >= 3 {
+            Ok(self.api.block_weights(self.parent_hash)?)
+        } else {
+            Ok(block_weights())
+        }
+    }
+
+    /// Limit the weight of the block to `block_weight_limit`, capped by the normal dispatch class
+    /// limit of the runtime's `BlockWeights`.
+    ///
+    /// The extrinsics are applied in order until the pre-dispatch weight of the next one would
+    /// exceed the limit, it and all the following extrinsics are left out of the block and
+    /// returned in [`BuiltBlock::remaining_extrinsics`]. The weight of the applied extrinsics is
+    /// tracked with the actual post-dispatch weight reported by the runtime, or with their
+    /// pre-dispatch weight if the runtime doesn't support querying the block weight. The
+    /// inherents created by the block builder are always applied, their weight still counts
+    /// towards the limit for the following extrinsics.
+    pub fn set_block_weight_limit(&mut self, block_weight_limit: Weight) -> Result<(), Error> {
+        let weights = self.runtime_block_weights()?;
+        let normal_weights = weights.get(DispatchClass::Normal);
+        let limit = match normal_weights.max_total {
+            Some(max_total) => block_weight_limit.min(max_total),
+            None => block_weight_limit,
+        };
+
+        let block_weight: Option<fn(&A::Api, Block::Hash) -> Result<Weight, ApiError>> =
+            if self.supports_block_weight()? {
+                Some(|api, at| api.block_weight(at))
+            } else {
+                None
+            };
+
+        self.weight_meter = Some(WeightMeter {
+            limit,
+            base_block: weights.base_block,
+            base_extrinsic: normal_weights.base_extrinsic,
+            extrinsic_weight: |api, at, xt| api.extrinsic_weight(at, xt),
+            block_weight,
+        });
+
+        Ok(())
+    }
+
+    /// Estimate the weight of the block with all the pending extrinsics applied, using their
+    /// pre-dispatch weight.
+    ///
+    /// The weight consumed by the block initialization is included if the runtime supports
+    /// querying the block weight, otherwise the base block weight is used instead.
+    pub fn estimate_block_weight(&self) -> Result<Weight, Error> {
+        let weights = self.runtime_block_weights()?;
+        let base_extrinsic = weights.get(DispatchClass::Normal).base_extrinsic;
+
+        let mut block_weight = if self.supports_block_weight()? {
+            self.api.block_weight(self.parent_hash)?
+        } else {
+            weights.base_block
+        };
+
+        for xt in &self.extrinsics {
+            block_weight = block_weight
+                .saturating_add(self.api.extrinsic_weight(self.parent_hash, xt)?)
+                .saturating_add(base_extrinsic);
+        }
+
+        Ok(block_weight)
+    }
+}
+
+impl<'a, Block, A, B> BlockBuilder<'a, Block, A, B>
+where
+    Block: BlockT,
+    A: ProvideRuntimeApi<Block> + Sync + 'a,
+    A::Api: BlockBuilderApi<Block> + ApiExt<Block> + TaggedTransactionQueue<Block>,
+    B: backend::Backend<Block>,
+{
+    /// Validate the pending extrinsics in parallel on `thread_pool` against the parent state and
+    /// return the indices of the statically invalid ones, i.e. those with a bad signature.
+    ///
+    /// The validation is purely advisory, the extrinsics are left untouched, so the block body
+    /// stays the same, and each worker uses its own runtime api instance so the state of the
+    /// block is not touched either. The statically invalid extrinsics are logged and counted in
+    /// the metrics, extrinsics whose validity depends on the state of the block being built, like
+    /// those with a future nonce, and the ones that failed to validate with a runtime api error
+    /// are not reported.
+    pub fn pre_validate_extrinsics(&self, thread_pool: &ThreadPool) -> Vec<usize> {
+        let api_provider = self.api_provider;
+        let parent_hash = self.parent_hash;
+
+        let statically_invalid = thread_pool.install(|| {
+            self.extrinsics
+                .par_iter()
+                .map_init(
+                    || api_provider.runtime_api(),
+                    |runtime_api, xt| {
+                        matches!(
+                            runtime_api.validate_transaction(
+                                parent_hash,
+                                TransactionSource::InBlock,
+                                xt.clone(),
+                                parent_hash,
+                            ),
+                            Ok(Err(TransactionValidityError::Invalid(
+                                InvalidTransaction::BadProof | InvalidTransaction::BadSigner
+                            )))
+                        )
+                    },
+                )
+                .collect::<Vec<_>>()
+        });
+
+        let statically_invalid = statically_invalid
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, is_invalid)| is_invalid.then_some(index))
+            .collect::<Vec<_>>();
+
+        if !statically_invalid.is_empty() {
+            tracing::debug!(
+                "Pre-validation found statically invalid extrinsics at indices {statically_invalid:?}"
+            );
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.inc_statically_invalid_extrinsics(statically_invalid.len());
+        }
+
+        statically_invalid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sc_client_api::Backend as _;
+    use sc_consensus::BlockImport;
+    use sp_blockchain::HeaderBackend;
+    use sp_core::Blake2Hasher;
+    use std::cell::Cell;
+    // TODO: Remove `substrate_test_runtime_client` dependency for faster build time
+    use std::collections::VecDeque;
+    use substrate_test_runtime_client::runtime::{Block, Extrinsic, Transfer};
+    use substrate_test_runtime_client::{
+        AccountKeyring, Backend, DefaultTestClientBuilderExt, TestClient, TestClientBuilderExt,
+    };
+
+    fn transfer(nonce: u64) -> Extrinsic {
+        Transfer {
+            from: AccountKeyring::Alice.into(),
+            to: AccountKeyring::Bob.into(),
+            amount: 1,
+            nonce,
+        }
+        .into_unchecked_extrinsic()
+    }
+
+    fn new_block_builder<'a>(
+        client: &'a TestClient,
+        backend: &'a Backend,
+        extrinsics: VecDeque<Extrinsic>,
+    ) -> BlockBuilder<'a, Block, TestClient, Backend> {
+        BlockBuilder::new(
+            client,
+            client.info().best_hash,
+            client.info().best_number,
+            RecordProof::No,
+            Default::default(),
+            backend,
+            extrinsics,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn inherent_data_block_builder_provider_creates_inherents() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+        let client = Arc::new(client);
+        let best_hash = client.info().best_hash;
+
+        let inherent_data_requests = Cell::new(0);
+        let provider = InherentDataBlockBuilderProvider::<Block, _, _, _>::new(
+            client.clone(),
+            backend.clone(),
+            |parent_hash| {
+                assert_eq!(parent_hash, best_hash);
+                inherent_data_requests.set(inherent_data_requests.get() + 1);
+                Ok(InherentData::new())
+            },
+        );
+
+        let block = provider
+            .new_block(Default::default())
+            .unwrap()
+            .build(BuildMode::Strict)
+            .unwrap()
+            .block;
+
+        let expected_block = BlockBuilder::new(
+            &*client,
+            best_hash,
+            client.info().best_number,
+            RecordProof::No,
+            Default::default(),
+            &*backend,
+            VecDeque::new(),
+            Some(InherentData::new()),
+        )
+        .unwrap()
+        .build(BuildMode::Strict)
+        .unwrap()
+        .block;
+        assert_eq!(block, expected_block);
+        assert_eq!(inherent_data_requests.get(), 1);
+
+        // The inherent data is reused for the blocks built on the same parent
+        provider.new_block(Default::default()).unwrap();
+        assert_eq!(inherent_data_requests.get(), 1);
+    }
+
+    #[test]
+    fn empty_inherent_data_creates_no_inherents() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+        let extrinsics = VecDeque::from(vec![transfer(0)]);
+
+        let new_block = |inherent_data| {
+            BlockBuilder::new(
+                &client,
+                client.info().best_hash,
+                client.info().best_number,
+                RecordProof::No,
+                Default::default(),
+                &backend,
+                extrinsics.clone(),
+                inherent_data,
+            )
+            .unwrap()
+        };
+
+        let block_builder = new_block(Some(InherentData::new()));
+        assert_eq!(block_builder.inherents_count, 0);
+        assert_eq!(block_builder.pending_extrinsics(), &extrinsics);
+
+        let block = block_builder.build(BuildMode::Strict).unwrap().block;
+        let expected_block = new_block(None).build(BuildMode::Strict).unwrap().block;
+        assert_eq!(block, expected_block);
+        assert_eq!(block.extrinsics, Vec::from(extrinsics));
+    }
+
+    #[test]
+    fn replay_storage_changes_before_indices_matches_single_index() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+        let extrinsics: VecDeque<_> = (0..5).map(transfer).collect();
+
+        let storage_changes = new_block_builder(&client, &backend, extrinsics.clone())
+            .replay_storage_changes_before_indices(&[3, 1, 3, 4], OnApplyFailure::Abort)
+            .unwrap();
+
+        assert_eq!(
+            storage_changes
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            vec![1, 3, 4]
+        );
+
+        for (index, changes) in storage_changes {
+            let expected = new_block_builder(&client, &backend, extrinsics.clone())
+                .prepare_storage_changes_before(index, OnApplyFailure::Abort)
+                .unwrap();
+
+            assert_eq!(changes.main_storage_changes, expected.main_storage_changes);
+            assert_eq!(
+                changes.transaction_storage_root,
+                expected.transaction_storage_root
+            );
+        }
+
+        assert!(new_block_builder(&client, &backend, extrinsics)
+            .replay_storage_changes_before_indices(&[1, 5], OnApplyFailure::Abort)
+            .is_err());
+    }
+
+    #[test]
+    fn replay_storage_changes_before_indices_applies_api_setup() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+        let extrinsics: VecDeque<_> = (0..5).map(transfer).collect();
+
+        let setup_calls = Arc::new(Mutex::new(0));
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics);
+        block_builder.set_api_setup({
+            let setup_calls = setup_calls.clone();
+            move |_api| *setup_calls.lock().unwrap() += 1
+        });
+
+        block_builder
+            .replay_storage_changes_before_indices(&[1, 3, 4], OnApplyFailure::Abort)
+            .unwrap();
+
+        // The smallest index is collected on the builder's own instance.
+        assert_eq!(*setup_calls.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn prepare_storage_changes_before_with_root() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+        let extrinsics: VecDeque<_> = (0..3).map(transfer).collect();
+
+        let (storage_changes, root) = new_block_builder(&client, &backend, extrinsics.clone())
+            .prepare_storage_changes_before_with_root(2, OnApplyFailure::Abort)
+            .unwrap();
+        assert_eq!(root, storage_changes.transaction_storage_root);
+
+        let expected = new_block_builder(&client, &backend, extrinsics)
+            .prepare_storage_changes_before(2, OnApplyFailure::Abort)
+            .unwrap();
+        assert_eq!(root, expected.transaction_storage_root);
+    }
+
+    #[test]
+    fn prepare_storage_changes_before_skips_invalid_extrinsic() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        // The nonce of the second transfer is in the future thus it fails the validity check.
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(5), transfer(1)]);
+
+        let storage_changes = new_block_builder(&client, &backend, extrinsics.clone())
+            .prepare_storage_changes_before(2, OnApplyFailure::Skip)
+            .unwrap();
+
+        // The state the block built from the first two extrinsics ends up with.
+        let mut block_builder = new_block_builder(
+            &client,
+            &backend,
+            extrinsics.iter().take(2).cloned().collect(),
+        );
+        let expected = block_builder
+            .prepare_storage_changes_before_finalize_block()
+            .unwrap();
+
+        assert_eq!(
+            storage_changes.main_storage_changes,
+            expected.main_storage_changes
+        );
+        assert_eq!(
+            storage_changes.transaction_storage_root,
+            expected.transaction_storage_root
+        );
+
+        let res = new_block_builder(&client, &backend, extrinsics)
+            .prepare_storage_changes_before(2, OnApplyFailure::Abort);
+
+        assert!(matches!(
+            res,
+            Err(Error::ApplyExtrinsicFailed(ApplyExtrinsicFailed::Validity(
+                _
+            )))
+        ));
+    }
+
+    #[test]
+    fn build_best_effort_keeps_failed_extrinsic() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        // The nonce of the second transfer is in the future thus it fails the validity check.
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(5), transfer(1)]);
+
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics.clone());
+        let state_root = block_builder.storage_root_after_finalize().unwrap();
+
+        // The failed extrinsic is rolled back but stays in the block body.
+        assert_eq!(block_builder.pending_extrinsics(), &extrinsics);
+        assert!(block_builder.remaining_extrinsics.is_empty());
+
+        let (storage_changes, _) = block_builder.build_state_only().unwrap();
+        assert_eq!(storage_changes.transaction_storage_root, state_root);
+
+        // The failed extrinsic leaves no trace in the state of the block.
+        let (expected, _) = new_block_builder(
+            &client,
+            &backend,
+            VecDeque::from(vec![transfer(0), transfer(1)]),
+        )
+        .build_state_only()
+        .unwrap();
+        assert_eq!(
+            storage_changes.main_storage_changes,
+            expected.main_storage_changes
+        );
+    }
+
+    #[test]
+    fn metrics_count_rejected_extrinsics() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let registry = substrate_prometheus_endpoint::Registry::new();
+        let metrics = BlockBuilderMetrics::new(&registry).unwrap();
+
+        // The nonce of the second transfer is in the future thus it fails the validity check.
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(5), transfer(1)]);
+
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics);
+        block_builder.set_metrics(metrics);
+        block_builder.build_state_only().unwrap();
+
+        let metric_families = registry.gather();
+        let rejected_extrinsics = metric_families
+            .iter()
+            .find(|family| family.get_name() == "domain_block_builder_rejected_extrinsics")
+            .unwrap();
+        assert_eq!(
+            rejected_extrinsics.get_metric()[0]
+                .get_counter()
+                .get_value(),
+            1.0
+        );
+
+        let block_finalization_time = metric_families
+            .iter()
+            .find(|family| family.get_name() == "domain_block_builder_block_finalization_time")
+            .unwrap();
+        assert_eq!(
+            block_finalization_time.get_metric()[0]
+                .get_histogram()
+                .get_sample_count(),
+            1
+        );
+    }
+
+    #[test]
+    fn duplicated_extrinsics_are_kept() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(0), transfer(1)]);
+
+        let block_builder = new_block_builder(&client, &backend, extrinsics.clone());
+        assert_eq!(block_builder.pending_extrinsics(), &extrinsics);
+    }
+
+    #[test]
+    fn build_strict_aborts_on_failed_extrinsic() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        // The nonce of the second transfer is in the future thus it fails the validity check.
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(5), transfer(1)]);
+
+        let res = new_block_builder(&client, &backend, extrinsics).build(BuildMode::Strict);
+
+        assert!(matches!(
+            res,
+            Err(Error::ApplyExtrinsicFailed(ApplyExtrinsicFailed::Validity(
+                _
+            )))
+        ));
+    }
+
+    #[test]
+    fn build_collects_extrinsic_profiles_when_enabled() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(1)]);
+        let built_block = new_block_builder(&client, &backend, extrinsics.clone())
+            .build(BuildMode::Strict)
+            .unwrap();
+        assert!(built_block.extrinsic_profiles.is_none());
+
+        let mut block_builder = BlockBuilder::new(
+            &client,
+            client.info().best_hash,
+            client.info().best_number,
+            RecordProof::Yes,
+            Default::default(),
+            &backend,
+            extrinsics.clone(),
+            None,
+        )
+        .unwrap();
+        block_builder.enable_profiling();
+
+        let extrinsic_profiles = block_builder
+            .build(BuildMode::Strict)
+            .unwrap()
+            .extrinsic_profiles
+            .unwrap();
+
+        assert_eq!(extrinsic_profiles.len(), extrinsics.len());
+        for (index, (profile, xt)) in extrinsic_profiles.iter().zip(extrinsics.iter()).enumerate() {
+            assert_eq!(profile.index, index);
+            assert_eq!(profile.encoded_size, xt.encoded_size());
+        }
+        // The first extrinsic touches storage that is not part of the proof yet.
+        assert!(extrinsic_profiles[0].proof_delta > 0);
+    }
+
+    #[test]
+    fn extrinsic_observer_is_called_for_each_extrinsic() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(1), transfer(2)]);
+        let observed = Arc::new(Mutex::new(Vec::new()));
+
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics.clone());
+        block_builder.set_extrinsic_observer({
+            let observed = observed.clone();
+            move |index, _duration| observed.lock().unwrap().push(index)
+        });
+        block_builder.build(BuildMode::Strict).unwrap();
+
+        assert_eq!(
+            *observed.lock().unwrap(),
+            (0..extrinsics.len()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn build_checked_detects_extrinsics_root_mismatch() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(1)]);
+        let built_block = new_block_builder(&client, &backend, extrinsics.clone())
+            .build_checked()
+            .unwrap();
+        assert_eq!(
+            built_block.block.extrinsics().to_vec(),
+            Vec::from(extrinsics)
+        );
+
+        // Pretend the runtime finalized the header with the root of a different body.
+        let (header, mut body) = built_block.block.deconstruct();
+        body.pop();
+        let tampered_block = Block::new(header, body);
+
+        assert!(matches!(
+            check_extrinsics_root(&tampered_block),
+            Err(BlockBuilderError::ExtrinsicsRootMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn with_api_on_initialized_block_matches_new() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let parent_hash = client.info().best_hash;
+        let parent_number = client.info().best_number;
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(1)]);
+
+        let api = client.runtime_api();
+        let header = <Block as BlockT>::Header::new(
+            parent_number + 1,
+            Default::default(),
+            Default::default(),
+            parent_hash,
+            Default::default(),
+        );
+        api.initialize_block(parent_hash, &header).unwrap();
+
+        let block = BlockBuilder::with_api(
+            &client,
+            api,
+            true,
+            parent_hash,
+            parent_number,
+            RecordProof::No,
+            Default::default(),
+            &backend,
+            extrinsics.clone(),
+            None,
+        )
+        .unwrap()
+        .build(BuildMode::Strict)
+        .unwrap()
+        .block;
 
-        if include_proof {
-            size + self
-                .api
-                .proof_recorder()
-                .map(|pr| pr.estimate_encoded_size())
-                .unwrap_or(0)
-        } else {
-            size
+        let expected = new_block_builder(&client, &backend, extrinsics)
+            .build(BuildMode::Strict)
+            .unwrap()
+            .block;
+
+        assert_eq!(block, expected);
+    }
+
+    #[test]
+    fn build_with_post_digests_appends_to_header() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(1)]);
+        let post_digest = DigestItem::Seal(*b"test", vec![1, 2, 3]);
+
+        let built_block = new_block_builder(&client, &backend, extrinsics.clone())
+            .build_with_post_digests(vec![post_digest.clone()])
+            .unwrap();
+        let expected = new_block_builder(&client, &backend, extrinsics)
+            .build(BuildMode::default())
+            .unwrap();
+
+        let mut expected_header = expected.block.header().clone();
+        expected_header.digest_mut().push(post_digest.clone());
+
+        assert_eq!(built_block.block.header(), &expected_header);
+        assert_eq!(
+            built_block.block.header().digest().logs().last(),
+            Some(&post_digest)
+        );
+        assert_ne!(
+            built_block.block.header().hash(),
+            expected.block.header().hash()
+        );
+        assert_eq!(built_block.block.extrinsics(), expected.block.extrinsics());
+        assert_eq!(
+            built_block.storage_changes.main_storage_changes,
+            expected.storage_changes.main_storage_changes
+        );
+        assert_eq!(
+            built_block.transaction_storage_root(),
+            expected.transaction_storage_root()
+        );
+    }
+
+    #[test]
+    fn max_proof_size_leaves_out_remaining_extrinsics() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics: VecDeque<_> = (0..4).map(transfer).collect();
+        let new_recording_block_builder = || {
+            BlockBuilder::new(
+                &client,
+                client.info().best_hash,
+                client.info().best_number,
+                RecordProof::Yes,
+                Default::default(),
+                &backend,
+                extrinsics.clone(),
+                None,
+            )
+            .unwrap()
+        };
+
+        // The proof recorded while initializing the block is already above the budget, so only
+        // the first extrinsic is applied.
+        let mut block_builder = new_recording_block_builder();
+        block_builder.set_max_proof_size(1);
+        let built_block = block_builder.build(BuildMode::Strict).unwrap();
+        assert_eq!(built_block.block.extrinsics(), &[transfer(0)]);
+        assert_eq!(
+            built_block.remaining_extrinsics,
+            extrinsics.iter().skip(1).cloned().collect::<VecDeque<_>>()
+        );
+
+        let mut block_builder = new_recording_block_builder();
+        block_builder.set_max_proof_size(usize::MAX);
+        let built_block = block_builder.build(BuildMode::Strict).unwrap();
+        assert_eq!(
+            built_block.block.extrinsics(),
+            extrinsics.iter().cloned().collect::<Vec<_>>()
+        );
+        assert!(built_block.remaining_extrinsics.is_empty());
+
+        // The budget is ignored without proof recording.
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics.clone());
+        block_builder.set_max_proof_size(1);
+        let built_block = block_builder.build(BuildMode::Strict).unwrap();
+        assert_eq!(built_block.block.extrinsics().len(), extrinsics.len());
+    }
+
+    #[test]
+    fn storage_root_after_finalize_matches_built_block() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics: VecDeque<_> = (0..5).map(transfer).collect();
+
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics.clone());
+        let state_root = block_builder.storage_root_after_finalize().unwrap();
+        // Calling it again doesn't execute the block again.
+        assert_eq!(
+            block_builder.storage_root_after_finalize().unwrap(),
+            state_root
+        );
+        let built_block = block_builder.build(BuildMode::BestEffort).unwrap();
+
+        let expected = new_block_builder(&client, &backend, extrinsics)
+            .build(BuildMode::BestEffort)
+            .unwrap();
+
+        assert_eq!(state_root, *expected.block.header().state_root());
+        assert_eq!(built_block.block, expected.block);
+        assert_eq!(
+            built_block.storage_changes.main_storage_changes,
+            expected.storage_changes.main_storage_changes
+        );
+        assert_eq!(
+            built_block.transaction_storage_root(),
+            expected.transaction_storage_root()
+        );
+    }
+
+    #[test]
+    fn storage_changes_after_finalize_are_cached() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics: VecDeque<_> = (0..5).map(transfer).collect();
+
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics.clone());
+        let state_root = block_builder.storage_root_after_finalize().unwrap();
+        let main_storage_changes = block_builder
+            .storage_changes_after_finalize()
+            .unwrap()
+            .main_storage_changes
+            .clone();
+        // The storage changes can only be collected once from the runtime api, thus the second
+        // call and the build must reuse the cached ones.
+        assert_eq!(
+            block_builder
+                .storage_changes_after_finalize()
+                .unwrap()
+                .main_storage_changes,
+            main_storage_changes
+        );
+        let built_block = block_builder.build(BuildMode::BestEffort).unwrap();
+
+        let expected = new_block_builder(&client, &backend, extrinsics)
+            .build(BuildMode::BestEffort)
+            .unwrap();
+
+        assert_eq!(state_root, *expected.block.header().state_root());
+        assert_eq!(
+            built_block.storage_changes.main_storage_changes,
+            main_storage_changes
+        );
+        assert_eq!(
+            main_storage_changes,
+            expected.storage_changes.main_storage_changes
+        );
+    }
+
+    #[test]
+    fn build_strict_after_storage_root_after_finalize_aborts_on_failed_extrinsic() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        // The nonce of the second transfer is in the future thus it fails the validity check.
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(5), transfer(1)]);
+
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics);
+        block_builder.storage_root_after_finalize().unwrap();
+        let res = block_builder.build(BuildMode::Strict);
+
+        assert!(matches!(
+            res,
+            Err(Error::ApplyExtrinsicFailed(ApplyExtrinsicFailed::Validity(
+                _
+            )))
+        ));
+    }
+
+    #[test]
+    fn remove_and_replace_extrinsic_before_execution() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        // The transfer with nonce 5 is replaced and the one with nonce 7 removed, leaving
+        // consecutive nonces.
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(5), transfer(7), transfer(2)]);
+
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics);
+        assert_eq!(block_builder.remove_extrinsic(2).unwrap(), transfer(7));
+        assert_eq!(
+            block_builder.replace_extrinsic(1, transfer(1)).unwrap(),
+            transfer(5)
+        );
+        assert!(block_builder.remove_extrinsic(3).is_err());
+
+        // The indices refer to the mutated queue.
+        let storage_changes = block_builder
+            .prepare_storage_changes_before(2, OnApplyFailure::Abort)
+            .unwrap();
+        let expected = new_block_builder(
+            &client,
+            &backend,
+            VecDeque::from(vec![transfer(0), transfer(1), transfer(2)]),
+        )
+        .prepare_storage_changes_before(2, OnApplyFailure::Abort)
+        .unwrap();
+        assert_eq!(
+            storage_changes.main_storage_changes,
+            expected.main_storage_changes
+        );
+
+        let res = block_builder.remove_extrinsic(0);
+        let Err(Error::Application(error)) = res else {
+            panic!("Extrinsics must not be modified after execution");
+        };
+        assert!(matches!(
+            error.downcast_ref::<BlockBuilderError>(),
+            Some(BlockBuilderError::ExtrinsicsAlreadyExecuted)
+        ));
+    }
+
+    #[test]
+    fn pending_extrinsics_returns_extrinsics_after_construction() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(1)]);
+        let block_builder = new_block_builder(&client, &backend, extrinsics.clone());
+
+        assert_eq!(block_builder.pending_extrinsics(), &extrinsics);
+    }
+
+    #[test]
+    fn build_unexecuted_matches_built_block() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics = vec![transfer(0), transfer(1)];
+        let built_block = new_block_builder(&client, &backend, extrinsics.clone().into())
+            .build(BuildMode::default())
+            .unwrap();
+        let header = built_block.block.header().clone();
+
+        let block = build_unexecuted::<Block>(header.clone(), extrinsics.clone()).unwrap();
+        assert_eq!(block, built_block.block);
+
+        // The header doesn't match a different body.
+        let res = build_unexecuted::<Block>(header, extrinsics[..1].to_vec());
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn estimated_header_and_body_size_add_up_to_block_size() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let block_builder = BlockBuilder::new(
+            &client,
+            client.info().best_hash,
+            client.info().best_number,
+            RecordProof::Yes,
+            Default::default(),
+            &backend,
+            VecDeque::from(vec![transfer(0), transfer(1)]),
+            None,
+        )
+        .unwrap();
+
+        let header_and_body_size =
+            block_builder.estimated_header_size() + block_builder.estimated_body_size();
+
+        assert_eq!(
+            block_builder.estimate_block_size(false),
+            header_and_body_size
+        );
+        assert_eq!(
+            block_builder.estimate_block_size(true),
+            header_and_body_size + block_builder.estimate_proof_size()
+        );
+    }
+
+    #[test]
+    fn pushed_digest_item_is_included_in_header() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let digest_item = DigestItem::Other(vec![1, 2, 3]);
+
+        let mut block_builder =
+            new_block_builder(&client, &backend, VecDeque::from(vec![transfer(0)]));
+        block_builder.push_digest_item(digest_item.clone()).unwrap();
+
+        let block = block_builder.build(BuildMode::Strict).unwrap().block;
+
+        assert!(block.header().digest().logs().contains(&digest_item));
+    }
+
+    #[test]
+    fn push_digest_item_fails_after_execution() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let setup_calls = Arc::new(Mutex::new(0));
+        let mut block_builder =
+            new_block_builder(&client, &backend, VecDeque::from(vec![transfer(0)]));
+        block_builder.set_api_setup({
+            let setup_calls = setup_calls.clone();
+            move |_api| *setup_calls.lock().unwrap() += 1
+        });
+
+        block_builder
+            .push_digest_item(DigestItem::Other(vec![1]))
+            .unwrap();
+        assert_eq!(*setup_calls.lock().unwrap(), 1);
+
+        block_builder.storage_root_after_finalize().unwrap();
+
+        let res = block_builder.push_digest_item(DigestItem::Other(vec![2]));
+        let Err(Error::Application(error)) = res else {
+            panic!("Digest must not be modified after execution");
+        };
+        assert!(matches!(
+            error.downcast_ref::<BlockBuilderError>(),
+            Some(BlockBuilderError::ExtrinsicsAlreadyExecuted)
+        ));
+    }
+
+    #[test]
+    fn build_fails_once_proof_exceeds_size_limit() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let res = BlockBuilder::new(
+            &client,
+            client.info().best_hash,
+            client.info().best_number,
+            RecordProof::YesWithSizeLimit(1),
+            Default::default(),
+            &backend,
+            VecDeque::from(vec![transfer(0), transfer(1)]),
+            None,
+        )
+        .unwrap()
+        .build(BuildMode::Strict);
+
+        let Err(Error::Application(error)) = res else {
+            panic!("Proof size limit must be exceeded");
+        };
+        assert!(matches!(
+            error.downcast_ref::<BlockBuilderError>(),
+            Some(BlockBuilderError::ProofSizeLimitExceeded {
+                extrinsic_index: 0,
+                size_limit: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn built_block_import_params_can_be_imported() {
+        let (mut client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let built_block = BlockBuilder::new(
+            &client,
+            client.info().best_hash,
+            client.info().best_number,
+            RecordProof::Yes,
+            Default::default(),
+            &backend,
+            VecDeque::from(vec![transfer(0)]),
+            None,
+        )
+        .unwrap()
+        .build(BuildMode::Strict)
+        .unwrap();
+
+        let header = built_block.block.header().clone();
+        let import_params =
+            built_block.into_import_params(BlockOrigin::Own, ForkChoiceStrategy::LongestChain);
+        assert!(import_params
+            .get_intermediate::<StorageProof>(STORAGE_PROOF_INTERMEDIATE_KEY)
+            .is_ok());
+
+        futures::executor::block_on(client.import_block(import_params)).unwrap();
+
+        assert_eq!(client.info().best_hash, header.hash());
+        let state_root = backend
+            .state_at(header.hash())
+            .unwrap()
+            .storage_root(std::iter::empty(), sp_core::storage::StateVersion::V1)
+            .0;
+        assert_eq!(&state_root, header.state_root());
+    }
+
+    #[test]
+    fn inherent_creation_error_contains_identifiers() {
+        let mut inherent_data = InherentData::new();
+        inherent_data.put_data(*b"testinh0", &1u32).unwrap();
+        inherent_data.put_data(*b"testinh1", &2u32).unwrap();
+
+        let identifiers = inherent_identifiers(&inherent_data);
+        assert_eq!(identifiers, vec![*b"testinh0", *b"testinh1"]);
+
+        let error = BlockBuilderError::InherentCreationFailed {
+            identifiers,
+            error: ApiError::Application("Bad inherent data".into()),
+        };
+        assert!(error.to_string().contains("testinh0, testinh1"));
+        assert!(error.to_string().contains("Bad inherent data"));
+    }
+
+    #[test]
+    fn remaining_extrinsics_preserve_original_order() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics: VecDeque<_> = (0..5).map(transfer).collect();
+
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics.clone());
+        let block_size_limit = block_builder.estimated_header_size()
+            + extrinsics
+                .iter()
+                .take(2)
+                .map(Encode::encoded_size)
+                .sum::<usize>();
+        block_builder.set_block_size_limit(block_size_limit);
+
+        let built_block = block_builder.build(BuildMode::Strict).unwrap();
+
+        assert_eq!(
+            built_block.block.extrinsics(),
+            &extrinsics.iter().take(2).cloned().collect::<Vec<_>>()[..]
+        );
+        assert_eq!(
+            built_block.remaining_extrinsics,
+            extrinsics.into_iter().skip(2).collect::<VecDeque<_>>()
+        );
+    }
+
+    #[test]
+    fn failed_extrinsics_count_towards_block_size_limit() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        // The nonce of the second transfer is in the future thus it fails the validity check.
+        let extrinsics = VecDeque::from(vec![transfer(0), transfer(5), transfer(1)]);
+
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics.clone());
+        let block_size_limit = block_builder.estimated_header_size()
+            + extrinsics
+                .iter()
+                .take(2)
+                .map(Encode::encoded_size)
+                .sum::<usize>();
+        block_builder.set_block_size_limit(block_size_limit);
+        block_builder.storage_root_after_finalize().unwrap();
+
+        assert_eq!(
+            block_builder.pending_extrinsics(),
+            &extrinsics.iter().take(2).cloned().collect::<VecDeque<_>>()
+        );
+        assert_eq!(
+            block_builder.remaining_extrinsics,
+            extrinsics.into_iter().skip(2).collect::<VecDeque<_>>()
+        );
+    }
+
+    #[test]
+    fn inherents_are_exempt_from_block_size_limit() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics: VecDeque<_> = (0..3).map(transfer).collect();
+
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics.clone());
+        // The test runtime creates no inherents, treat the first extrinsic as one.
+        block_builder.inherents_count = 1;
+        block_builder.set_block_size_limit(0);
+
+        let built_block = block_builder.build(BuildMode::Strict).unwrap();
+
+        assert_eq!(built_block.block.extrinsics(), &[transfer(0)]);
+        assert_eq!(
+            built_block.remaining_extrinsics,
+            extrinsics.into_iter().skip(1).collect::<VecDeque<_>>()
+        );
+    }
+
+    #[test]
+    fn inherents_are_exempt_from_block_weight_limit() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let extrinsics: VecDeque<_> = (0..3).map(transfer).collect();
+
+        let mut block_builder = new_block_builder(&client, &backend, extrinsics.clone());
+        // The test runtime creates no inherents, treat the first extrinsic as one.
+        block_builder.inherents_count = 1;
+        // The test runtime doesn't implement `DomainCoreApi`, meter every extrinsic with a fixed
+        // weight above the limit instead.
+        block_builder.weight_meter = Some(WeightMeter {
+            limit: Weight::from_parts(5, 0),
+            base_block: Weight::zero(),
+            base_extrinsic: Weight::zero(),
+            extrinsic_weight: |_, _, _| Ok(Weight::from_parts(10, 0)),
+            block_weight: None,
+        });
+
+        let built_block = block_builder.build(BuildMode::Strict).unwrap();
+
+        assert_eq!(built_block.block.extrinsics(), &[transfer(0)]);
+        assert_eq!(
+            built_block.remaining_extrinsics,
+            extrinsics.into_iter().skip(1).collect::<VecDeque<_>>()
+        );
+    }
+
+    #[test]
+    fn build_state_only_matches_build() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let new_block_builder = || {
+            BlockBuilder::new(
+                &client,
+                client.info().best_hash,
+                client.info().best_number,
+                RecordProof::Yes,
+                Default::default(),
+                &backend,
+                (0..3).map(transfer).collect(),
+                None,
+            )
+            .unwrap()
+        };
+
+        let (storage_changes, proof) = new_block_builder().build_state_only().unwrap();
+        let (_block, expected_storage_changes, expected_proof) = new_block_builder()
+            .build(BuildMode::BestEffort)
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(
+            storage_changes.main_storage_changes,
+            expected_storage_changes.main_storage_changes
+        );
+        assert_eq!(
+            storage_changes.transaction_storage_root,
+            expected_storage_changes.transaction_storage_root
+        );
+        assert_eq!(proof, expected_proof);
+    }
+
+    #[test]
+    fn pre_validation_reports_extrinsics_with_bad_signature() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let mut bad_signature = transfer(1);
+        if let Some((_, signature, _)) = bad_signature.signature.as_mut() {
+            *signature = sp_core::sr25519::Signature::from_raw([0u8; 64]);
         }
+
+        // The nonce of the last transfer is in the future, it can only be applied after the
+        // preceding transfers thus it must not be reported.
+        let extrinsics = VecDeque::from(vec![transfer(0), bad_signature, transfer(1), transfer(2)]);
+
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+
+        let block_builder = new_block_builder(&client, &backend, extrinsics.clone());
+        assert_eq!(block_builder.pre_validate_extrinsics(&thread_pool), vec![1]);
+
+        // The extrinsics are left untouched.
+        assert_eq!(block_builder.pending_extrinsics(), &extrinsics);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use sp_blockchain::HeaderBackend;
-    use sp_core::Blake2Hasher;
-    use sp_state_machine::Backend;
-    // TODO: Remove `substrate_test_runtime_client` dependency for faster build time
-    use std::collections::VecDeque;
-    use substrate_test_runtime_client::{DefaultTestClientBuilderExt, TestClientBuilderExt};
+    #[test]
+    fn build_with_inherent_check_accepts_valid_block() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let block = new_block_builder(&client, &backend, VecDeque::from(vec![transfer(0)]))
+            .build_with_inherent_check(InherentData::new())
+            .unwrap()
+            .block;
+
+        assert_eq!(block.extrinsics().len(), 1);
+    }
+
+    #[test]
+    fn verify_proof() {
+        let (client, backend) =
+            substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
+
+        let build_block = |record_proof| {
+            BlockBuilder::new(
+                &client,
+                client.info().best_hash,
+                client.info().best_number,
+                record_proof,
+                Default::default(),
+                &*backend,
+                VecDeque::from(vec![transfer(0)]),
+                None,
+            )
+            .unwrap()
+            .build(BuildMode::Strict)
+            .unwrap()
+        };
+
+        let parent_state_root = *client
+            .header(client.info().best_hash)
+            .unwrap()
+            .unwrap()
+            .state_root();
+
+        let mut block = build_block(RecordProof::Yes);
+        assert_eq!(block.parent_state_root, parent_state_root);
+        block.verify_proof().unwrap();
 
-    // TODO: Unlock this test, it got broken in https://github.com/subspace/subspace/pull/1548 and
-    //  doesn't run on Windows at all
+        // The proof is recorded against the parent state, not the state after the block
+        let proof = block.proof.clone().expect("Proof is build on request");
+        assert!(
+            sp_state_machine::create_proof_check_backend::<Blake2Hasher>(
+                block.transaction_storage_root(),
+                proof
+            )
+            .is_err()
+        );
+
+        block.proof = Some(StorageProof::empty());
+        assert!(block.verify_proof().is_err());
+
+        // Nothing to verify without proof
+        build_block(RecordProof::No).verify_proof().unwrap();
+    }
+
+    // The proof is recorded against the parent state, checking it against the post-block
+    // `transaction_storage_root` fails before the runtime code is even looked up, which is what
+    // broke this test.
     #[test]
-    #[ignore]
     fn block_building_storage_proof_does_not_include_runtime_by_default() {
         let (client, backend) =
             substrate_test_runtime_client::TestClientBuilder::new().build_with_backend();
@@ -357,16 +2827,19 @@ mod tests {
             Default::default(),
         )
         .unwrap()
-        .build()
+        .build(BuildMode::BestEffort)
         .unwrap();
 
+        let storage_root = *client
+            .header(client.info().best_hash)
+            .unwrap()
+            .unwrap()
+            .state_root();
         let proof = block.proof.expect("Proof is build on request");
 
-        let backend = sp_state_machine::create_proof_check_backend::<Blake2Hasher>(
-            block.storage_changes.transaction_storage_root,
-            proof,
-        )
-        .unwrap();
+        let backend =
+            sp_state_machine::create_proof_check_backend::<Blake2Hasher>(storage_root, proof)
+                .unwrap();
 
         assert!(backend
             .storage(sp_core::storage::well_known_keys::CODE)
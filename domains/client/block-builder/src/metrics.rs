@@ -0,0 +1,92 @@
+//! Block builder metrics
+
+use std::time::Duration;
+use substrate_prometheus_endpoint::{
+    exponential_buckets, register, Counter, Histogram, HistogramOpts, PrometheusError, Registry,
+    U64,
+};
+
+/// Prometheus metrics of the [`BlockBuilder`](crate::BlockBuilder).
+///
+/// The metrics are shared by all clones, so one instance can be created for the node's registry
+/// and passed to every block builder with [`BlockBuilder::set_metrics`](crate::BlockBuilder::set_metrics).
+#[derive(Debug, Clone)]
+pub struct BlockBuilderMetrics {
+    block_initialization_time: Histogram,
+    extrinsics_execution_time: Histogram,
+    block_finalization_time: Histogram,
+    rejected_extrinsics: Counter<U64>,
+    statically_invalid_extrinsics: Counter<U64>,
+}
+
+impl BlockBuilderMetrics {
+    /// Create the metrics and register them in the given `registry`.
+    pub fn new(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            block_initialization_time: register(
+                Histogram::with_opts(
+                    HistogramOpts::new(
+                        "domain_block_builder_block_initialization_time",
+                        "Time spent initializing the block and creating its inherents, in seconds",
+                    )
+                    .buckets(exponential_buckets(0.0001, 2.0, 15)?),
+                )?,
+                registry,
+            )?,
+            extrinsics_execution_time: register(
+                Histogram::with_opts(
+                    HistogramOpts::new(
+                        "domain_block_builder_extrinsics_execution_time",
+                        "Time spent applying the extrinsics of the block, in seconds",
+                    )
+                    .buckets(exponential_buckets(0.0001, 2.0, 15)?),
+                )?,
+                registry,
+            )?,
+            block_finalization_time: register(
+                Histogram::with_opts(
+                    HistogramOpts::new(
+                        "domain_block_builder_block_finalization_time",
+                        "Time spent finalizing the block, in seconds",
+                    )
+                    .buckets(exponential_buckets(0.0001, 2.0, 15)?),
+                )?,
+                registry,
+            )?,
+            rejected_extrinsics: register(
+                Counter::new(
+                    "domain_block_builder_rejected_extrinsics",
+                    "Total number of extrinsics that failed to apply",
+                )?,
+                registry,
+            )?,
+            statically_invalid_extrinsics: register(
+                Counter::new(
+                    "domain_block_builder_statically_invalid_extrinsics",
+                    "Total number of extrinsics reported as statically invalid by the pre-validation",
+                )?,
+                registry,
+            )?,
+        })
+    }
+
+    pub(crate) fn observe_block_initialization_time(&self, time: Duration) {
+        self.block_initialization_time.observe(time.as_secs_f64());
+    }
+
+    pub(crate) fn observe_extrinsics_execution_time(&self, time: Duration) {
+        self.extrinsics_execution_time.observe(time.as_secs_f64());
+    }
+
+    pub(crate) fn observe_block_finalization_time(&self, time: Duration) {
+        self.block_finalization_time.observe(time.as_secs_f64());
+    }
+
+    pub(crate) fn inc_rejected_extrinsics(&self, count: usize) {
+        self.rejected_extrinsics.inc_by(count as u64);
+    }
+
+    pub(crate) fn inc_statically_invalid_extrinsics(&self, count: usize) {
+        self.statically_invalid_extrinsics.inc_by(count as u64);
+    }
+}
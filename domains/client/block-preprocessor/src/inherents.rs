@@ -12,12 +12,13 @@
 //! Deriving these extrinsics during fraud proof verification should be possible since
 //! verification environment will have access to consensus chain.
 
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_domains::{DomainId, DomainsApi, DomainsDigestItem};
 use sp_inherents::{CreateInherentDataProviders, InherentData, InherentDataProvider};
-use sp_runtime::traits::{Block as BlockT, Header};
+use sp_runtime::traits::{Block as BlockT, Hash as HashT, Header, NumberFor};
 use sp_timestamp::InherentType;
+use sp_version::RuntimeVersion;
 use std::error::Error;
 use std::sync::Arc;
 
@@ -88,6 +89,9 @@ where
 }
 
 /// Returns new upgraded runtime if upgraded did happen in the provided consensus block.
+///
+/// The new runtime code is checked against the code hash of the upgrade that was pending at the
+/// parent consensus block, if the consensus runtime supports querying it.
 pub fn extract_domain_runtime_upgrade_code<CClient, CBlock, Block>(
     consensus_client: &Arc<CClient>,
     consensus_block_hash: CBlock::Hash,
@@ -127,12 +131,59 @@ where
                 )))
             })?;
 
+        if let Some((_, _, pending_code_hash)) = pending_domain_runtime_upgrade::<_, _, Block>(
+            consensus_client,
+            *header.parent_hash(),
+            domain_id,
+        )? {
+            let new_code_hash =
+                <<CBlock::Header as Header>::Hashing as HashT>::hash(&new_domain_runtime);
+            if new_code_hash != pending_code_hash {
+                return Err(sp_blockchain::Error::Application(Box::from(format!(
+                    "New runtime code of {domain_id:?} doesn't match the pending upgrade, \
+                    expected code hash {pending_code_hash:?}, got {new_code_hash:?}"
+                ))));
+            }
+        }
+
         Ok(Some(new_domain_runtime))
     } else {
         Ok(None)
     }
 }
 
+/// Returns the consensus block number, target version and code hash of the pending upgrade of
+/// the runtime used by the domain, if any.
+///
+/// Always returns `None` if the consensus runtime doesn't support querying the pending upgrade.
+pub fn pending_domain_runtime_upgrade<CClient, CBlock, Block>(
+    consensus_client: &Arc<CClient>,
+    consensus_block_hash: CBlock::Hash,
+    domain_id: DomainId,
+) -> Result<Option<(NumberFor<CBlock>, RuntimeVersion, CBlock::Hash)>, sp_blockchain::Error>
+where
+    CClient: ProvideRuntimeApi<CBlock> + HeaderBackend<CBlock>,
+    CClient::Api: DomainsApi<CBlock, Block::Header>,
+    CBlock: BlockT,
+    Block: BlockT,
+{
+    let runtime_api = consensus_client.runtime_api();
+    let supports_pending_runtime_upgrade = runtime_api
+        .api_version::<dyn DomainsApi<CBlock, Block::Header>>(consensus_block_hash)?
+        .map_or(false, |version| version >= 2);
+    if !supports_pending_runtime_upgrade {
+        return Ok(None);
+    }
+
+    let runtime_id = runtime_api
+        .runtime_id(consensus_block_hash, domain_id)?
+        .ok_or(sp_blockchain::Error::Application(Box::from(format!(
+            "No RuntimeId found for {domain_id:?}"
+        ))))?;
+
+    Ok(runtime_api.pending_runtime_upgrade(consensus_block_hash, runtime_id)?)
+}
+
 #[derive(Debug)]
 pub struct CreateInherentDataProvider<CClient, CBlock: BlockT> {
     consensus_client: Arc<CClient>,
@@ -3,15 +3,15 @@ use crate::fraud_proof::FraudProofGenerator;
 use crate::utils::{DomainBlockImportNotification, DomainImportNotificationSinks};
 use crate::ExecutionReceiptFor;
 use codec::{Decode, Encode};
-use domain_block_builder::{BlockBuilder, BuiltBlock, RecordProof};
+use domain_block_builder::{
+    BlockBuilderMetrics, BlockBuilderProvider, BuildMode, ClientBlockBuilderProvider, RecordProof,
+};
 use domain_block_preprocessor::inherents::get_inherent_data;
 use domain_block_preprocessor::PreprocessResult;
 use domain_runtime_primitives::DomainCoreApi;
+use rayon::ThreadPool;
 use sc_client_api::{AuxStore, BlockBackend, Finalizer, ProofProvider};
-use sc_consensus::{
-    BlockImportParams, ForkChoiceStrategy, ImportResult, SharedBlockImport, StateAction,
-    StorageChanges,
-};
+use sc_consensus::{BlockImportParams, ForkChoiceStrategy, ImportResult, SharedBlockImport};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::{HashAndNumber, HeaderBackend, HeaderMetadata};
@@ -22,8 +22,10 @@ use sp_domains::merkle_tree::MerkleTree;
 use sp_domains::{BundleValidity, DomainId, DomainsApi, ExecutionReceipt, HeaderHashingFor};
 use sp_domains_fraud_proof::fraud_proof::{FraudProof, ValidBundleProof};
 use sp_domains_fraud_proof::FraudProofApi;
+use sp_runtime::generic::BlockId;
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, One, Zero};
 use sp_runtime::{Digest, Saturating};
+use sp_transaction_pool::runtime_api::TaggedTransactionQueue;
 use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::sync::Arc;
@@ -63,6 +65,10 @@ where
     pub(crate) block_import: SharedBlockImport<Block>,
     pub(crate) import_notification_sinks: DomainImportNotificationSinks<Block, CBlock>,
     pub(crate) consensus_network_sync_oracle: Arc<dyn SyncOracle + Send + Sync>,
+    /// Thread pool used to pre-validate the extrinsics of a domain block, if enabled.
+    pub(crate) extrinsics_pre_validation_thread_pool: Option<Arc<ThreadPool>>,
+    pub(crate) block_builder_metrics: Option<BlockBuilderMetrics>,
+    pub(crate) block_builder_provider: ClientBlockBuilderProvider<Client, Backend>,
 }
 
 impl<Block, CBlock, Client, CClient, Backend> Clone
@@ -82,6 +88,11 @@ where
             block_import: self.block_import.clone(),
             import_notification_sinks: self.import_notification_sinks.clone(),
             consensus_network_sync_oracle: self.consensus_network_sync_oracle.clone(),
+            extrinsics_pre_validation_thread_pool: self
+                .extrinsics_pre_validation_thread_pool
+                .clone(),
+            block_builder_metrics: self.block_builder_metrics.clone(),
+            block_builder_provider: self.block_builder_provider.clone(),
         }
     }
 }
@@ -112,8 +123,10 @@ where
         + ProvideRuntimeApi<Block>
         + Finalizer<Block, Backend>
         + 'static,
-    Client::Api:
-        DomainCoreApi<Block> + sp_block_builder::BlockBuilder<Block> + sp_api::ApiExt<Block>,
+    Client::Api: DomainCoreApi<Block>
+        + sp_block_builder::BlockBuilder<Block>
+        + sp_api::ApiExt<Block>
+        + TaggedTransactionQueue<Block>,
     CClient: HeaderBackend<CBlock>
         + HeaderMetadata<CBlock, Error = sp_blockchain::Error>
         + BlockBackend<CBlock>
@@ -303,7 +316,6 @@ where
         } = self
             .build_and_import_block(
                 parent_hash,
-                parent_number,
                 extrinsics,
                 fork_choice,
                 inherent_digests,
@@ -408,30 +420,30 @@ where
     async fn build_and_import_block(
         &self,
         parent_hash: Block::Hash,
-        parent_number: NumberFor<Block>,
         extrinsics: VecDeque<Block::Extrinsic>,
         fork_choice: ForkChoiceStrategy,
         inherent_digests: Digest,
         inherent_data: sp_inherents::InherentData,
     ) -> Result<DomainBlockBuildResult<Block>, sp_blockchain::Error> {
-        let block_builder = BlockBuilder::new(
-            &*self.client,
-            parent_hash,
-            parent_number,
-            RecordProof::No,
+        let mut block_builder = self.block_builder_provider.new_block_at_with_extrinsics(
+            &BlockId::Hash(parent_hash),
             inherent_digests,
-            &*self.backend,
+            RecordProof::No,
             extrinsics,
             Some(inherent_data),
         )?;
 
-        let BuiltBlock {
-            block,
-            storage_changes,
-            proof: _,
-        } = block_builder.build()?;
+        if let Some(metrics) = &self.block_builder_metrics {
+            block_builder.set_metrics(metrics.clone());
+        }
+
+        if let Some(thread_pool) = &self.extrinsics_pre_validation_thread_pool {
+            block_builder.pre_validate_extrinsics(thread_pool);
+        }
+
+        let built_block = block_builder.build(BuildMode::BestEffort)?;
 
-        let (header, body) = block.deconstruct();
+        let header = built_block.block.header();
         let state_root = *header.state_root();
         let extrinsics_root = *header.extrinsics_root();
         let header_hash = header.hash();
@@ -445,12 +457,7 @@ where
             } else {
                 BlockOrigin::Own
             };
-            let mut import_block = BlockImportParams::new(block_origin, header);
-            import_block.body = Some(body);
-            import_block.state_action =
-                StateAction::ApplyChanges(StorageChanges::Changes(storage_changes));
-            import_block.fork_choice = Some(fork_choice);
-            import_block
+            built_block.into_import_params(block_origin, fork_choice)
         };
         self.import_domain_block(block_import_params).await?;
 
@@ -1,7 +1,9 @@
 use crate::aux_schema::BundleMismatchType;
 use crate::ExecutionReceiptFor;
 use codec::{Decode, Encode};
-use domain_block_builder::{BlockBuilder, RecordProof};
+use domain_block_builder::{
+    BlockBuilderProvider, ClientBlockBuilderProvider, OnApplyFailure, RecordProof,
+};
 use domain_runtime_primitives::opaque::AccountId;
 use domain_runtime_primitives::{CheckExtrinsicsValidityError, DomainCoreApi};
 use sc_client_api::{AuxStore, BlockBackend, ProofProvider};
@@ -73,6 +75,7 @@ pub struct FraudProofGenerator<Block, CBlock, Client, CClient, Backend, E> {
     consensus_client: Arc<CClient>,
     backend: Arc<Backend>,
     code_executor: Arc<E>,
+    block_builder_provider: ClientBlockBuilderProvider<Client, Backend>,
     _phantom: PhantomData<(Block, CBlock)>,
 }
 
@@ -85,6 +88,7 @@ impl<Block, CBlock, Client, CClient, Backend, E> Clone
             consensus_client: self.consensus_client.clone(),
             backend: self.backend.clone(),
             code_executor: self.code_executor.clone(),
+            block_builder_provider: self.block_builder_provider.clone(),
             _phantom: self._phantom,
         }
     }
@@ -123,6 +127,10 @@ where
         code_executor: Arc<E>,
     ) -> Self {
         Self {
+            block_builder_provider: ClientBlockBuilderProvider::new(
+                client.clone(),
+                backend.clone(),
+            ),
             client,
             consensus_client,
             backend,
@@ -404,13 +412,10 @@ where
         let max_extrinsic_index = extrinsics.len() - 1;
         let encoded_extrinsics: Vec<_> = extrinsics.iter().map(Encode::encode).collect();
 
-        let block_builder = BlockBuilder::new(
-            &*self.client,
-            parent_header.hash(),
-            *parent_header.number(),
-            RecordProof::No,
+        let mut block_builder = self.block_builder_provider.new_block_at_with_extrinsics(
+            &BlockId::Hash(parent_header.hash()),
             inherent_digests.clone(),
-            &*self.backend,
+            RecordProof::No,
             extrinsics.into(),
             None,
         )?;
@@ -442,10 +447,10 @@ where
                     },
                 )?;
 
-                (
-                    Some(block_builder.prepare_storage_changes_before(extrinsic_index)?),
-                    target_extrinsic.clone(),
-                )
+                let storage_changes = block_builder
+                    .prepare_storage_changes_before(extrinsic_index, OnApplyFailure::Skip)?;
+
+                (Some(storage_changes), target_extrinsic.clone())
             }
             ExecutionPhase::FinalizeBlock { .. } => (
                 Some(block_builder.prepare_storage_changes_before_finalize_block()?),
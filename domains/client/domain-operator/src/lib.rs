@@ -99,6 +99,7 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 use subspace_core_primitives::Randomness;
 use subspace_runtime_primitives::Balance;
+use substrate_prometheus_endpoint::Registry;
 
 pub type ExecutionReceiptFor<Block, CBlock> = ExecutionReceipt<
     NumberFor<CBlock>,
@@ -177,6 +178,11 @@ pub struct OperatorParams<
     pub domain_confirmation_depth: NumberFor<Block>,
     pub block_import: SharedBlockImport<Block>,
     pub skip_empty_bundle_production: bool,
+    /// Number of threads used to pre-validate the extrinsics of a domain block in parallel before
+    /// they are applied, `0` disables the pre-validation.
+    pub extrinsics_pre_validation_threads: usize,
+    /// Registry to report the domain block builder metrics to, if any.
+    pub prometheus_registry: Option<Registry>,
 }
 
 pub(crate) fn load_execution_receipt_by_domain_hash<Block, CBlock, Client>(
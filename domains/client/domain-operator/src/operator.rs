@@ -4,9 +4,11 @@ use crate::domain_bundle_producer::DomainBundleProducer;
 use crate::domain_bundle_proposer::DomainBundleProposer;
 use crate::fraud_proof::FraudProofGenerator;
 use crate::{DomainImportNotifications, NewSlotNotification, OperatorParams};
+use domain_block_builder::{BlockBuilderMetrics, ClientBlockBuilderProvider};
 use domain_runtime_primitives::DomainCoreApi;
 use futures::channel::mpsc;
 use futures::{FutureExt, Stream};
+use rayon::ThreadPoolBuilder;
 use sc_client_api::{
     AuxStore, BlockBackend, BlockImportNotification, BlockchainEvents, Finalizer, ProofProvider,
 };
@@ -144,6 +146,37 @@ where
             params.code_executor,
         );
 
+        let extrinsics_pre_validation_thread_pool = match params.extrinsics_pre_validation_threads {
+            0 => None,
+            num_threads => {
+                let thread_pool = ThreadPoolBuilder::new()
+                    .num_threads(num_threads)
+                    .thread_name(|index| format!("domain-pre-validation-{index}"))
+                    .build()
+                    .map_err(|error| {
+                        sp_consensus::Error::Other(
+                            format!(
+                                "Failed to create thread pool for extrinsics pre-validation: \
+                                {error}"
+                            )
+                            .into(),
+                        )
+                    })?;
+                Some(Arc::new(thread_pool))
+            }
+        };
+
+        let block_builder_metrics = params
+            .prometheus_registry
+            .as_ref()
+            .map(BlockBuilderMetrics::new)
+            .transpose()
+            .map_err(|error| {
+                sp_consensus::Error::Other(
+                    format!("Failed to register block builder metrics: {error}").into(),
+                )
+            })?;
+
         let domain_block_processor = DomainBlockProcessor {
             domain_id: params.domain_id,
             domain_created_at: params.domain_created_at,
@@ -154,6 +187,12 @@ where
             block_import: params.block_import,
             import_notification_sinks: Default::default(),
             consensus_network_sync_oracle: params.consensus_network_sync_oracle.clone(),
+            extrinsics_pre_validation_thread_pool,
+            block_builder_metrics,
+            block_builder_provider: ClientBlockBuilderProvider::new(
+                params.client.clone(),
+                params.backend.clone(),
+            ),
         };
 
         let receipts_checker = ReceiptsChecker {
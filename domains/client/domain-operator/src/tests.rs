@@ -5,6 +5,7 @@ use crate::fraud_proof::{FraudProofGenerator, TraceDiffType};
 use crate::tests::TxPoolError::InvalidTransaction as TxPoolInvalidTransaction;
 use crate::utils::OperatorSlotInfo;
 use codec::{Decode, Encode};
+use domain_block_builder::ClientBlockBuilderProvider;
 use domain_runtime_primitives::{DomainCoreApi, Hash};
 use domain_test_primitives::{OnchainStateApi, TimestampApi};
 use domain_test_service::evm_domain_test_runtime::{Header, UncheckedExtrinsic};
@@ -102,6 +103,71 @@ async fn test_domain_instance_bootstrapper() {
         .expect("3 consensus blocks produced successfully");
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_domain_runtime_upgrade_apis() {
+    let directory = TempDir::new().expect("Must be able to create temporary directory");
+
+    let mut builder = sc_cli::LoggerBuilder::new("");
+    builder.with_colors(false);
+    let _ = builder.init();
+
+    let tokio_handle = tokio::runtime::Handle::current();
+
+    // Start Ferdie
+    let mut ferdie = MockConsensusNode::run(
+        tokio_handle.clone(),
+        Ferdie,
+        BasePath::new(directory.path().join("ferdie")),
+    );
+
+    // Run Alice (a evm domain authority node)
+    let alice = domain_test_service::DomainNodeBuilder::new(
+        tokio_handle.clone(),
+        Alice,
+        BasePath::new(directory.path().join("alice")),
+    )
+    .build_evm_node(Role::Authority, GENESIS_DOMAIN_ID, &mut ferdie)
+    .await;
+
+    produce_blocks!(ferdie, alice, 3).await.unwrap();
+
+    let best_hash = ferdie.client.info().best_hash;
+    let runtime_api = ferdie.client.runtime_api();
+    let runtime_id = runtime_api
+        .runtime_id(best_hash, GENESIS_DOMAIN_ID)
+        .unwrap()
+        .unwrap();
+    let runtime_code = runtime_api
+        .domain_runtime_code(best_hash, GENESIS_DOMAIN_ID)
+        .unwrap()
+        .unwrap();
+
+    let runtime_obj = runtime_api
+        .domain_runtime_object(best_hash, runtime_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(runtime_obj.runtime_upgrades, 0);
+    assert_eq!(runtime_obj.hash, BlakeTwo256::hash(&runtime_code));
+    assert_eq!(runtime_obj.code_size, runtime_code.len() as u32);
+
+    // No upgrade is scheduled for the genesis domain runtime.
+    assert_eq!(
+        runtime_api
+            .pending_runtime_upgrade(best_hash, runtime_id)
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        domain_block_preprocessor::inherents::pending_domain_runtime_upgrade::<
+            _,
+            _,
+            domain_test_service::evm_domain_test_runtime::Block,
+        >(&ferdie.client, best_hash, GENESIS_DOMAIN_ID)
+        .unwrap(),
+        None
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_domain_chain_fork_choice() {
     let directory = TempDir::new().expect("Must be able to create temporary directory");
@@ -324,6 +390,12 @@ async fn test_processing_empty_consensus_block() {
         block_import: SharedBlockImport::new(alice.client.clone()),
         import_notification_sinks: Default::default(),
         consensus_network_sync_oracle: ferdie.sync_service.clone(),
+        extrinsics_pre_validation_thread_pool: None,
+        block_builder_metrics: None,
+        block_builder_provider: ClientBlockBuilderProvider::new(
+            alice.client.clone(),
+            alice.backend.clone(),
+        ),
     };
 
     let domain_genesis_hash = alice.client.info().best_hash;
@@ -3851,3 +3923,89 @@ async fn test_domain_chain_storage_price_should_be_aligned_with_the_consensus_ch
         .unwrap();
     assert_eq!(consensus_chain_byte_fee, operator_consensus_chain_byte_fee);
 }
+
+async fn query_runtime_configs_rpc(
+    consensus_node: &MockConsensusNode,
+    method: &str,
+) -> serde_json::Value {
+    let request = format!(r#"{{"jsonrpc":"2.0","id":0,"method":"{method}","params":[]}}"#);
+    let (response, _) = consensus_node
+        .rpc_handlers
+        .rpc_query(&request)
+        .await
+        .unwrap_or_else(|error| panic!("Failed to query {method}: {error:?}"));
+    let mut response: serde_json::Value =
+        serde_json::from_str(&response).expect("Response must be valid json");
+    response["result"].take()
+}
+
+async fn runtime_configs_from_rpc(
+    consensus_node: &MockConsensusNode,
+) -> sp_runtime_configs::RuntimeConfigs<subspace_core_primitives::BlockNumber> {
+    let result = query_runtime_configs_rpc(consensus_node, "runtimeConfigs_get").await;
+    serde_json::from_value(result).expect("Result must be runtime configs")
+}
+
+async fn config_change_log_from_rpc(
+    consensus_node: &MockConsensusNode,
+) -> Vec<(
+    sp_runtime_configs::ConfigKey,
+    subspace_core_primitives::BlockNumber,
+    Option<sp_runtime::AccountId32>,
+)> {
+    let result = query_runtime_configs_rpc(consensus_node, "runtimeConfigs_changeLog").await;
+    serde_json::from_value(result).expect("Result must be config change log")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_runtime_configs_rpc_follows_sudo_changes() {
+    let directory = TempDir::new().expect("Must be able to create temporary directory");
+
+    let mut builder = sc_cli::LoggerBuilder::new("");
+    builder.with_colors(false);
+    let _ = builder.init();
+
+    let tokio_handle = tokio::runtime::Handle::current();
+
+    // Start Ferdie
+    let mut ferdie = MockConsensusNode::run(
+        tokio_handle.clone(),
+        Ferdie,
+        BasePath::new(directory.path().join("ferdie")),
+    );
+
+    let runtime_configs = runtime_configs_from_rpc(&ferdie).await;
+    assert!(runtime_configs.enable_balance_transfers);
+    assert!(!runtime_configs.enable_non_root_calls);
+    assert!(config_change_log_from_rpc(&ferdie).await.is_empty());
+
+    // Flip the flag through sudo and check the rpc reports the new value at the best block
+    let tx = ferdie.construct_extrinsic(
+        Sr25519Keyring::Alice,
+        0,
+        pallet_sudo::Call::sudo {
+            call: Box::new(subspace_test_runtime::RuntimeCall::RuntimeConfigs(
+                pallet_runtime_configs::Call::set_enable_non_root_calls {
+                    enable_non_root_calls: true,
+                },
+            )),
+        },
+    );
+    ferdie
+        .produce_block_with_extrinsics(vec![tx.into()])
+        .await
+        .unwrap();
+
+    let runtime_configs = runtime_configs_from_rpc(&ferdie).await;
+    assert!(runtime_configs.enable_non_root_calls);
+
+    // The change is logged at the best block without an account as it was made by root
+    assert_eq!(
+        config_change_log_from_rpc(&ferdie).await,
+        vec![(
+            sp_runtime_configs::ConfigKey::EnableNonRootCalls,
+            ferdie.client.info().best_number,
+            None
+        )]
+    );
+}
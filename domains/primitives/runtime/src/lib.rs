@@ -282,6 +282,10 @@ sp_api::decl_runtime_apis! {
         /// Return the consumed weight of the block
         #[api_version(2)]
         fn block_weight() -> Weight;
+
+        /// Return the `BlockWeights` limits of the runtime
+        #[api_version(3)]
+        fn block_weights() -> BlockWeights;
     }
 }
 
@@ -872,7 +872,7 @@ impl_runtime_apis! {
         }
     }
 
-    #[api_version(2)]
+    #[api_version(3)]
     impl domain_runtime_primitives::DomainCoreApi<Block> for Runtime {
         fn extract_signer(
             extrinsics: Vec<<Block as BlockT>::Extrinsic>,
@@ -988,6 +988,10 @@ impl_runtime_apis! {
             System::block_weight().total()
         }
 
+        fn block_weights() -> BlockWeights {
+            RuntimeBlockWeights::get()
+        }
+
         fn construct_consensus_chain_byte_fee_extrinsic(transaction_byte_fee: Balance) -> <Block as BlockT>::Extrinsic {
             UncheckedExtrinsic::new_unsigned(
                 pallet_block_fees::Call::set_next_consensus_chain_byte_fee{ transaction_byte_fee }.into()
@@ -224,6 +224,7 @@ where
     pub domain_message_receiver: TracingUnboundedReceiver<ChainTxPoolMsg>,
     pub provider: Provider,
     pub skip_empty_bundle_production: bool,
+    pub extrinsics_pre_validation_threads: usize,
 }
 
 /// Builds service for a domain full node.
@@ -320,6 +321,7 @@ where
         domain_message_receiver,
         provider,
         skip_empty_bundle_production,
+        extrinsics_pre_validation_threads,
     } = domain_params;
 
     // TODO: Do we even need block announcement on domain node?
@@ -393,6 +395,8 @@ where
         })
     };
 
+    let prometheus_registry = domain_config.prometheus_registry().cloned();
+
     let rpc_handlers = sc_service::spawn_tasks(SpawnTasksParams {
         rpc_builder,
         client: client.clone(),
@@ -440,6 +444,8 @@ where
             domain_confirmation_depth,
             block_import,
             skip_empty_bundle_production,
+            extrinsics_pre_validation_threads,
+            prometheus_registry,
         },
     )
     .await?;
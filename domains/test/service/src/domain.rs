@@ -226,6 +226,7 @@ where
             domain_message_receiver,
             provider: DefaultProvider,
             skip_empty_bundle_production,
+            extrinsics_pre_validation_threads: 0,
             maybe_operator_id,
         };
 
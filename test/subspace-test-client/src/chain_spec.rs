@@ -12,9 +12,9 @@ use std::marker::PhantomData;
 use std::num::NonZeroU32;
 use subspace_runtime_primitives::{AccountId, Balance, BlockNumber, Signature};
 use subspace_test_runtime::{
-    AllowAuthoringBy, BalancesConfig, DomainsConfig, EnableRewardsAt, MaxDomainBlockSize,
-    MaxDomainBlockWeight, RuntimeGenesisConfig, SubspaceConfig, SudoConfig, SystemConfig,
-    VestingConfig, SSC, WASM_BINARY,
+    AllowAuthoringBy, BalancesConfig, ConfirmationDepthK, DomainsConfig, EnableRewardsAt,
+    MaxDomainBlockSize, MaxDomainBlockWeight, RuntimeConfigsConfig, RuntimeGenesisConfig,
+    SubspaceConfig, SudoConfig, SystemConfig, VestingConfig, SSC, WASM_BINARY,
 };
 
 /// The `ChainSpec` parameterized for subspace test runtime.
@@ -119,6 +119,12 @@ fn create_genesis_config(
             phantom: PhantomData,
         },
         vesting: VestingConfig { vesting },
+        runtime_configs: RuntimeConfigsConfig {
+            // Same as the `ConstRuntimeConfigs` used by the test runtime.
+            enable_balance_transfers: true,
+            confirmation_depth_k: ConfirmationDepthK::get(),
+            ..Default::default()
+        },
         domains: DomainsConfig {
             genesis_domain: Some(GenesisDomain {
                 runtime_name: "evm".to_owned(),
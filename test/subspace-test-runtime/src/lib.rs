@@ -33,13 +33,13 @@ use domain_runtime_primitives::{
 use frame_support::inherent::ProvideInherent;
 use frame_support::traits::{
     ConstU128, ConstU16, ConstU32, ConstU64, ConstU8, Currency, ExistenceRequirement, Get,
-    Imbalance, VariantCount, WithdrawReasons,
+    Imbalance, Nothing, VariantCount, WithdrawReasons,
 };
 use frame_support::weights::constants::{RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND};
 use frame_support::weights::{ConstantMultiplier, IdentityFee, Weight};
 use frame_support::{construct_runtime, parameter_types, PalletId};
 use frame_system::limits::{BlockLength, BlockWeights};
-use frame_system::EnsureNever;
+use frame_system::{EnsureNever, EnsureRoot};
 use pallet_balances::NegativeImbalance;
 pub use pallet_subspace::{AllowAuthoringBy, EnableRewardsAt};
 use pallet_transporter::EndpointHandler;
@@ -71,6 +71,7 @@ use sp_runtime::transaction_validity::{
     InvalidTransaction, TransactionSource, TransactionValidity, TransactionValidityError,
 };
 use sp_runtime::{create_runtime_str, generic, AccountId32, ApplyExtrinsicResult, Perbill};
+use sp_runtime_configs::ConstRuntimeConfigs;
 use sp_std::collections::btree_map::BTreeMap;
 use sp_std::iter::Peekable;
 use sp_std::marker::PhantomData;
@@ -393,7 +394,11 @@ impl pallet_transaction_fees::Config for Runtime {
     type BlockchainHistorySize = BlockchainHistorySize;
     type Currency = Balances;
     type FindBlockRewardAddress = Subspace;
-    type DynamicCostOfStorage = ConstBool<{ !cfg!(feature = "do-not-enforce-cost-of-storage") }>;
+    type RuntimeConfigs = ConstRuntimeConfigs<
+        { !cfg!(feature = "do-not-enforce-cost-of-storage") },
+        true,
+        { ConfirmationDepthK::get() },
+    >;
     type WeightInfo = ();
 }
 
@@ -611,6 +616,10 @@ parameter_types! {
     pub const MaxBundlesPerBlock: u32 = 10;
     pub const DomainInstantiationDeposit: Balance = 100 * SSC;
     pub const MaxDomainNameLength: u32 = 32;
+    pub const MaxRuntimeUpgradeHistory: u32 = 16;
+    pub const MaxRuntimeUpgradesPerBlock: u32 = 5;
+    pub const MaxRuntimeNameLength: u32 = 32;
+    pub const MaxDomainRuntimeCodeSize: u32 = 4 * 1024 * 1024;
     pub const BlockTreePruningDepth: u32 = 16;
     pub const StakeWithdrawalLockingPeriod: BlockNumber = 20;
     pub const StakeEpochDuration: DomainNumber = 5;
@@ -649,7 +658,15 @@ impl pallet_domains::Config for Runtime {
     type MaxNominators = MaxNominators;
     type Randomness = Subspace;
     type SudoId = SudoId;
+    type RuntimeUpgradeOrigin = EnsureRoot<AccountId>;
     type MinNominatorStake = MinNominatorStake;
+    type MaxRuntimeUpgradeHistory = MaxRuntimeUpgradeHistory;
+    type MaxRuntimeUpgradesPerBlock = MaxRuntimeUpgradesPerBlock;
+    type MaxRuntimeNameLength = MaxRuntimeNameLength;
+    type MaxDomainRuntimeCodeSize = MaxDomainRuntimeCodeSize;
+    // Speed up runtime upgrades in tests.
+    type AllowImmediateRuntimeUpgrade = ConstBool<true>;
+    type FrozenDomains = Nothing;
 }
 
 parameter_types! {
@@ -668,6 +685,38 @@ impl pallet_rewards::Config for Runtime {
     type OnReward = ();
 }
 
+parameter_types! {
+    pub const NonDisableableCalls: &'static [(&'static str, &'static str)] = &[
+        ("Timestamp", "set"),
+        ("Subspace", "store_segment_headers"),
+        ("Sudo", "sudo"),
+        ("Sudo", "sudo_unchecked_weight"),
+        ("Sudo", "sudo_as"),
+        ("Sudo", "set_key"),
+    ];
+}
+
+// NOTE: the runtime configs are only exposed through the runtime api, the other pallets still
+// use `ConstRuntimeConfigs`.
+impl pallet_runtime_configs::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MinConfirmationDepthK = ConstU32<1>;
+    type MaxConfirmationDepthK = ConstU32<14_400>;
+    type MaxScheduledConfigChangesPerBlock = ConstU32<16>;
+    type MaxTransferAllowlistSize = ConstU32<100>;
+    type MaxFrozenDomains = ConstU32<100>;
+    type EnableDomainsOrigin = EnsureRoot<AccountId>;
+    type EnableDynamicCostOfStorageOrigin = EnsureRoot<AccountId>;
+    type EnableBalanceTransfersOrigin = EnsureRoot<AccountId>;
+    type EnableNonRootCallsOrigin = EnsureRoot<AccountId>;
+    type ConfirmationDepthKOrigin = EnsureRoot<AccountId>;
+    type DisabledCallsOrigin = EnsureRoot<AccountId>;
+    type NonDisableableCalls = NonDisableableCalls;
+    type MaintenanceModeOrigin = EnsureRoot<AccountId>;
+    type FrozenDomainsOrigin = EnsureRoot<AccountId>;
+}
+
 parameter_types! {
     // This value doesn't matter, we don't use it (`VestedTransferOrigin = EnsureNever` below).
     pub const MinVestedTransfer: Balance = 0;
@@ -719,6 +768,7 @@ construct_runtime!(
         Utility: pallet_utility = 8,
 
         Domains: pallet_domains = 11,
+        RuntimeConfigs: pallet_runtime_configs = 14,
 
         Vesting: orml_vesting = 7,
 
@@ -1074,6 +1124,17 @@ impl_runtime_apis! {
         }
     }
 
+    impl sp_runtime_configs::RuntimeConfigsApi<Block> for Runtime {
+        fn runtime_configs() -> sp_runtime_configs::RuntimeConfigs<BlockNumber> {
+            RuntimeConfigs::runtime_configs()
+        }
+
+        fn config_change_log(
+        ) -> Vec<(sp_runtime_configs::ConfigKey, BlockNumber, Option<AccountId>)> {
+            RuntimeConfigs::config_change_log()
+        }
+    }
+
     impl sp_consensus_subspace::SubspaceApi<Block, FarmerPublicKey> for Runtime {
         fn pot_parameters() -> PotParameters {
             Subspace::pot_parameters()
@@ -1167,6 +1228,7 @@ impl_runtime_apis! {
         }
     }
 
+    #[api_version(2)]
     impl sp_domains::DomainsApi<Block, DomainHeader> for Runtime {
         fn submit_bundle_unsigned(
             opaque_bundle: OpaqueBundle<NumberFor<Block>, <Block as BlockT>::Hash, DomainHeader, Balance>,
@@ -1276,6 +1338,22 @@ impl_runtime_apis! {
         fn consensus_chain_byte_fee() -> Balance {
             DOMAIN_STORAGE_FEE_MULTIPLIER * TransactionFees::transaction_byte_fee()
         }
+
+        fn runtime_registry_storage_version() -> u16 {
+            Domains::runtime_registry_storage_version()
+        }
+
+        fn scheduled_runtime_upgrades() -> Vec<(BlockNumber, sp_domains::RuntimeId, RuntimeVersion)> {
+            Domains::scheduled_runtime_upgrades()
+        }
+
+        fn domain_runtime_object(runtime_id: sp_domains::RuntimeId) -> Option<sp_domains::RuntimeObjectInfo<BlockNumber, Hash>> {
+            Domains::domain_runtime_object(runtime_id)
+        }
+
+        fn pending_runtime_upgrade(runtime_id: sp_domains::RuntimeId) -> Option<(BlockNumber, RuntimeVersion, Hash)> {
+            Domains::pending_runtime_upgrade(runtime_id)
+        }
     }
 
     impl sp_domains::BundleProducerElectionApi<Block, Balance> for Runtime {
@@ -23,7 +23,6 @@ use cross_domain_message_gossip::{xdm_gossip_peers_set_config, GossipWorkerBuild
 use domain_runtime_primitives::opaque::{Block as DomainBlock, Header as DomainHeader};
 use futures::channel::mpsc;
 use futures::{Future, StreamExt};
-use jsonrpsee::RpcModule;
 use parking_lot::Mutex;
 use sc_block_builder::BlockBuilderBuilder;
 use sc_client_api::execution_extensions::ExtensionsFactory;
@@ -36,6 +35,7 @@ use sc_consensus::{
 };
 use sc_network::config::{NetworkConfiguration, TransportConfig};
 use sc_network::{multiaddr, NotificationService};
+use sc_runtime_configs_rpc::{RuntimeConfigsRpc, RuntimeConfigsRpcApiServer};
 use sc_service::config::{
     DatabaseSource, KeystoreConfig, MultiaddrWithPeerId, WasmExecutionMethod,
     WasmtimeInstantiationStrategy,
@@ -61,11 +61,11 @@ use sp_domains_fraud_proof::{FraudProofExtension, FraudProofHostFunctionsImpl};
 use sp_externalities::Extensions;
 use sp_inherents::{InherentData, InherentDataProvider};
 use sp_keyring::Sr25519Keyring;
-use sp_runtime::generic::{BlockId, Digest};
+use sp_runtime::generic::{self, BlockId, Digest};
 use sp_runtime::traits::{
     BlakeTwo256, Block as BlockT, Hash as HashT, Header as HeaderT, NumberFor,
 };
-use sp_runtime::{DigestItem, OpaqueExtrinsic};
+use sp_runtime::{DigestItem, MultiAddress, OpaqueExtrinsic};
 use sp_subspace_mmr::host_functions::{SubspaceMmrExtension, SubspaceMmrHostFunctionsImpl};
 use sp_timestamp::Timestamp;
 use std::error::Error;
@@ -76,11 +76,13 @@ use std::sync::Arc;
 use std::time;
 use subspace_core_primitives::{Randomness, Solution};
 use subspace_runtime_primitives::opaque::Block;
-use subspace_runtime_primitives::{AccountId, Balance, Hash};
+use subspace_runtime_primitives::{AccountId, Balance, Hash, Nonce};
 use subspace_service::transaction_pool::FullPool;
 use subspace_service::{FullSelectChain, RuntimeExecutor};
 use subspace_test_client::{chain_spec, Backend, Client};
-use subspace_test_runtime::{RuntimeApi, RuntimeCall, UncheckedExtrinsic, SLOT_DURATION};
+use subspace_test_runtime::{
+    Runtime, RuntimeApi, RuntimeCall, SignedExtra, UncheckedExtrinsic, SLOT_DURATION, VERSION,
+};
 
 type FraudProofFor<Block, DomainBlock> =
     FraudProof<NumberFor<Block>, <Block as BlockT>::Hash, <DomainBlock as BlockT>::Header>;
@@ -340,7 +342,10 @@ impl MockConsensusNode {
             keystore: keystore_container.keystore(),
             task_manager: &mut task_manager,
             transaction_pool: transaction_pool.clone(),
-            rpc_builder: Box::new(|_, _| Ok(RpcModule::new(()))),
+            rpc_builder: {
+                let client = client.clone();
+                Box::new(move |_, _| Ok(RuntimeConfigsRpc::new(client.clone()).into_rpc()))
+            },
             backend: backend.clone(),
             system_rpc_tx,
             config,
@@ -800,6 +805,50 @@ impl MockConsensusNode {
         Ok(())
     }
 
+    /// Construct an immortal extrinsic signed by `caller` with the given `nonce` that can be
+    /// applied to the test runtime.
+    pub fn construct_extrinsic(
+        &self,
+        caller: Sr25519Keyring,
+        nonce: Nonce,
+        function: impl Into<RuntimeCall>,
+    ) -> UncheckedExtrinsic {
+        let function = function.into();
+        let genesis_hash = self.client.info().genesis_hash;
+        let extra: SignedExtra = (
+            frame_system::CheckNonZeroSender::<Runtime>::new(),
+            frame_system::CheckSpecVersion::<Runtime>::new(),
+            frame_system::CheckTxVersion::<Runtime>::new(),
+            frame_system::CheckGenesis::<Runtime>::new(),
+            frame_system::CheckMortality::<Runtime>::from(generic::Era::Immortal),
+            frame_system::CheckNonce::<Runtime>::from(nonce),
+            frame_system::CheckWeight::<Runtime>::new(),
+            pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+        );
+        let raw_payload = generic::SignedPayload::<RuntimeCall, SignedExtra>::from_raw(
+            function.clone(),
+            extra.clone(),
+            (
+                (),
+                VERSION.spec_version,
+                VERSION.transaction_version,
+                genesis_hash,
+                genesis_hash,
+                (),
+                (),
+                (),
+            ),
+        );
+        let signature = raw_payload.using_encoded(|e| caller.sign(e));
+
+        UncheckedExtrinsic::new_signed(
+            function,
+            MultiAddress::Id(caller.to_account_id()),
+            signature.into(),
+            extra,
+        )
+    }
+
     /// Produce `n` number of blocks.
     #[sc_tracing::logging::prefix_logs_with(self.log_prefix)]
     pub async fn produce_blocks(&mut self, n: u64) -> Result<(), Box<dyn Error>> {